@@ -0,0 +1,245 @@
+//! Parameter sweeps over the left size, the right size and the mapping density.
+//!
+//! Every sweep point benchmarks setup, `Witness::new`, `generate_statement`, `prove` and
+//! `verify` separately, and the `backend` group compares `prove` and `verify` of the
+//! log-derivative and grand-product backends over the left sizes. Besides criterion's own
+//! per-benchmark `estimates.json`, the run writes `report.json` into criterion's output
+//! directory with one entry per benchmark measured in this run, for comparison across commits:
+//!
+//! ```sh
+//! cargo bench --bench isep
+//! cp target/criterion/report.json report-$(git rev-parse --short HEAD).json
+//! ```
+//!
+//! Criterion's `--save-baseline <name>` and `--baseline <name>` flags compare against earlier
+//! runs directly.
+use ark_bn254::{Bn254, Fr};
+use ark_isep::prover::{prove, prove_with_backend, Backend};
+use ark_isep::public_parameters::PublicParameters;
+use ark_isep::verifier::{verify, verify_with_backend};
+use ark_isep::witness::Witness;
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::SeedableRng;
+use ark_std::UniformRand;
+use criterion::{BenchmarkId, Criterion};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// One point of a parameter sweep.
+#[derive(Clone, Copy)]
+struct Config {
+    log_size_left: usize,
+    log_size_right: usize,
+    /// The fraction of left positions that are mapped is `1 / 2^log_inv_density`.
+    log_inv_density: usize,
+}
+
+impl Config {
+    fn label(&self) -> String {
+        format!(
+            "l=2^{},r=2^{},d=1/{}",
+            self.log_size_left,
+            self.log_size_right,
+            1 << self.log_inv_density
+        )
+    }
+
+    /// Maps every `2^log_inv_density`-th left position to evenly spread right positions.
+    fn mappings(&self) -> BTreeMap<usize, usize> {
+        let num_mappings = 1 << (self.log_size_left - self.log_inv_density);
+        let stride_right = (1 << self.log_size_right) / num_mappings;
+        assert!(stride_right > 0, "more mappings than right positions");
+
+        (0..num_mappings)
+            .map(|k| (k << self.log_inv_density, k * stride_right))
+            .collect()
+    }
+}
+
+/// Sweeps with the name of the varied parameter.
+fn sweeps() -> Vec<(&'static str, Vec<Config>)> {
+    let config = |log_size_left, log_size_right, log_inv_density| Config {
+        log_size_left,
+        log_size_right,
+        log_inv_density,
+    };
+
+    vec![
+        ("left_size", (8..=12).step_by(2).map(|l| config(l, 12, 2)).collect()),
+        ("right_size", (10..=14).step_by(2).map(|r| config(10, r, 2)).collect()),
+        ("density", [4, 2, 0].into_iter().map(|d| config(10, 12, d)).collect()),
+    ]
+}
+
+/// Full ids of the benchmarks registered in this run, as criterion records them in
+/// `benchmark.json`.
+type Ids = BTreeSet<String>;
+
+fn bench_config(c: &mut Criterion, ids: &mut Ids, sweep: &str, config: Config) {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let size_left_values = 1 << config.log_size_left;
+    let size_right_values = 1 << config.log_size_right;
+    let mappings = config.mappings();
+    let mut id = |operation: &str| {
+        ids.insert(format!("{sweep}/{operation}/{}", config.label()));
+        BenchmarkId::new(operation, config.label())
+    };
+
+    let build = |rng: &mut StdRng| {
+        PublicParameters::<Bn254>::builder()
+            .size_left_values(size_left_values)
+            .size_right_values(size_right_values)
+            .position_mappings(&mappings)
+            .build(rng)
+            .unwrap()
+    };
+    let pp = build(rng);
+
+    let left_values = (0..size_left_values).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let mut right_values = (0..size_right_values).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    mappings.iter().for_each(|(&i, &j)| right_values[j] = left_values[i]);
+    let witness = Witness::new(&pp, &left_values, &right_values).unwrap();
+    let statement = witness.generate_statement(&pp).unwrap();
+    let proof = prove(&pp, &witness, &statement).unwrap();
+
+    let mut group = c.benchmark_group(sweep);
+    group.sample_size(10);
+    group.bench_function(id("setup"), |b| b.iter(|| build(rng)));
+    group.bench_function(id("witness_new"), |b| {
+        b.iter(|| Witness::new(&pp, &left_values, &right_values).unwrap())
+    });
+    group.bench_function(id("generate_statement"), |b| {
+        b.iter(|| witness.generate_statement(&pp).unwrap())
+    });
+    group.bench_function(id("prove"), |b| {
+        b.iter(|| prove(&pp, &witness, &statement).unwrap())
+    });
+    group.bench_function(id("verify"), |b| {
+        b.iter(|| verify(&pp, &statement, &proof).unwrap())
+    });
+    group.finish();
+}
+
+/// Compares the backends on the same parameters and witness.
+fn bench_backends(c: &mut Criterion, ids: &mut Ids, config: Config) {
+    let rng = &mut StdRng::seed_from_u64(0);
+    let size_left_values = 1 << config.log_size_left;
+    let size_right_values = 1 << config.log_size_right;
+    let mappings = config.mappings();
+
+    let pp = PublicParameters::<Bn254>::builder()
+        .size_left_values(size_left_values)
+        .size_right_values(size_right_values)
+        .position_mappings(&mappings)
+        .build(rng)
+        .unwrap();
+    let left_values = (0..size_left_values).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let mut right_values = (0..size_right_values).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    mappings.iter().for_each(|(&i, &j)| right_values[j] = left_values[i]);
+    let witness = Witness::new(&pp, &left_values, &right_values).unwrap();
+    let statement = witness.generate_statement(&pp).unwrap();
+
+    let mut id = |operation: String| {
+        ids.insert(format!("backend/{operation}/{}", config.label()));
+        BenchmarkId::new(operation, config.label())
+    };
+    let mut group = c.benchmark_group("backend");
+    group.sample_size(10);
+    for (name, backend) in [("log_derivative", Backend::LogDerivative), ("grand_product", Backend::GrandProduct)] {
+        let proof = prove_with_backend(&pp, &witness, &statement, backend).unwrap();
+        group.bench_function(id(format!("prove/{name}")), |b| {
+            b.iter(|| prove_with_backend(&pp, &witness, &statement, backend).unwrap())
+        });
+        group.bench_function(id(format!("verify/{name}")), |b| {
+            b.iter(|| verify_with_backend(&pp, &statement, &proof).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn criterion_dir() -> PathBuf {
+    std::env::var_os("CRITERION_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            std::env::var_os("CARGO_TARGET_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("target"))
+                .join("criterion")
+        })
+}
+
+/// Collects criterion's `benchmark.json` and `estimates.json` of the benchmarks in `dir` that
+/// are in `ids` and were measured since `started`. Results of earlier runs, of renamed
+/// benchmarks, of benchmarks skipped by a filter and of other bench targets are left out.
+fn collect_estimates(dir: &Path, ids: &Ids, started: SystemTime, report: &mut Vec<Value>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let read = |file: &str| -> Option<Value> {
+            serde_json::from_str(&std::fs::read_to_string(path.join("new").join(file)).ok()?).ok()
+        };
+        if let (Some(benchmark), Some(estimates)) = (read("benchmark.json"), read("estimates.json")) {
+            let is_current = std::fs::metadata(path.join("new").join("estimates.json"))
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified >= started);
+            if !is_current || !benchmark["full_id"].as_str().is_some_and(|id| ids.contains(id)) {
+                continue;
+            }
+            let point = |name: &str| estimates[name]["point_estimate"].clone();
+            report.push(json!({
+                "id": benchmark["full_id"],
+                "mean_ns": point("mean"),
+                "median_ns": point("median"),
+                "std_dev_ns": point("std_dev"),
+            }));
+        } else {
+            collect_estimates(&path, ids, started, report);
+        }
+    }
+}
+
+fn write_report(ids: &Ids, started: SystemTime) {
+    let dir = criterion_dir();
+    let mut report = Vec::new();
+    // Only this suite's group directories.
+    let groups: BTreeSet<&str> = ids.iter().filter_map(|id| id.split('/').next()).collect();
+    for group in groups {
+        collect_estimates(&dir.join(group), ids, started, &mut report);
+    }
+    report.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+
+    let report = json!({
+        "crate_version": env!("CARGO_PKG_VERSION"),
+        "benchmarks": report,
+    });
+    let path = dir.join("report.json");
+    if std::fs::create_dir_all(&dir).is_ok()
+        && std::fs::write(&path, serde_json::to_string_pretty(&report).unwrap()).is_ok()
+    {
+        println!("wrote {}", path.display());
+    }
+}
+
+fn main() {
+    let started = SystemTime::now();
+    let mut ids = Ids::new();
+    let mut criterion = Criterion::default().configure_from_args();
+    for (sweep, configs) in sweeps() {
+        for config in configs {
+            bench_config(&mut criterion, &mut ids, sweep, config);
+        }
+    }
+    let (_, left_size_configs) = sweeps().into_iter().find(|&(sweep, _)| sweep == "left_size").unwrap();
+    for config in left_size_configs {
+        bench_backends(&mut criterion, &mut ids, config);
+    }
+    criterion.final_summary();
+    write_report(&ids, started);
+}
@@ -0,0 +1,246 @@
+use ark_ec::pairing::Pairing;
+use ark_isep::prover::Proof;
+use ark_isep::public_parameters::PublicParameters;
+use ark_isep::statement::Statement;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+/// Magic bytes at the start of every artifact file.
+const MAGIC: &[u8; 4] = b"ISEP";
+/// Version of the artifact envelope. Bump it whenever the layout of a payload changes.
+pub const FORMAT_VERSION: u16 = 3;
+const HEADER_SIZE: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Curve {
+    Bn254,
+    #[value(name = "bls12_381")]
+    Bls12_381,
+    #[value(name = "bls12_377")]
+    Bls12_377,
+}
+
+impl Curve {
+    fn id(self) -> u8 {
+        match self {
+            Curve::Bn254 => 1,
+            Curve::Bls12_381 => 2,
+            Curve::Bls12_377 => 3,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Curve::Bn254),
+            2 => Some(Curve::Bls12_381),
+            3 => Some(Curve::Bls12_377),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Curve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Curve::Bn254 => write!(f, "bn254"),
+            Curve::Bls12_381 => write!(f, "bls12_381"),
+            Curve::Bls12_377 => write!(f, "bls12_377"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    PublicParameters,
+    Statement,
+    Proof,
+}
+
+impl Kind {
+    fn id(self) -> u8 {
+        match self {
+            Kind::PublicParameters => 1,
+            Kind::Statement => 2,
+            Kind::Proof => 3,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Kind::PublicParameters),
+            2 => Some(Kind::Statement),
+            3 => Some(Kind::Proof),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::PublicParameters => write!(f, "public parameters"),
+            Kind::Statement => write!(f, "statement"),
+            Kind::Proof => write!(f, "proof"),
+        }
+    }
+}
+
+/// Header of an artifact file: `ISEP`, format version (u16 LE), curve id, kind id.
+#[derive(Clone, Copy, Debug)]
+pub struct Header {
+    pub version: u16,
+    pub curve: Curve,
+    pub kind: Kind,
+}
+
+/// Types that can be stored in an artifact file.
+pub trait Artifact: CanonicalSerialize + CanonicalDeserialize {
+    const KIND: Kind;
+}
+
+impl<P: Pairing> Artifact for PublicParameters<P> {
+    const KIND: Kind = Kind::PublicParameters;
+}
+
+impl<P: Pairing> Artifact for Statement<P> {
+    const KIND: Kind = Kind::Statement;
+}
+
+impl<P: Pairing> Artifact for Proof<P> {
+    const KIND: Kind = Kind::Proof;
+}
+
+pub fn write<A: Artifact>(path: &Path, curve: Curve, artifact: &A) -> Result<(), Box<dyn Error>> {
+    let mut buf = Vec::with_capacity(HEADER_SIZE + artifact.compressed_size());
+    buf.extend_from_slice(MAGIC);
+    buf.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    buf.push(curve.id());
+    buf.push(A::KIND.id());
+    artifact.serialize_compressed(&mut buf)?;
+    std::fs::write(path, buf)
+        .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+
+    Ok(())
+}
+
+/// Reads the header and the raw payload of an artifact file.
+pub fn read_raw(path: &Path) -> Result<(Header, Vec<u8>), Box<dyn Error>> {
+    let buf = std::fs::read(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    if buf.len() < HEADER_SIZE || &buf[..4] != MAGIC {
+        return Err(format!("{} is not an ISEP artifact", path.display()).into());
+    }
+    let version = u16::from_le_bytes([buf[4], buf[5]]);
+    if version != FORMAT_VERSION {
+        return Err(format!(
+            "{} has format version {}, expected {}",
+            path.display(),
+            version,
+            FORMAT_VERSION
+        )
+        .into());
+    }
+    let curve = Curve::from_id(buf[6])
+        .ok_or_else(|| format!("{} has unknown curve id {}", path.display(), buf[6]))?;
+    let kind = Kind::from_id(buf[7])
+        .ok_or_else(|| format!("{} has unknown artifact kind {}", path.display(), buf[7]))?;
+
+    Ok((Header { version, curve, kind }, buf[HEADER_SIZE..].to_vec()))
+}
+
+/// Reads an artifact of type `A`, checking that it was produced for `curve`.
+pub fn read<A: Artifact>(path: &Path, curve: Curve) -> Result<A, Box<dyn Error>> {
+    let (header, payload) = read_raw(path)?;
+    if header.kind != A::KIND {
+        return Err(format!("{} holds {}, expected {}", path.display(), header.kind, A::KIND).into());
+    }
+    if header.curve != curve {
+        return Err(format!("{} is for curve {}, expected {}", path.display(), header.curve, curve).into());
+    }
+
+    A::deserialize_compressed(&payload[..])
+        .map_err(|e| format!("failed to deserialize {}: {}", path.display(), e).into())
+}
+
+/// Returns the curve recorded in the header of an artifact file.
+pub fn curve_of(path: &Path) -> Result<Curve, Box<dyn Error>> {
+    Ok(read_raw(path)?.0.curve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read, read_raw, write, Curve, Kind, FORMAT_VERSION};
+    use crate::temp_file;
+    use ark_isep::ark_bn254::Bn254;
+    use ark_isep::public_parameters::PublicParameters;
+    use ark_serialize::CanonicalSerialize;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn write_parameters(name: &str) -> (PathBuf, PublicParameters<Bn254>) {
+        let pp = PublicParameters::<Bn254>::builder()
+            .size_left_values(2)
+            .size_right_values(4)
+            .position_mappings(&BTreeMap::from([(1, 3)]))
+            .build(&mut rand::thread_rng())
+            .unwrap();
+        let path = temp_file(name, b"");
+        write(&path, Curve::Bn254, &pp).unwrap();
+
+        (path, pp)
+    }
+
+    /// Overwrites one header byte of the artifact at `path`.
+    fn patch(path: &PathBuf, offset: usize, byte: u8) {
+        let mut buf = std::fs::read(path).unwrap();
+        buf[offset] = byte;
+        std::fs::write(path, buf).unwrap();
+    }
+
+    #[test]
+    fn header_round_trip() {
+        let (path, pp) = write_parameters("round_trip.isep");
+
+        let (header, payload) = read_raw(&path).unwrap();
+        assert_eq!(header.version, FORMAT_VERSION);
+        assert_eq!(header.curve, Curve::Bn254);
+        assert_eq!(header.kind, Kind::PublicParameters);
+        let mut expected = Vec::new();
+        pp.serialize_compressed(&mut expected).unwrap();
+        assert_eq!(payload, expected);
+
+        let read_back = read::<PublicParameters<Bn254>>(&path, Curve::Bn254).unwrap();
+        assert_eq!(read_back.positions_left, pp.positions_left);
+        assert_eq!(read_back.g1_affine_position_mappings, pp.g1_affine_position_mappings);
+    }
+
+    #[test]
+    fn header_rejects_wrong_version() {
+        let (path, _) = write_parameters("wrong_version.isep");
+        patch(&path, 4, (FORMAT_VERSION + 1) as u8);
+
+        let err = read_raw(&path).unwrap_err().to_string();
+        assert!(err.contains(&format!("expected {}", FORMAT_VERSION)), "{}", err);
+    }
+
+    #[test]
+    fn header_rejects_wrong_curve() {
+        let (path, _) = write_parameters("wrong_curve.isep");
+
+        // A known curve other than the one requested.
+        let err = read::<PublicParameters<Bn254>>(&path, Curve::Bls12_381).unwrap_err().to_string();
+        assert!(err.contains("is for curve bn254, expected bls12_381"), "{}", err);
+
+        // An unknown curve tag.
+        patch(&path, 6, 42);
+        let err = read_raw(&path).unwrap_err().to_string();
+        assert!(err.contains("unknown curve id 42"), "{}", err);
+    }
+
+    #[test]
+    fn header_rejects_other_files() {
+        let path = temp_file("not_an_artifact.isep", b"ISEX\x02\x00\x01\x01");
+        assert!(read_raw(&path).unwrap_err().to_string().contains("is not an ISEP artifact"));
+    }
+}
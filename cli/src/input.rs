@@ -0,0 +1,176 @@
+use ark_ff::PrimeField;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+
+/// Reads a left→right position mapping.
+///
+/// JSON files hold either an object `{"left": right, ...}` or an array of pairs
+/// `[[left, right], ...]`. Any other file is read as CSV with one `left,right` pair per line;
+/// a non-numeric first line is treated as a header.
+pub fn read_mapping(path: &Path) -> Result<BTreeMap<usize, usize>, Box<dyn Error>> {
+    let content = read_to_string(path)?;
+    let mut mappings = BTreeMap::new();
+    let mut insert = |left: usize, right: usize| -> Result<(), Box<dyn Error>> {
+        if mappings.insert(left, right).is_some() {
+            return Err(format!("left position {} is mapped more than once", left).into());
+        }
+        Ok(())
+    };
+
+    if is_json(path) {
+        match serde_json::from_str::<Value>(&content)? {
+            Value::Object(object) => {
+                for (left, right) in object {
+                    insert(left.trim().parse()?, json_to_usize(&right)?)?;
+                }
+            }
+            Value::Array(pairs) => {
+                for pair in pairs {
+                    match pair.as_array().map(Vec::as_slice) {
+                        Some([left, right]) => insert(json_to_usize(left)?, json_to_usize(right)?)?,
+                        _ => return Err(format!("expected a [left, right] pair, got {}", pair).into()),
+                    }
+                }
+            }
+            other => return Err(format!("expected a JSON object or array, got {}", other).into()),
+        }
+    } else {
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [left, right] = fields[..] else {
+                return Err(format!("line {}: expected `left,right`", line_number + 1).into());
+            };
+            match (left.parse(), right.parse()) {
+                (Ok(left), Ok(right)) => insert(left, right)?,
+                _ if line_number == 0 => continue,
+                _ => return Err(format!("line {}: invalid position", line_number + 1).into()),
+            }
+        }
+    }
+
+    Ok(mappings)
+}
+
+/// Reads a vector of field elements in decimal.
+///
+/// JSON files hold an array of numbers or decimal strings. Any other file holds values
+/// separated by commas, whitespace or newlines.
+pub fn read_values<F: PrimeField>(path: &Path) -> Result<Vec<F>, Box<dyn Error>> {
+    let content = read_to_string(path)?;
+    let tokens: Vec<String> = if is_json(path) {
+        match serde_json::from_str::<Value>(&content)? {
+            Value::Array(values) => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::String(s) => Ok(s),
+                    Value::Number(n) => Ok(n.to_string()),
+                    other => Err(format!("expected a number or a string, got {}", other)),
+                })
+                .collect::<Result<_, _>>()?,
+            other => return Err(format!("expected a JSON array, got {}", other).into()),
+        }
+    } else {
+        content
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(str::to_owned)
+            .collect()
+    };
+
+    tokens
+        .iter()
+        .map(|token| {
+            F::from_str(token.trim())
+                .map_err(|_| format!("invalid field element: {}", token).into())
+        })
+        .collect()
+}
+
+fn read_to_string(path: &Path) -> Result<String, Box<dyn Error>> {
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e).into())
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+fn json_to_usize(value: &Value) -> Result<usize, Box<dyn Error>> {
+    match value {
+        Value::Number(n) => n
+            .as_u64()
+            .map(|n| n as usize)
+            .ok_or_else(|| format!("invalid position: {}", n).into()),
+        Value::String(s) => Ok(s.trim().parse()?),
+        other => Err(format!("invalid position: {}", other).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_mapping, read_values};
+    use crate::temp_file;
+    use ark_isep::ark_bn254::Fr;
+    use std::collections::BTreeMap;
+
+    fn mapping(name: &str, content: &str) -> Result<BTreeMap<usize, usize>, String> {
+        read_mapping(&temp_file(name, content.as_bytes())).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn json_mapping() {
+        let expected = BTreeMap::from([(0, 4), (3, 1)]);
+        assert_eq!(mapping("object.json", r#"{"0": 4, " 3 ": "1"}"#).unwrap(), expected);
+        assert_eq!(mapping("pairs.json", "[[0, 4], [3, 1]]").unwrap(), expected);
+    }
+
+    #[test]
+    fn json_mapping_rejects_malformed_input() {
+        assert!(mapping("triple.json", "[[0, 4, 5]]").unwrap_err().contains("[left, right] pair"));
+        assert!(mapping("scalar.json", "7").unwrap_err().contains("object or array"));
+        assert!(mapping("negative.json", "[[0, -1]]").unwrap_err().contains("invalid position"));
+        assert!(mapping("key.json", r#"{"a": 1}"#).is_err());
+        assert!(mapping("truncated.json", "[[0, 4]").is_err());
+    }
+
+    #[test]
+    fn json_mapping_rejects_duplicate_left_index() {
+        let err = mapping("duplicate.json", "[[2, 4], [2, 5]]").unwrap_err();
+        assert!(err.contains("left position 2 is mapped more than once"), "{}", err);
+    }
+
+    #[test]
+    fn csv_mapping() {
+        let expected = BTreeMap::from([(0, 4), (3, 1)]);
+        assert_eq!(mapping("plain.csv", "0,4\n3, 1\n").unwrap(), expected);
+        assert_eq!(mapping("header.csv", "left,right\n0,4\n\n3,1").unwrap(), expected);
+    }
+
+    #[test]
+    fn csv_mapping_rejects_malformed_rows() {
+        assert_eq!(mapping("fields.csv", "0,4\n1,2,3").unwrap_err(), "line 2: expected `left,right`");
+        assert_eq!(mapping("value.csv", "0,4\n1,x").unwrap_err(), "line 2: invalid position");
+        assert_eq!(mapping("single.csv", "5").unwrap_err(), "line 1: expected `left,right`");
+    }
+
+    #[test]
+    fn csv_mapping_rejects_duplicate_left_index() {
+        let err = mapping("duplicate.csv", "1,2\n1,3").unwrap_err();
+        assert!(err.contains("left position 1 is mapped more than once"), "{}", err);
+    }
+
+    #[test]
+    fn values() {
+        let expected = vec![Fr::from(1u64), Fr::from(22u64), Fr::from(333u64)];
+        assert_eq!(read_values::<Fr>(&temp_file("values.json", br#"[1, "22", 333]"#)).unwrap(), expected);
+        assert_eq!(read_values::<Fr>(&temp_file("values.txt", b"1, 22\n333 ")).unwrap(), expected);
+        assert!(read_values::<Fr>(&temp_file("bad_values.txt", b"1 two")).is_err());
+        assert!(read_values::<Fr>(&temp_file("bad_values.json", br#"{"0": 1}"#)).is_err());
+    }
+}
@@ -0,0 +1,282 @@
+//! `isep`: command-line tool to set up, commit, prove, verify and inspect ISEP artifacts.
+//!
+//! Every artifact file starts with a versioned header that records the curve it was produced
+//! for; see [`artifact`].
+mod artifact;
+mod input;
+
+use ark_ec::pairing::Pairing;
+use ark_isep::ark_bls12_377::Bls12_377;
+use ark_isep::ark_bls12_381::Bls12_381;
+use ark_isep::ark_bn254::Bn254;
+use ark_isep::prover::{prove, Proof};
+use ark_isep::public_parameters::PublicParameters;
+use ark_isep::statement::Statement;
+use ark_isep::verifier::verify;
+use ark_isep::witness::Witness;
+use artifact::{Curve, Kind};
+use clap::{Parser, Subcommand};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "isep", version, about = "Prove equality of values shared between two committed vectors")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build public parameters from a left→right position mapping.
+    ///
+    /// The SRS is generated locally from fresh randomness, so the parameters are only as
+    /// trustworthy as the machine that runs this command.
+    Setup {
+        #[arg(long, value_enum, default_value_t = Curve::Bn254)]
+        curve: Curve,
+        /// Mapping file: JSON object/array of pairs, or CSV with `left,right` lines.
+        #[arg(long)]
+        mapping: PathBuf,
+        /// Number of left values (a power of two).
+        #[arg(long)]
+        left_size: usize,
+        /// Number of right values (a power of two).
+        #[arg(long)]
+        right_size: usize,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Commit to left and right vectors and write the statement.
+    Commit {
+        #[arg(long)]
+        params: PathBuf,
+        /// Left values: JSON array or comma/whitespace separated decimals.
+        #[arg(long)]
+        left: PathBuf,
+        /// Right values: JSON array or comma/whitespace separated decimals.
+        #[arg(long)]
+        right: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Prove that the mapped values of the left and right vectors are equal.
+    Prove {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long)]
+        left: PathBuf,
+        #[arg(long)]
+        right: PathBuf,
+        /// Statement to prove; it must match the committed vectors. Recomputed if omitted.
+        #[arg(long)]
+        statement: Option<PathBuf>,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Verify a proof against public parameters and a statement.
+    Verify {
+        #[arg(long)]
+        params: PathBuf,
+        #[arg(long)]
+        statement: PathBuf,
+        #[arg(long)]
+        proof: PathBuf,
+    },
+    /// Pretty-print any artifact file.
+    Inspect { file: PathBuf },
+}
+
+/// Calls the generic function `$f` instantiated with the pairing of `$curve`.
+macro_rules! with_curve {
+    ($curve:expr, $f:ident($($arg:expr),*)) => {
+        match $curve {
+            Curve::Bn254 => $f::<Bn254>($($arg),*),
+            Curve::Bls12_381 => $f::<Bls12_381>($($arg),*),
+            Curve::Bls12_377 => $f::<Bls12_377>($($arg),*),
+        }
+    };
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            let mut source = err.source();
+            while let Some(err) = source {
+                eprintln!("  caused by: {}", err);
+                source = err.source();
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Setup { curve, mapping, left_size, right_size, out } => {
+            with_curve!(curve, setup(curve, &mapping, left_size, right_size, &out))
+        }
+        Command::Commit { params, left, right, out } => {
+            let curve = artifact::curve_of(&params)?;
+            with_curve!(curve, commit(curve, &params, &left, &right, &out))
+        }
+        Command::Prove { params, left, right, statement, out } => {
+            let curve = artifact::curve_of(&params)?;
+            with_curve!(curve, prove_cmd(curve, &params, &left, &right, statement.as_deref(), &out))
+        }
+        Command::Verify { params, statement, proof } => {
+            let curve = artifact::curve_of(&params)?;
+            with_curve!(curve, verify_cmd(curve, &params, &statement, &proof))
+        }
+        Command::Inspect { file } => {
+            let curve = artifact::curve_of(&file)?;
+            with_curve!(curve, inspect(curve, &file))
+        }
+    }
+}
+
+fn setup<P: Pairing>(
+    curve: Curve,
+    mapping: &Path,
+    left_size: usize,
+    right_size: usize,
+    out: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let mappings = input::read_mapping(mapping)?;
+    if let Some((left, right)) = mappings
+        .iter()
+        .find(|(&left, &right)| left >= left_size || right >= right_size)
+    {
+        return Err(format!("mapping {} -> {} is out of range", left, right).into());
+    }
+
+    let pp = PublicParameters::<P>::builder()
+        .size_left_values(left_size)
+        .size_right_values(right_size)
+        .position_mappings(&mappings)
+        .build(&mut rand::thread_rng())?;
+    artifact::write(out, curve, &pp)?;
+    println!("wrote {} public parameters with {} mappings to {}", curve, mappings.len(), out.display());
+
+    Ok(())
+}
+
+fn load_witness<P: Pairing>(
+    pp: &PublicParameters<P>,
+    left: &Path,
+    right: &Path,
+) -> Result<Witness<P>, Box<dyn Error>> {
+    let left_values = input::read_values::<P::ScalarField>(left)?;
+    let right_values = input::read_values::<P::ScalarField>(right)?;
+
+    Ok(Witness::new(pp, &left_values, &right_values)?)
+}
+
+fn commit<P: Pairing>(
+    curve: Curve,
+    params: &Path,
+    left: &Path,
+    right: &Path,
+    out: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let pp = artifact::read::<PublicParameters<P>>(params, curve)?;
+    let statement = load_witness(&pp, left, right)?.generate_statement(&pp)?;
+    artifact::write(out, curve, &statement)?;
+    println!("wrote statement to {}", out.display());
+
+    Ok(())
+}
+
+fn prove_cmd<P: Pairing>(
+    curve: Curve,
+    params: &Path,
+    left: &Path,
+    right: &Path,
+    statement: Option<&Path>,
+    out: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let pp = artifact::read::<PublicParameters<P>>(params, curve)?;
+    let witness = load_witness(&pp, left, right)?;
+    let computed = witness.generate_statement(&pp)?;
+    if let Some(path) = statement {
+        let given = artifact::read::<Statement<P>>(path, curve)?;
+        if artifact_bytes(&given)? != artifact_bytes(&computed)? {
+            return Err(format!("{} does not commit to the given vectors", path.display()).into());
+        }
+    }
+
+    let proof = prove(&pp, &witness, &computed)?;
+    artifact::write(out, curve, &proof)?;
+    println!("wrote proof to {}", out.display());
+
+    Ok(())
+}
+
+fn verify_cmd<P: Pairing>(
+    curve: Curve,
+    params: &Path,
+    statement: &Path,
+    proof: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let pp = artifact::read::<PublicParameters<P>>(params, curve)?;
+    let statement = artifact::read::<Statement<P>>(statement, curve)?;
+    let proof = artifact::read::<Proof<P>>(proof, curve)?;
+    verify(&pp, &statement, &proof)?;
+    println!("proof is valid");
+
+    Ok(())
+}
+
+fn inspect<P: Pairing>(curve: Curve, file: &Path) -> Result<(), Box<dyn Error>> {
+    let (header, payload) = artifact::read_raw(file)?;
+    println!("format version: {}", header.version);
+    println!("curve:          {}", header.curve);
+    println!("kind:           {}", header.kind);
+    println!("payload size:   {} bytes", payload.len());
+
+    match header.kind {
+        Kind::PublicParameters => {
+            let pp = artifact::read::<PublicParameters<P>>(file, curve)?;
+            println!("left size:      {}", pp.size_left_values);
+            println!("right size:     {}", pp.size_right_values);
+            println!("g1 srs size:    {}", pp.g1_affine_srs.len());
+            println!("g2 srs size:    {}", pp.g2_affine_srs.len());
+            println!("mappings:       {}", pp.positions_left.len());
+            for (left, right) in pp.positions_left.iter().zip(pp.positions_right.iter()) {
+                println!("  {} -> {}", left, right);
+            }
+            println!("g1 positions left:     {}", pp.g1_affine_positions_left);
+            println!("g1 positions right:    {}", pp.g1_affine_positions_right);
+            println!("g1 position mappings:  {}", pp.g1_affine_position_mappings);
+        }
+        Kind::Statement => {
+            println!("{:#?}", artifact::read::<Statement<P>>(file, curve)?);
+        }
+        Kind::Proof => {
+            println!("{:#?}", artifact::read::<Proof<P>>(file, curve)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn artifact_bytes<A: artifact::Artifact>(artifact: &A) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut buf = Vec::new();
+    artifact.serialize_compressed(&mut buf)?;
+
+    Ok(buf)
+}
+
+/// Writes `content` to a fresh file named `name` under the system temporary directory.
+#[cfg(test)]
+fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("isep-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+
+    path
+}
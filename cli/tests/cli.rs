@@ -0,0 +1,79 @@
+//! Drives `isep setup`, `commit`, `prove`, `verify` and `inspect` on temporary files.
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn isep(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_isep"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("failed to run isep")
+}
+
+fn work_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+fn assert_success(output: &Output) -> String {
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(
+        output.status.success(),
+        "isep failed:\n{}{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    stdout
+}
+
+#[test]
+fn setup_commit_prove_verify() {
+    let dir = work_dir("setup_commit_prove_verify");
+    std::fs::write(dir.join("mapping.csv"), "left,right\n0,3\n2,5\n").unwrap();
+    std::fs::write(dir.join("left.json"), "[11, 12, 13, 14]").unwrap();
+    std::fs::write(dir.join("right.txt"), "1 2 3 11\n5 13 7 8").unwrap();
+    std::fs::write(dir.join("other_right.txt"), "1 2 3 11\n5 99 7 8").unwrap();
+
+    assert_success(&isep(&dir, &[
+        "setup", "--curve", "bls12_381", "--mapping", "mapping.csv",
+        "--left-size", "4", "--right-size", "8", "--out", "pp.isep",
+    ]));
+    assert_success(&isep(&dir, &[
+        "commit", "--params", "pp.isep", "--left", "left.json", "--right", "right.txt",
+        "--out", "statement.isep",
+    ]));
+    assert_success(&isep(&dir, &[
+        "prove", "--params", "pp.isep", "--left", "left.json", "--right", "right.txt",
+        "--statement", "statement.isep", "--out", "proof.isep",
+    ]));
+    let stdout = assert_success(&isep(&dir, &[
+        "verify", "--params", "pp.isep", "--statement", "statement.isep", "--proof", "proof.isep",
+    ]));
+    assert!(stdout.contains("proof is valid"));
+
+    let stdout = assert_success(&isep(&dir, &["inspect", "pp.isep"]));
+    assert!(stdout.contains("bls12_381"));
+    assert!(stdout.contains("2 -> 5"));
+
+    // The proof does not verify against a statement for other vectors.
+    assert_success(&isep(&dir, &[
+        "commit", "--params", "pp.isep", "--left", "left.json", "--right", "other_right.txt",
+        "--out", "other_statement.isep",
+    ]));
+    let output = isep(&dir, &[
+        "verify", "--params", "pp.isep", "--statement", "other_statement.isep", "--proof", "proof.isep",
+    ]);
+    assert!(!output.status.success());
+
+    // Nor does `prove` accept a statement that does not commit to the vectors.
+    let output = isep(&dir, &[
+        "prove", "--params", "pp.isep", "--left", "left.json", "--right", "right.txt",
+        "--statement", "other_statement.isep", "--out", "other_proof.isep",
+    ]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("does not commit to the given vectors"));
+}
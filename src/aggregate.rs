@@ -0,0 +1,457 @@
+//! Proves many instances over the same public parameters with one proof.
+//!
+//! Each instance commits to its own `L` and `R` as in [`crate::prover::prove`], with challenges
+//! shared by all instances. The left and right numerators of the instances are combined with
+//! the powers of a challenge `rho` into one left and one right quotient, and all polynomials
+//! are opened with one batch proof, so the verifier runs one pairing check. An instance adds
+//! its two commitments and seven evaluations to the proof.
+//!
+//! The commitments to `L_k` and `R_k` stay per instance: the sumcheck needs each `L_k(0)` and
+//! `R_k(0)`, and the quotient identity multiplies `L_k` by the instance's own denominator, so
+//! each half has to be bound and opened on its own. Only the quotients are combined.
+
+use crate::domain::roots_of_unity;
+use crate::error::Error;
+use crate::kzg::{multi_point_batch_verify, powers_of_scalars, Kzg};
+use crate::prover::{fractional_poly, quotient_poly};
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::transcript::{Label, Transcript};
+use crate::verifier::opened_or;
+use crate::witness::{fold_columns, Witness};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, PrimeField};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{EvaluationDomain, Polynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{cfg_iter, cfg_iter_mut, vec, vec::Vec, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Evaluations of the polynomials of one instance.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct InstanceEvaluations<F: Field> {
+    pub(crate) l_at_delta: F,
+    pub(crate) r_at_delta: F,
+    pub(crate) lv_at_delta: F,
+    pub(crate) rv_at_delta: F,
+    /// Only for parameters with [`PublicParameters::has_constants`].
+    pub(crate) lv0_at_delta: Option<F>,
+    pub(crate) l_at_zero: F,
+    pub(crate) r_at_zero: F,
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AggregateProof<P: Pairing> {
+    pub(crate) g1_affine_l: Vec<P::G1Affine>,
+    pub(crate) g1_affine_r: Vec<P::G1Affine>,
+    pub(crate) g1_affine_ql: P::G1Affine,
+    pub(crate) g1_affine_qr: P::G1Affine,
+    pub(crate) batch_proof: P::G1Affine,
+    pub(crate) instance_evaluations: Vec<InstanceEvaluations<P::ScalarField>>,
+    pub(crate) pl_at_delta: P::ScalarField,
+    pub(crate) pr_at_delta: P::ScalarField,
+    pub(crate) pm_at_delta: P::ScalarField,
+    /// Only for parameters with [`PublicParameters::has_coefficients`].
+    pub(crate) pa_at_delta: Option<P::ScalarField>,
+    pub(crate) pb_at_delta: Option<P::ScalarField>,
+    /// Only for parameters with [`PublicParameters::has_constants`].
+    pub(crate) ps_at_delta: Option<P::ScalarField>,
+    pub(crate) pc_at_delta: Option<P::ScalarField>,
+}
+
+/// Polynomials of one instance, with its columns folded.
+struct Instance<F: Field> {
+    poly_l: DensePolynomial<F>,
+    poly_r: DensePolynomial<F>,
+    poly_left_values: DensePolynomial<F>,
+    poly_right_values: DensePolynomial<F>,
+}
+
+/// Proves every witness against the statement at the same index.
+pub fn prove<P: Pairing>(
+    pp: &PublicParameters<P>,
+    witnesses: &[&Witness<P>],
+    statements: &[&Statement<P>],
+) -> Result<AggregateProof<P>, Error> {
+    if witnesses.is_empty() || witnesses.len() != statements.len() {
+        return Err(Error::WrongNumberOfValues(statements.len()));
+    }
+    pp.ensure_mapping()?;
+
+    let mut transcript = new_transcript(pp, statements)?;
+
+    // Sample random alpha, beta, gamma, shared by all instances.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    let roots_of_unity_r = roots_of_unity::<P>(&pp.domain_r);
+    let mut instances = Vec::with_capacity(witnesses.len());
+    for witness in witnesses {
+        let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+        let (left_values, poly_left_values) =
+            fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+        let (right_values, poly_right_values) =
+            fold_columns(&witness.right_values, &witness.poly_right_values, &powers_of_alpha);
+        let fr_sum_of_alpha: P::ScalarField = powers_of_alpha.iter().sum();
+
+        let fraction_list_l = pp.positions_left.iter()
+            .map(|&i| {
+                let (a, b) = pp.coefficients(i);
+                let denominator = beta + a * left_values[i] + fr_sum_of_alpha * b + gamma * pp.position_mappings[&i];
+
+                (i, P::ScalarField::one(), denominator)
+            })
+            .collect();
+        let fraction_list_r = pp.positions_right.iter()
+            .map(|&i| (i, P::ScalarField::one(), beta + right_values[i] + gamma * roots_of_unity_r[i]))
+            .collect();
+
+        instances.push(Instance {
+            poly_l: fractional_poly(&pp.domain_l, fraction_list_l)?,
+            poly_r: fractional_poly(&pp.domain_r, fraction_list_r)?,
+            poly_left_values,
+            poly_right_values,
+        });
+    }
+    let g1_affine_l: Vec<P::G1Affine> = instances.iter()
+        .map(|instance| Kzg::<P::G1>::commit(&pp.g1_affine_srs, &instance.poly_l).into_affine())
+        .collect();
+    let g1_affine_r: Vec<P::G1Affine> = instances.iter()
+        .map(|instance| Kzg::<P::G1>::commit(&pp.g1_affine_srs, &instance.poly_r).into_affine())
+        .collect();
+
+    transcript.append_elements(&[(Label::G1L, g1_affine_l.clone()), (Label::G1R, g1_affine_r.clone())])?;
+
+    // Sample random eta for the public constants and rho to combine the instances.
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+    let rho = transcript.squeeze_challenge(Label::ChallengeRho)?;
+    let powers_of_rho = powers_of_scalars(rho, instances.len());
+
+    // Combine the numerators of the instances over the cosets.
+    let mut coset_eval_list_ql = vec![P::ScalarField::zero(); pp.domain_coset_l.size()];
+    let mut coset_eval_list_qr = vec![P::ScalarField::zero(); pp.domain_coset_r.size()];
+    for ((instance, witness), &fr_pow_rho) in instances.iter().zip(witnesses.iter()).zip(powers_of_rho.iter()) {
+        let fr_sum_of_alpha: P::ScalarField = powers_of_scalars(alpha, witness.num_columns()).iter().sum();
+
+        let coset_eval_list_l = pp.domain_coset_l.fft(&instance.poly_l);
+        let coset_eval_list_left_values = pp.domain_coset_l.fft(&instance.poly_left_values);
+        let coset_eval_list_first_column = pp.domain_coset_l.fft(&witness.poly_left_values[0]);
+        cfg_iter_mut!(coset_eval_list_ql)
+            .zip(cfg_iter!(coset_eval_list_l))
+            .zip(cfg_iter!(coset_eval_list_left_values))
+            .zip(cfg_iter!(pp.coset_eval_list_positions_left))
+            .zip(cfg_iter!(pp.coset_eval_list_position_mappings))
+            .zip(cfg_iter!(pp.coset_eval_list_coefficients_a))
+            .zip(cfg_iter!(pp.coset_eval_list_coefficients_b))
+            .zip(cfg_iter!(coset_eval_list_first_column))
+            .zip(cfg_iter!(pp.coset_eval_list_constant_positions))
+            .zip(cfg_iter!(pp.coset_eval_list_constants))
+            .for_each(|(((((((((q, &l), &v), &p), &m), &a), &b), &v0), &s), &c)| {
+                let n = l * (beta + a * v + fr_sum_of_alpha * b + gamma * m) - p;
+                *q += fr_pow_rho * (n + eta * (s * v0 - c));
+            });
+
+        let coset_eval_list_r = pp.domain_coset_r.fft(&instance.poly_r);
+        let coset_eval_list_right_values = pp.domain_coset_r.fft(&instance.poly_right_values);
+        cfg_iter_mut!(coset_eval_list_qr)
+            .zip(cfg_iter!(coset_eval_list_r))
+            .zip(cfg_iter!(coset_eval_list_right_values))
+            .zip(cfg_iter!(pp.coset_eval_list_positions_right))
+            .zip(cfg_iter!(pp.roots_of_unity_coset_r))
+            .for_each(|((((q, &r), &e), &p), &c)| *q += fr_pow_rho * (r * (beta + e + gamma * c) - p));
+    }
+    let poly_ql = quotient_poly::<P>(&pp.domain_l, &pp.domain_coset_l, coset_eval_list_ql)?;
+    let g1_affine_ql = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_ql).into_affine();
+    let poly_qr = quotient_poly::<P>(&pp.domain_r, &pp.domain_coset_r, coset_eval_list_qr)?;
+    let g1_affine_qr = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_qr).into_affine();
+
+    transcript.append_elements(&[(Label::G1Ql, g1_affine_ql), (Label::G1Qr, g1_affine_qr)])?;
+
+    // Sample random delta.
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    let fr_zero = P::ScalarField::zero();
+    let has_constants = pp.has_constants();
+    let instance_evaluations: Vec<InstanceEvaluations<P::ScalarField>> = instances.iter()
+        .zip(witnesses.iter())
+        .map(|(instance, witness)| InstanceEvaluations {
+            l_at_delta: instance.poly_l.evaluate(&delta),
+            r_at_delta: instance.poly_r.evaluate(&delta),
+            lv_at_delta: instance.poly_left_values.evaluate(&delta),
+            rv_at_delta: instance.poly_right_values.evaluate(&delta),
+            lv0_at_delta: has_constants.then(|| witness.poly_left_values[0].evaluate(&delta)),
+            l_at_zero: instance.poly_l.evaluate(&fr_zero),
+            r_at_zero: instance.poly_r.evaluate(&fr_zero),
+        })
+        .collect();
+    let pl_at_delta = pp.poly_positions_left.evaluate(&delta);
+    let pr_at_delta = pp.poly_positions_right.evaluate(&delta);
+    let pm_at_delta = pp.poly_position_mappings.evaluate(&delta);
+    let has_coefficients = pp.has_coefficients();
+    let pa_at_delta = has_coefficients.then(|| pp.poly_coefficients_a.evaluate(&delta));
+    let pb_at_delta = has_coefficients.then(|| pp.poly_coefficients_b.evaluate(&delta));
+    let ps_at_delta = has_constants.then(|| pp.poly_constant_positions.evaluate(&delta));
+    let pc_at_delta = has_constants.then(|| pp.poly_constants.evaluate(&delta));
+
+    append_evaluations(
+        &mut transcript,
+        [pl_at_delta, pr_at_delta, pm_at_delta],
+        [pa_at_delta, pb_at_delta],
+        [ps_at_delta, pc_at_delta],
+        &instance_evaluations,
+    )?;
+
+    // Sample random epsilon to separate the opened polynomials.
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let mut poly_list_at_delta = vec![
+        &poly_ql,
+        &poly_qr,
+        &pp.poly_positions_left,
+        &pp.poly_positions_right,
+        &pp.poly_position_mappings,
+    ];
+    if has_coefficients {
+        poly_list_at_delta.extend([&pp.poly_coefficients_a, &pp.poly_coefficients_b]);
+    }
+    if has_constants {
+        poly_list_at_delta.extend([&pp.poly_constant_positions, &pp.poly_constants]);
+    }
+    let mut poly_list_at_zero = Vec::with_capacity(2 * instances.len());
+    for (instance, witness) in instances.iter().zip(witnesses.iter()) {
+        poly_list_at_delta.extend([
+            &instance.poly_l,
+            &instance.poly_r,
+            &instance.poly_left_values,
+            &instance.poly_right_values,
+        ]);
+        if has_constants {
+            poly_list_at_delta.push(&witness.poly_left_values[0]);
+        }
+        poly_list_at_zero.extend([&instance.poly_l, &instance.poly_r]);
+    }
+    let batch_proof = Kzg::<P::G1>::multi_point_batch_open(
+        &pp.g1_affine_srs,
+        &[&poly_list_at_delta, &poly_list_at_zero],
+        &[delta, fr_zero],
+        epsilon,
+    );
+
+    Ok(AggregateProof {
+        g1_affine_l,
+        g1_affine_r,
+        g1_affine_ql,
+        g1_affine_qr,
+        batch_proof,
+        instance_evaluations,
+        pl_at_delta,
+        pr_at_delta,
+        pm_at_delta,
+        pa_at_delta,
+        pb_at_delta,
+        ps_at_delta,
+        pc_at_delta,
+    })
+}
+
+pub fn verify<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statements: &[&Statement<P>],
+    proof: &AggregateProof<P>,
+) -> Result<(), Error> {
+    if statements.is_empty() {
+        return Err(Error::WrongNumberOfValues(0));
+    }
+    if [proof.g1_affine_l.len(), proof.g1_affine_r.len(), proof.instance_evaluations.len()]
+        .iter()
+        .any(|&len| len != statements.len())
+    {
+        return Err(Error::MalformedProof("instances"));
+    }
+    if let Some(statement) = statements.iter().find(|statement| {
+        statement.g1_affine_left_values.is_empty()
+            || statement.g1_affine_left_values.len() != statement.g1_affine_right_values.len()
+    }) {
+        return Err(Error::WrongNumberOfColumns(statement.g1_affine_right_values.len()));
+    }
+
+    let mut transcript = new_transcript(pp, statements)?;
+
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    transcript.append_elements(&[(Label::G1L, proof.g1_affine_l.clone()), (Label::G1R, proof.g1_affine_r.clone())])?;
+
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+    let rho = transcript.squeeze_challenge(Label::ChallengeRho)?;
+
+    transcript.append_elements(&[(Label::G1Ql, proof.g1_affine_ql), (Label::G1Qr, proof.g1_affine_qr)])?;
+
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    append_evaluations(
+        &mut transcript,
+        [proof.pl_at_delta, proof.pr_at_delta, proof.pm_at_delta],
+        [proof.pa_at_delta, proof.pb_at_delta],
+        [proof.ps_at_delta, proof.pc_at_delta],
+        &proof.instance_evaluations,
+    )?;
+
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let has_coefficients = pp.has_coefficients();
+    let [pa_at_delta, pb_at_delta] = opened_or(
+        has_coefficients,
+        [proof.pa_at_delta, proof.pb_at_delta],
+        [P::ScalarField::one(), P::ScalarField::zero()],
+        "coefficient evaluations",
+    )?;
+    let has_constants = pp.has_constants();
+    let [ps_at_delta, pc_at_delta] = opened_or(
+        has_constants,
+        [proof.ps_at_delta, proof.pc_at_delta],
+        [P::ScalarField::zero(); 2],
+        "constant evaluations",
+    )?;
+
+    // Combine the numerators of the instances at delta as the prover did over the cosets, and
+    // fold the column commitments of each instance.
+    let fr_n_l = P::ScalarField::from(pp.size_left_values as u64);
+    let fr_n_r = P::ScalarField::from(pp.size_right_values as u64);
+    let mut fr_nl_at_delta = P::ScalarField::zero();
+    let mut fr_nr_at_delta = P::ScalarField::zero();
+    let mut fr_pow_rho = P::ScalarField::one();
+    let mut g1_list_at_delta = Vec::with_capacity(5 * statements.len());
+    let mut fr_list_at_delta = Vec::with_capacity(5 * statements.len());
+    let mut g1_list_at_zero = Vec::with_capacity(2 * statements.len());
+    let mut fr_list_at_zero = Vec::with_capacity(2 * statements.len());
+    for (((statement, evals), &g1_affine_l), &g1_affine_r) in statements.iter()
+        .zip(proof.instance_evaluations.iter())
+        .zip(proof.g1_affine_l.iter())
+        .zip(proof.g1_affine_r.iter())
+    {
+        let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+        let fr_sum_of_alpha: P::ScalarField = powers_of_alpha.iter().sum();
+        let [lv0_at_delta] = opened_or(
+            has_constants,
+            [evals.lv0_at_delta],
+            [P::ScalarField::zero()],
+            "constant evaluations",
+        )?;
+
+        let fr_nl = evals.l_at_delta
+            * (beta + pa_at_delta * evals.lv_at_delta + fr_sum_of_alpha * pb_at_delta + gamma * proof.pm_at_delta)
+            - proof.pl_at_delta
+            + eta * (ps_at_delta * lv0_at_delta - pc_at_delta);
+        let fr_nr = evals.r_at_delta * (beta + evals.rv_at_delta + gamma * delta) - proof.pr_at_delta;
+        fr_nl_at_delta += fr_pow_rho * fr_nl;
+        fr_nr_at_delta += fr_pow_rho * fr_nr;
+        fr_pow_rho *= rho;
+
+        // Sumcheck Lemma of the instance.
+        if evals.l_at_zero * fr_n_l != evals.r_at_zero * fr_n_r {
+            return Err(Error::EqualityCheckFailed("sumcheck of left and right halves"));
+        }
+
+        g1_list_at_delta.extend([
+            g1_affine_l,
+            g1_affine_r,
+            P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha).into_affine(),
+            P::G1::msm_unchecked(&statement.g1_affine_right_values, &powers_of_alpha).into_affine(),
+        ]);
+        fr_list_at_delta.extend([evals.l_at_delta, evals.r_at_delta, evals.lv_at_delta, evals.rv_at_delta]);
+        if has_constants {
+            g1_list_at_delta.push(statement.g1_affine_left_values[0]);
+            fr_list_at_delta.push(lv0_at_delta);
+        }
+        g1_list_at_zero.extend([g1_affine_l, g1_affine_r]);
+        fr_list_at_zero.extend([evals.l_at_zero, evals.r_at_zero]);
+    }
+
+    let fr_one = P::ScalarField::one();
+    let fr_zl_at_delta = delta.pow([pp.size_left_values as u64]) - fr_one;
+    let fr_zr_at_delta = delta.pow([pp.size_right_values as u64]) - fr_one;
+    let fr_ql_at_delta = fr_nl_at_delta * fr_zl_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let fr_qr_at_delta = fr_nr_at_delta * fr_zr_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+
+    // Pairing check of the batch proof at delta and zero.
+    let mut g1_list = vec![
+        proof.g1_affine_ql,
+        proof.g1_affine_qr,
+        pp.g1_affine_positions_left,
+        pp.g1_affine_positions_right,
+        pp.g1_affine_position_mappings,
+    ];
+    let mut fr_list = vec![
+        fr_ql_at_delta,
+        fr_qr_at_delta,
+        proof.pl_at_delta,
+        proof.pr_at_delta,
+        proof.pm_at_delta,
+    ];
+    if has_coefficients {
+        g1_list.extend([pp.g1_affine_coefficients_a, pp.g1_affine_coefficients_b]);
+        fr_list.extend([pa_at_delta, pb_at_delta]);
+    }
+    if has_constants {
+        g1_list.extend([pp.g1_affine_constant_positions, pp.g1_affine_constants]);
+        fr_list.extend([ps_at_delta, pc_at_delta]);
+    }
+    g1_list.extend(g1_list_at_delta);
+    fr_list.extend(fr_list_at_delta);
+
+    if !multi_point_batch_verify::<P>(
+        pp.g1_affine_srs[0],
+        &pp.g2_affine_srs,
+        &[&g1_list, &g1_list_at_zero],
+        &[&fr_list, &fr_list_at_zero],
+        &[delta, P::ScalarField::zero()],
+        epsilon,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("batch opening at delta and zero"));
+    }
+
+    Ok(())
+}
+
+/// Binds the evaluations of the public polynomials, skipping those the proof does not carry,
+/// and then those of the instances.
+fn append_evaluations<F: PrimeField>(
+    transcript: &mut Transcript<F>,
+    [pl_at_delta, pr_at_delta, pm_at_delta]: [F; 3],
+    [pa_at_delta, pb_at_delta]: [Option<F>; 2],
+    [ps_at_delta, pc_at_delta]: [Option<F>; 2],
+    instance_evaluations: &[InstanceEvaluations<F>],
+) -> Result<(), Error> {
+    let mut evaluations = vec![
+        (Label::FrPlAtDelta, pl_at_delta),
+        (Label::FrPrAtDelta, pr_at_delta),
+        (Label::FrPmAtDelta, pm_at_delta),
+    ];
+    evaluations.extend(pa_at_delta.map(|pa_at_delta| (Label::FrPaAtDelta, pa_at_delta)));
+    evaluations.extend(pb_at_delta.map(|pb_at_delta| (Label::FrPbAtDelta, pb_at_delta)));
+    evaluations.extend(ps_at_delta.map(|ps_at_delta| (Label::FrPsAtDelta, ps_at_delta)));
+    evaluations.extend(pc_at_delta.map(|pc_at_delta| (Label::FrPcAtDelta, pc_at_delta)));
+    transcript.append_elements(&evaluations)?;
+
+    transcript.append_element(Label::InstanceEvaluations, &instance_evaluations)
+}
+
+/// Binds the parameters and every statement, in order.
+fn new_transcript<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statements: &[&Statement<P>],
+) -> Result<Transcript<P::ScalarField>, Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_element(Label::PublicParameters, &pp.hash_representation)?;
+    for statement in statements {
+        transcript.append_element(Label::Statement, &statement.hash_representation)?;
+    }
+
+    Ok(transcript)
+}
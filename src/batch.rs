@@ -0,0 +1,408 @@
+//! Proves instances over different public parameters that share one SRS with one proof.
+//!
+//! Every instance proves the relation of [`crate::prover::prove`] with its own mappings and
+//! quotients, but one transcript binds the hash representations of all parameters and
+//! statements, so the instances share the challenges. All polynomials are then opened at
+//! `delta` and zero with one batch proof, and the verifier runs one pairing check.
+
+use crate::domain::roots_of_unity;
+use crate::error::Error;
+use crate::kzg::{multi_point_batch_verify, powers_of_scalars, Kzg};
+use crate::prover::{fractional_poly, quotient_poly};
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::transcript::{Label, Transcript};
+use crate::verifier::opened_or;
+use crate::witness::{fold_columns, Witness};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{EvaluationDomain, Polynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{cfg_iter, vec::Vec, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Commitments and evaluations of one instance.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct InstanceProof<P: Pairing> {
+    pub(crate) g1_affine_l: P::G1Affine,
+    pub(crate) g1_affine_r: P::G1Affine,
+    pub(crate) g1_affine_ql: P::G1Affine,
+    pub(crate) g1_affine_qr: P::G1Affine,
+    pub(crate) l_at_delta: P::ScalarField,
+    pub(crate) r_at_delta: P::ScalarField,
+    pub(crate) lv_at_delta: P::ScalarField,
+    pub(crate) rv_at_delta: P::ScalarField,
+    pub(crate) pl_at_delta: P::ScalarField,
+    pub(crate) pr_at_delta: P::ScalarField,
+    pub(crate) pm_at_delta: P::ScalarField,
+    /// Only for parameters with [`PublicParameters::has_coefficients`].
+    pub(crate) pa_at_delta: Option<P::ScalarField>,
+    pub(crate) pb_at_delta: Option<P::ScalarField>,
+    /// Only for parameters with [`PublicParameters::has_constants`].
+    pub(crate) lv0_at_delta: Option<P::ScalarField>,
+    pub(crate) ps_at_delta: Option<P::ScalarField>,
+    pub(crate) pc_at_delta: Option<P::ScalarField>,
+    pub(crate) l_at_zero: P::ScalarField,
+    pub(crate) r_at_zero: P::ScalarField,
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BatchProof<P: Pairing> {
+    pub(crate) instance_proofs: Vec<InstanceProof<P>>,
+    pub(crate) batch_proof: P::G1Affine,
+}
+
+/// Polynomials of one instance, with its columns folded.
+struct Instance<F: Field> {
+    poly_l: DensePolynomial<F>,
+    poly_r: DensePolynomial<F>,
+    poly_ql: DensePolynomial<F>,
+    poly_qr: DensePolynomial<F>,
+    poly_left_values: DensePolynomial<F>,
+    poly_right_values: DensePolynomial<F>,
+}
+
+/// Proves each witness against its statement under its own public parameters.
+pub fn prove<P: Pairing>(
+    instances: &[(&PublicParameters<P>, &Witness<P>, &Statement<P>)],
+) -> Result<BatchProof<P>, Error> {
+    let g1_affine_srs = shared_srs(&instances.iter().map(|&(pp, _, _)| pp).collect::<Vec<_>>())?;
+    instances.iter().try_for_each(|&(pp, _, _)| pp.ensure_mapping())?;
+    let mut transcript = new_transcript(&instances.iter().map(|&(pp, _, statement)| (pp, statement)).collect::<Vec<_>>())?;
+
+    // Sample random alpha, beta, gamma, shared by all instances.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    let mut polys = Vec::with_capacity(instances.len());
+    let mut g1_affine_lr = Vec::with_capacity(instances.len());
+    for &(pp, witness, _) in instances {
+        let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+        let (left_values, poly_left_values) =
+            fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+        let (right_values, poly_right_values) =
+            fold_columns(&witness.right_values, &witness.poly_right_values, &powers_of_alpha);
+        let fr_sum_of_alpha: P::ScalarField = powers_of_alpha.iter().sum();
+
+        let fraction_list_l = pp.positions_left.iter()
+            .map(|&i| {
+                let (a, b) = pp.coefficients(i);
+                let denominator = beta + a * left_values[i] + fr_sum_of_alpha * b + gamma * pp.position_mappings[&i];
+
+                (i, P::ScalarField::one(), denominator)
+            })
+            .collect();
+        let poly_l = fractional_poly(&pp.domain_l, fraction_list_l)?;
+
+        let roots_of_unity_r = roots_of_unity::<P>(&pp.domain_r);
+        let fraction_list_r = pp.positions_right.iter()
+            .map(|&i| (i, P::ScalarField::one(), beta + right_values[i] + gamma * roots_of_unity_r[i]))
+            .collect();
+        let poly_r = fractional_poly(&pp.domain_r, fraction_list_r)?;
+
+        g1_affine_lr.push((
+            Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_l).into_affine(),
+            Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_r).into_affine(),
+        ));
+        polys.push(Instance {
+            poly_l,
+            poly_r,
+            poly_ql: DensePolynomial::zero(),
+            poly_qr: DensePolynomial::zero(),
+            poly_left_values,
+            poly_right_values,
+        });
+    }
+
+    transcript.append_element(Label::G1L, &g1_affine_lr)?;
+
+    // Sample random eta to add the public constants to the left quotients.
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+
+    let mut g1_affine_q = Vec::with_capacity(instances.len());
+    for (&(pp, witness, _), instance) in instances.iter().zip(polys.iter_mut()) {
+        let fr_sum_of_alpha: P::ScalarField = powers_of_scalars(alpha, witness.num_columns()).iter().sum();
+
+        let coset_eval_list_l = pp.domain_coset_l.fft(&instance.poly_l);
+        let coset_eval_list_left_values = pp.domain_coset_l.fft(&instance.poly_left_values);
+        let coset_eval_list_first_column = pp.domain_coset_l.fft(&witness.poly_left_values[0]);
+        let coset_eval_list_ql: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_l)
+            .zip(cfg_iter!(coset_eval_list_left_values))
+            .zip(cfg_iter!(pp.coset_eval_list_positions_left))
+            .zip(cfg_iter!(pp.coset_eval_list_position_mappings))
+            .zip(cfg_iter!(pp.coset_eval_list_coefficients_a))
+            .zip(cfg_iter!(pp.coset_eval_list_coefficients_b))
+            .map(|(((((&l, &v), &p), &m), &a), &b)| l * (beta + a * v + fr_sum_of_alpha * b + gamma * m) - p)
+            .zip(cfg_iter!(coset_eval_list_first_column))
+            .zip(cfg_iter!(pp.coset_eval_list_constant_positions))
+            .zip(cfg_iter!(pp.coset_eval_list_constants))
+            .map(|(((n, &v0), &s), &c)| n + eta * (s * v0 - c))
+            .collect();
+        instance.poly_ql = quotient_poly::<P>(&pp.domain_l, &pp.domain_coset_l, coset_eval_list_ql)?;
+
+        let coset_eval_list_r = pp.domain_coset_r.fft(&instance.poly_r);
+        let coset_eval_list_right_values = pp.domain_coset_r.fft(&instance.poly_right_values);
+        let coset_eval_list_qr: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_r)
+            .zip(cfg_iter!(coset_eval_list_right_values))
+            .zip(cfg_iter!(pp.coset_eval_list_positions_right))
+            .zip(cfg_iter!(pp.roots_of_unity_coset_r))
+            .map(|(((&r, &e), &p), &c)| r * (beta + e + gamma * c) - p)
+            .collect();
+        instance.poly_qr = quotient_poly::<P>(&pp.domain_r, &pp.domain_coset_r, coset_eval_list_qr)?;
+
+        g1_affine_q.push((
+            Kzg::<P::G1>::commit(&pp.g1_affine_srs, &instance.poly_ql).into_affine(),
+            Kzg::<P::G1>::commit(&pp.g1_affine_srs, &instance.poly_qr).into_affine(),
+        ));
+    }
+
+    transcript.append_element(Label::G1Ql, &g1_affine_q)?;
+
+    // Sample random delta.
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    let fr_zero = P::ScalarField::zero();
+    let instance_proofs: Vec<InstanceProof<P>> = instances.iter()
+        .zip(polys.iter())
+        .zip(g1_affine_lr.iter().zip(g1_affine_q.iter()))
+        .map(|((&(pp, witness, _), instance), (&(g1_affine_l, g1_affine_r), &(g1_affine_ql, g1_affine_qr)))| {
+            InstanceProof {
+                g1_affine_l,
+                g1_affine_r,
+                g1_affine_ql,
+                g1_affine_qr,
+                l_at_delta: instance.poly_l.evaluate(&delta),
+                r_at_delta: instance.poly_r.evaluate(&delta),
+                lv_at_delta: instance.poly_left_values.evaluate(&delta),
+                rv_at_delta: instance.poly_right_values.evaluate(&delta),
+                pl_at_delta: pp.poly_positions_left.evaluate(&delta),
+                pr_at_delta: pp.poly_positions_right.evaluate(&delta),
+                pm_at_delta: pp.poly_position_mappings.evaluate(&delta),
+                pa_at_delta: pp.has_coefficients().then(|| pp.poly_coefficients_a.evaluate(&delta)),
+                pb_at_delta: pp.has_coefficients().then(|| pp.poly_coefficients_b.evaluate(&delta)),
+                lv0_at_delta: pp.has_constants().then(|| witness.poly_left_values[0].evaluate(&delta)),
+                ps_at_delta: pp.has_constants().then(|| pp.poly_constant_positions.evaluate(&delta)),
+                pc_at_delta: pp.has_constants().then(|| pp.poly_constants.evaluate(&delta)),
+                l_at_zero: instance.poly_l.evaluate(&fr_zero),
+                r_at_zero: instance.poly_r.evaluate(&fr_zero),
+            }
+        })
+        .collect();
+
+    transcript.append_element(Label::InstanceEvaluations, &instance_proofs)?;
+
+    // Sample random epsilon to separate the opened polynomials.
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let mut poly_list_at_delta = Vec::with_capacity(14 * instances.len());
+    let mut poly_list_at_zero = Vec::with_capacity(2 * instances.len());
+    for (&(pp, witness, _), instance) in instances.iter().zip(polys.iter()) {
+        poly_list_at_delta.extend([
+            &instance.poly_l,
+            &instance.poly_r,
+            &instance.poly_ql,
+            &instance.poly_qr,
+            &instance.poly_left_values,
+            &instance.poly_right_values,
+            &pp.poly_positions_left,
+            &pp.poly_positions_right,
+            &pp.poly_position_mappings,
+        ]);
+        if pp.has_coefficients() {
+            poly_list_at_delta.extend([&pp.poly_coefficients_a, &pp.poly_coefficients_b]);
+        }
+        if pp.has_constants() {
+            poly_list_at_delta.extend([
+                &witness.poly_left_values[0],
+                &pp.poly_constant_positions,
+                &pp.poly_constants,
+            ]);
+        }
+        poly_list_at_zero.extend([&instance.poly_l, &instance.poly_r]);
+    }
+    let batch_proof = Kzg::<P::G1>::multi_point_batch_open(
+        g1_affine_srs,
+        &[&poly_list_at_delta, &poly_list_at_zero],
+        &[delta, fr_zero],
+        epsilon,
+    );
+
+    Ok(BatchProof {
+        instance_proofs,
+        batch_proof,
+    })
+}
+
+pub fn verify<P: Pairing>(
+    instances: &[(&PublicParameters<P>, &Statement<P>)],
+    proof: &BatchProof<P>,
+) -> Result<(), Error> {
+    shared_srs(&instances.iter().map(|&(pp, _)| pp).collect::<Vec<_>>())?;
+    if proof.instance_proofs.len() != instances.len() {
+        return Err(Error::MalformedProof("instances"));
+    }
+    if let Some(&(_, statement)) = instances.iter().find(|(_, statement)| {
+        statement.g1_affine_left_values.is_empty()
+            || statement.g1_affine_left_values.len() != statement.g1_affine_right_values.len()
+    }) {
+        return Err(Error::WrongNumberOfColumns(statement.g1_affine_right_values.len()));
+    }
+
+    let mut transcript = new_transcript(instances)?;
+
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    let g1_affine_lr: Vec<(P::G1Affine, P::G1Affine)> = proof.instance_proofs.iter()
+        .map(|p| (p.g1_affine_l, p.g1_affine_r))
+        .collect();
+    transcript.append_element(Label::G1L, &g1_affine_lr)?;
+
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+
+    let g1_affine_q: Vec<(P::G1Affine, P::G1Affine)> = proof.instance_proofs.iter()
+        .map(|p| (p.g1_affine_ql, p.g1_affine_qr))
+        .collect();
+    transcript.append_element(Label::G1Ql, &g1_affine_q)?;
+
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    transcript.append_element(Label::InstanceEvaluations, &proof.instance_proofs)?;
+
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let fr_one = P::ScalarField::one();
+    let mut g1_list_at_delta = Vec::with_capacity(14 * instances.len());
+    let mut fr_list_at_delta = Vec::with_capacity(14 * instances.len());
+    let mut g1_list_at_zero = Vec::with_capacity(2 * instances.len());
+    let mut fr_list_at_zero = Vec::with_capacity(2 * instances.len());
+    for (&(pp, statement), p) in instances.iter().zip(proof.instance_proofs.iter()) {
+        let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+        let fr_sum_of_alpha: P::ScalarField = powers_of_alpha.iter().sum();
+        let [pa_at_delta, pb_at_delta] = opened_or(
+            pp.has_coefficients(),
+            [p.pa_at_delta, p.pb_at_delta],
+            [fr_one, P::ScalarField::zero()],
+            "coefficient evaluations",
+        )?;
+        let [lv0_at_delta, ps_at_delta, pc_at_delta] = opened_or(
+            pp.has_constants(),
+            [p.lv0_at_delta, p.ps_at_delta, p.pc_at_delta],
+            [P::ScalarField::zero(); 3],
+            "constant evaluations",
+        )?;
+
+        let fr_zl_at_delta = delta.pow([pp.size_left_values as u64]) - fr_one;
+        let fr_inv_zl_at_delta = fr_zl_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+        let fr_ql_at_delta = (p.l_at_delta
+            * (beta + pa_at_delta * p.lv_at_delta + fr_sum_of_alpha * pb_at_delta + gamma * p.pm_at_delta)
+            - p.pl_at_delta
+            + eta * (ps_at_delta * lv0_at_delta - pc_at_delta))
+            * fr_inv_zl_at_delta;
+
+        let fr_zr_at_delta = delta.pow([pp.size_right_values as u64]) - fr_one;
+        let fr_inv_zr_at_delta = fr_zr_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+        let fr_qr_at_delta = (p.r_at_delta * (beta + p.rv_at_delta + gamma * delta) - p.pr_at_delta)
+            * fr_inv_zr_at_delta;
+
+        // Sumcheck Lemma of the instance.
+        if p.l_at_zero * P::ScalarField::from(pp.size_left_values as u64)
+            != p.r_at_zero * P::ScalarField::from(pp.size_right_values as u64)
+        {
+            return Err(Error::EqualityCheckFailed("sumcheck of left and right halves"));
+        }
+
+        g1_list_at_delta.extend([
+            p.g1_affine_l,
+            p.g1_affine_r,
+            p.g1_affine_ql,
+            p.g1_affine_qr,
+            P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha).into_affine(),
+            P::G1::msm_unchecked(&statement.g1_affine_right_values, &powers_of_alpha).into_affine(),
+            pp.g1_affine_positions_left,
+            pp.g1_affine_positions_right,
+            pp.g1_affine_position_mappings,
+        ]);
+        fr_list_at_delta.extend([
+            p.l_at_delta,
+            p.r_at_delta,
+            fr_ql_at_delta,
+            fr_qr_at_delta,
+            p.lv_at_delta,
+            p.rv_at_delta,
+            p.pl_at_delta,
+            p.pr_at_delta,
+            p.pm_at_delta,
+        ]);
+        if pp.has_coefficients() {
+            g1_list_at_delta.extend([pp.g1_affine_coefficients_a, pp.g1_affine_coefficients_b]);
+            fr_list_at_delta.extend([pa_at_delta, pb_at_delta]);
+        }
+        if pp.has_constants() {
+            g1_list_at_delta.extend([
+                statement.g1_affine_left_values[0],
+                pp.g1_affine_constant_positions,
+                pp.g1_affine_constants,
+            ]);
+            fr_list_at_delta.extend([lv0_at_delta, ps_at_delta, pc_at_delta]);
+        }
+        g1_list_at_zero.extend([p.g1_affine_l, p.g1_affine_r]);
+        fr_list_at_zero.extend([p.l_at_zero, p.r_at_zero]);
+    }
+
+    let pp = instances[0].0;
+    if !multi_point_batch_verify::<P>(
+        pp.g1_affine_srs[0],
+        &pp.g2_affine_srs,
+        &[&g1_list_at_delta, &g1_list_at_zero],
+        &[&fr_list_at_delta, &fr_list_at_zero],
+        &[delta, P::ScalarField::zero()],
+        epsilon,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("batch opening at delta and zero"));
+    }
+
+    Ok(())
+}
+
+/// Checks that the SRS of every instance is a prefix of the longest one, that is, all of them
+/// come from the same `tau`, and returns the longest G1 SRS.
+fn shared_srs<'a, P: Pairing>(pps: &[&'a PublicParameters<P>]) -> Result<&'a [P::G1Affine], Error> {
+    let longest = pps.iter()
+        .max_by_key(|pp| pp.g1_affine_srs.len())
+        .ok_or(Error::WrongNumberOfValues(0))?;
+    for (k, pp) in pps.iter().enumerate() {
+        // The longest G1 SRS need not come with the longest G2 SRS in deserialized parameters.
+        let g1_len = pp.g1_affine_srs.len();
+        let g2_len = pp.g2_affine_srs.len();
+        if g2_len > longest.g2_affine_srs.len()
+            || pp.g1_affine_srs[..] != longest.g1_affine_srs[..g1_len]
+            || pp.g2_affine_srs[..] != longest.g2_affine_srs[..g2_len]
+        {
+            return Err(Error::SrsMismatch(k));
+        }
+    }
+
+    Ok(&longest.g1_affine_srs)
+}
+
+/// Binds the parameters and the statement of every instance, in order.
+fn new_transcript<P: Pairing>(
+    instances: &[(&PublicParameters<P>, &Statement<P>)],
+) -> Result<Transcript<P::ScalarField>, Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    for (pp, statement) in instances {
+        transcript.append_elements(&[
+            (Label::PublicParameters, pp.hash_representation.clone()),
+            (Label::Statement, statement.hash_representation.clone()),
+        ])?;
+    }
+
+    Ok(transcript)
+}
@@ -0,0 +1,129 @@
+//! Reveals the left values at chosen positions with one proof against the committed columns.
+//!
+//! [`open_positions`] folds the left columns with a transcript challenge and opens the folded
+//! polynomial at the disclosed points of the left domain, with the quotient by the vanishing
+//! polynomial of the disclosed subset. [`verify_positions`] checks the revealed values against
+//! the left commitments of the [`Statement`] with one pairing check.
+
+use crate::error::Error;
+use crate::kzg::{powers_of_scalars, verify_at_points, Kzg};
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::transcript::{Label, Transcript};
+use crate::witness::{fold_columns, Witness};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_poly::EvaluationDomain;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+
+/// Left values revealed at chosen positions, with one proof that they match the committed
+/// left columns.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DisclosureProof<P: Pairing> {
+    /// Disclosed positions of the left vector, in ascending order.
+    pub indices: Vec<usize>,
+    /// One entry per column, holding the values at `indices`.
+    pub values: Vec<Vec<P::ScalarField>>,
+    pub(crate) batch_proof: P::G1Affine,
+}
+
+/// Reveals the left values at `indices`.
+///
+/// The columns are folded with a transcript challenge and the folded polynomial is opened at
+/// the disclosed points of `domain_l`, with the quotient by the vanishing polynomial of the
+/// disclosed subset. `statement` must be the statement of `witness`, as its hash seeds the
+/// folding challenge.
+pub fn open_positions<P: Pairing>(
+    pp: &PublicParameters<P>,
+    witness: &Witness<P>,
+    statement: &Statement<P>,
+    indices: &[usize],
+) -> Result<DisclosureProof<P>, Error> {
+    let mut indices = indices.to_vec();
+    indices.sort_unstable();
+    indices.dedup();
+    if indices.is_empty() {
+        return Err(Error::LeftIndicesCannotBeNone);
+    }
+    if let Some(&i) = indices.iter().find(|&&i| i >= pp.size_left_values) {
+        return Err(Error::InputIsTooLarge(i));
+    }
+
+    let values: Vec<Vec<P::ScalarField>> = witness.left_values.iter()
+        .map(|column| indices.iter().map(|&i| column[i]).collect())
+        .collect();
+
+    let alpha = disclosure_challenge(pp, statement, &indices, &values)?;
+    let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+    let (_, poly_left_values) = fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+
+    let fr_points: Vec<P::ScalarField> = indices.iter().map(|&i| pp.domain_l.element(i)).collect();
+    let (_, batch_proof) = Kzg::<P::G1>::open_at_points(&pp.g1_affine_srs, &poly_left_values, &fr_points);
+
+    Ok(DisclosureProof {
+        indices,
+        values,
+        batch_proof,
+    })
+}
+
+/// Checks disclosed left values against the left commitments of `statement`.
+pub fn verify_positions<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &Statement<P>,
+    proof: &DisclosureProof<P>,
+) -> Result<(), Error> {
+    if proof.indices.is_empty()
+        || proof.indices.windows(2).any(|w| w[0] >= w[1])
+        || proof.indices[proof.indices.len() - 1] >= pp.size_left_values
+        || proof.indices.len() + 1 > pp.g2_affine_srs.len()
+    {
+        return Err(Error::MalformedProof("indices"));
+    }
+    if proof.values.len() != statement.num_columns()
+        || proof.values.iter().any(|column| column.len() != proof.indices.len())
+    {
+        return Err(Error::MalformedProof("values"));
+    }
+
+    let alpha = disclosure_challenge(pp, statement, &proof.indices, &proof.values)?;
+    let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+    let g1_affine_left_values = P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha)
+        .into_affine();
+    let fr_values: Vec<P::ScalarField> = (0..proof.indices.len())
+        .map(|k| proof.values.iter().zip(powers_of_alpha.iter()).map(|(column, &fr_pow)| column[k] * fr_pow).sum())
+        .collect();
+
+    let fr_points: Vec<P::ScalarField> = proof.indices.iter().map(|&i| pp.domain_l.element(i)).collect();
+    if !verify_at_points::<P>(
+        &pp.g1_affine_srs,
+        &pp.g2_affine_srs,
+        g1_affine_left_values,
+        &fr_points,
+        &fr_values,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("opening at disclosed positions"));
+    }
+
+    Ok(())
+}
+
+/// Binds the parameters, the statement and the disclosed values before folding the columns.
+fn disclosure_challenge<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &Statement<P>,
+    indices: &[usize],
+    values: &[Vec<P::ScalarField>],
+) -> Result<P::ScalarField, Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+    transcript.append_element(Label::DisclosedIndices, &indices)?;
+    transcript.append_element(Label::DisclosedValues, &values)?;
+
+    transcript.squeeze_challenge(Label::ChallengeAlpha)
+}
@@ -1,9 +1,9 @@
 use crate::error::Error;
 use ark_ec::pairing::Pairing;
-use ark_ec::CurveGroup;
-use ark_ff::{FftField, Field};
+use ark_ff::{batch_inversion, FftField};
 use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
-use rayon::iter::IntoParallelRefMutIterator;
+use ark_std::{cfg_iter_mut, vec::Vec};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 pub(crate) fn create_domain_with_generator<F: FftField>(
@@ -63,17 +63,27 @@ pub(crate) fn roots_of_unity<P: Pairing>(
     domain.elements().collect()
 }
 
-pub fn divide_by_vanishing_poly_on_coset_in_place<C: CurveGroup>(
-    domain: &Radix2EvaluationDomain<C::ScalarField>,
-    evaluations: &mut [C::ScalarField],
+/// Divides evaluations over `domain_coset` by the vanishing polynomial of `domain`.
+///
+/// The coset may be larger than `domain`, in which case the vanishing polynomial takes
+/// `domain_coset.size() / domain.size()` distinct values that repeat over the coset.
+pub(crate) fn divide_by_vanishing_poly_on_coset_in_place<F: FftField>(
+    domain: &Radix2EvaluationDomain<F>,
+    domain_coset: &Radix2EvaluationDomain<F>,
+    evaluations: &mut [F],
 ) -> Result<(), Error> {
-    let vanishing_poly_eval = domain.evaluate_vanishing_polynomial(C::ScalarField::GENERATOR);
-    let inv_vanishing_poly_eval = vanishing_poly_eval
-        .inverse()
-        .ok_or(Error::FailedToInverseFieldElement)?;
-    evaluations
-        .par_iter_mut()
-        .for_each(|eval| *eval *= &inv_vanishing_poly_eval);
+    let ratio = domain_coset.size() / domain.size();
+    let mut inv_vanishing_poly_eval_list: Vec<F> = domain_coset.elements()
+        .take(ratio)
+        .map(|x| domain.evaluate_vanishing_polynomial(x))
+        .collect();
+    if inv_vanishing_poly_eval_list.iter().any(|eval| eval.is_zero()) {
+        return Err(Error::FailedToInverseFieldElement);
+    }
+    batch_inversion(&mut inv_vanishing_poly_eval_list);
+    cfg_iter_mut!(evaluations)
+        .enumerate()
+        .for_each(|(i, eval)| *eval *= &inv_vanishing_poly_eval_list[i % ratio]);
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,4 +1,8 @@
+use ark_serialize::SerializationError;
+use ark_std::fmt;
+
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     MissingParameter(&'static str),
     InvalidEvaluationDomainSize(usize),
@@ -9,14 +13,106 @@ pub enum Error {
     LeftIndicesCannotBeNone,
     RightIndicesCannotBeNone,
     IndexMappingCannotBeNone,
+    /// Two left positions map to the same right position.
+    MappingIsNotInjective(usize),
+    /// The blinders of a private mapping do not hide it over another opening.
+    OpeningLimitReached,
+    /// Coefficients were given for a left position that is not mapped.
+    CoefficientsForUnmappedPosition(usize),
+    /// A constant was given for a position outside the left vector.
+    ConstantPositionOutOfRange(usize),
     WrongNumberOfLeftValues(usize),
     WrongNumberOfRightValues(usize),
-    FailedToSerializeElement,
+    WrongNumberOfColumns(usize),
+    WrongNumberOfVectors(usize),
+    WrongNumberOfValues(usize),
+    /// Unpaired left and right positions differ in number, as counts of distinct positions.
+    UnequalNumberOfPositions(usize, usize),
+    /// An edge refers to a vector that does not exist.
+    InvalidVectorIndex(usize),
+    /// The vector is neither the source nor the target of any edge.
+    UnlinkedVector(usize),
+    /// The left value at the position does not appear in the right vector.
+    ValueNotFound(usize),
+    /// A selector is set at a left position that is not mapped.
+    UnmappedPosition(usize),
+    /// The SRS of the instance's parameters differs from the SRS of the other instances.
+    SrsMismatch(usize),
+    /// The named element could not be serialized.
+    FailedToSerializeElement(&'static str, SerializationError),
     RemainderAfterDivisionIsNonZero,
     FailedToCreateCosetOfEvaluationDomain,
 
-    Pairing1Failed,
-    Pairing2Failed,
-    // Pairing3Failed,
-    EqualityCheckFailed,
-}
\ No newline at end of file
+    /// The pairing check of the named opening proof failed.
+    PairingCheckFailed(&'static str),
+    /// The named equality between evaluations failed.
+    EqualityCheckFailed(&'static str),
+    /// The named part of the proof does not match the shape of the public parameters.
+    MalformedProof(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingParameter(name) => write!(f, "missing parameter: {}", name),
+            Error::InvalidEvaluationDomainSize(size) => {
+                write!(f, "invalid evaluation domain size: {}", size)
+            }
+            Error::InputShouldBePowerOfTwo(input) => {
+                write!(f, "input should be a power of two: {}", input)
+            }
+            Error::InputIsTooLarge(input) => write!(f, "input is too large: {}", input),
+            Error::FailedToCreateEvaluationDomain => write!(f, "failed to create evaluation domain"),
+            Error::FailedToInverseFieldElement => write!(f, "failed to inverse field element"),
+            Error::LeftIndicesCannotBeNone => write!(f, "left indices cannot be none"),
+            Error::RightIndicesCannotBeNone => write!(f, "right indices cannot be none"),
+            Error::IndexMappingCannotBeNone => write!(f, "index mapping cannot be none"),
+            Error::MappingIsNotInjective(index) => {
+                write!(f, "mapping is not injective at right position: {}", index)
+            }
+            Error::OpeningLimitReached => write!(f, "opening limit of the private mapping reached"),
+            Error::CoefficientsForUnmappedPosition(index) => {
+                write!(f, "coefficients for unmapped position: {}", index)
+            }
+            Error::ConstantPositionOutOfRange(index) => {
+                write!(f, "constant position is out of range: {}", index)
+            }
+            Error::WrongNumberOfLeftValues(num) => write!(f, "wrong number of left values: {}", num),
+            Error::WrongNumberOfRightValues(num) => {
+                write!(f, "wrong number of right values: {}", num)
+            }
+            Error::WrongNumberOfColumns(num) => write!(f, "wrong number of columns: {}", num),
+            Error::WrongNumberOfVectors(num) => write!(f, "wrong number of vectors: {}", num),
+            Error::WrongNumberOfValues(num) => write!(f, "wrong number of values: {}", num),
+            Error::UnequalNumberOfPositions(num_left, num_right) => {
+                write!(f, "unequal number of left and right positions: {} and {}", num_left, num_right)
+            }
+            Error::InvalidVectorIndex(index) => write!(f, "invalid vector index: {}", index),
+            Error::UnlinkedVector(index) => write!(f, "vector is not linked: {}", index),
+            Error::ValueNotFound(index) => write!(f, "left value not found in right vector: {}", index),
+            Error::UnmappedPosition(index) => write!(f, "position is not mapped: {}", index),
+            Error::SrsMismatch(index) => write!(f, "srs differs between instances: {}", index),
+            Error::FailedToSerializeElement(name, _) => {
+                write!(f, "failed to serialize element: {}", name)
+            }
+            Error::RemainderAfterDivisionIsNonZero => {
+                write!(f, "remainder after division is non-zero")
+            }
+            Error::FailedToCreateCosetOfEvaluationDomain => {
+                write!(f, "failed to create coset of evaluation domain")
+            }
+            Error::PairingCheckFailed(name) => write!(f, "pairing check failed: {}", name),
+            Error::EqualityCheckFailed(name) => write!(f, "equality check failed: {}", name),
+            Error::MalformedProof(name) => write!(f, "malformed proof: {}", name),
+        }
+    }
+}
+
+impl ark_std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn ark_std::error::Error + 'static)> {
+        match self {
+            Error::FailedToSerializeElement(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,635 @@
+//! Folds ISEP instances over the same public parameters into a running accumulator, in the
+//! style of Nova, and proves the accumulator once at the end.
+//!
+//! Over the evaluation domains, the relation of [`crate::verifier::verify`] for one column is
+//! `l * (beta + a * v + b + gamma * m) - p_l = 0`, `r * (beta + w + gamma * x) - p_r = 0`,
+//! `s * v - c = 0` and `sum(l) = sum(r)`, where `n_l * L(0) = n_r * R(0)` is the last one. The
+//! relaxed relation adds a scalar `u`, takes `beta` and `gamma` as part of the instance and
+//! error vectors `e_l` and `e_r` for the quadratic identities:
+//!
+//! - `l * (beta + a * v + u * b + gamma * m) - u^2 * p_l = e_l`,
+//! - `r * (beta + w + gamma * x) - u^2 * p_r = e_r`,
+//! - `s * v - u * c = 0` and `sum(l) = sum(r)`.
+//!
+//! A fresh instance, with `u = 1` and no error, satisfies it exactly when the ISEP relation
+//! holds. Folding two instances with a challenge `rho` takes `z_1 + rho * z_2` for every
+//! vector, scalar and commitment, and `e_1 + rho * t` for the errors, where the cross terms
+//! `t` are committed before `rho` is sampled. The decider then proves the relaxed relation of
+//! the accumulator with one quotient per domain and one batch opening.
+
+use crate::domain::roots_of_unity;
+use crate::error::Error;
+use crate::kzg::{multi_point_batch_verify, Kzg};
+use crate::prover::quotient_poly;
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::transcript::{Label, Transcript};
+use crate::verifier::opened_or;
+use crate::witness::Witness;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{batch_inversion, FftField, Field, One};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Polynomial, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{cfg_iter, vec, vec::Vec, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Commitments and scalars of a relaxed instance.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct RelaxedInstance<P: Pairing> {
+    pub g1_affine_left_values: P::G1Affine,
+    pub g1_affine_right_values: P::G1Affine,
+    pub g1_affine_l: P::G1Affine,
+    pub g1_affine_r: P::G1Affine,
+    pub g1_affine_el: P::G1Affine,
+    pub g1_affine_er: P::G1Affine,
+    pub u: P::ScalarField,
+    pub beta: P::ScalarField,
+    pub gamma: P::ScalarField,
+}
+
+impl<P: Pairing> RelaxedInstance<P> {
+    /// The instance of the empty accumulator, which every fold starts from.
+    pub fn zero() -> Self {
+        let g1_zero = P::G1Affine::zero();
+        let fr_zero = P::ScalarField::zero();
+
+        Self {
+            g1_affine_left_values: g1_zero,
+            g1_affine_right_values: g1_zero,
+            g1_affine_l: g1_zero,
+            g1_affine_r: g1_zero,
+            g1_affine_el: g1_zero,
+            g1_affine_er: g1_zero,
+            u: fr_zero,
+            beta: fr_zero,
+            gamma: fr_zero,
+        }
+    }
+
+    fn fold(&self, other: &Self, g1_affine_tl: P::G1Affine, g1_affine_tr: P::G1Affine, rho: P::ScalarField) -> Self {
+        let fold = |a: P::G1Affine, b: P::G1Affine| (a + b * rho).into_affine();
+
+        Self {
+            g1_affine_left_values: fold(self.g1_affine_left_values, other.g1_affine_left_values),
+            g1_affine_right_values: fold(self.g1_affine_right_values, other.g1_affine_right_values),
+            g1_affine_l: fold(self.g1_affine_l, other.g1_affine_l),
+            g1_affine_r: fold(self.g1_affine_r, other.g1_affine_r),
+            g1_affine_el: fold(self.g1_affine_el, g1_affine_tl),
+            g1_affine_er: fold(self.g1_affine_er, g1_affine_tr),
+            u: self.u + rho * other.u,
+            beta: self.beta + rho * other.beta,
+            gamma: self.gamma + rho * other.gamma,
+        }
+    }
+}
+
+/// Evaluations over the domains behind a relaxed instance.
+#[derive(Debug, Clone)]
+struct RelaxedWitness<F: Field> {
+    left_values: Vec<F>,
+    right_values: Vec<F>,
+    l: Vec<F>,
+    r: Vec<F>,
+    el: Vec<F>,
+    er: Vec<F>,
+}
+
+impl<F: Field> RelaxedWitness<F> {
+    fn fold(&self, other: &Self, tl: &[F], tr: &[F], rho: F) -> Self {
+        let fold = |a: &[F], b: &[F]| a.iter().zip(b.iter()).map(|(&x, &y)| x + rho * y).collect();
+
+        Self {
+            left_values: fold(&self.left_values, &other.left_values),
+            right_values: fold(&self.right_values, &other.right_values),
+            l: fold(&self.l, &other.l),
+            r: fold(&self.r, &other.r),
+            el: fold(&self.el, tl),
+            er: fold(&self.er, tr),
+        }
+    }
+}
+
+/// A relaxed instance together with its witness, held by the prover.
+#[derive(Debug, Clone)]
+pub struct Accumulator<P: Pairing> {
+    instance: RelaxedInstance<P>,
+    witness: RelaxedWitness<P::ScalarField>,
+}
+
+impl<P: Pairing> Accumulator<P> {
+    /// The empty accumulator, which satisfies the relaxed relation trivially.
+    pub fn new(pp: &PublicParameters<P>) -> Self {
+        let zeros_l = vec![P::ScalarField::zero(); pp.size_left_values];
+        let zeros_r = vec![P::ScalarField::zero(); pp.size_right_values];
+
+        Self {
+            instance: RelaxedInstance::zero(),
+            witness: RelaxedWitness {
+                left_values: zeros_l.clone(),
+                right_values: zeros_r.clone(),
+                l: zeros_l.clone(),
+                r: zeros_r.clone(),
+                el: zeros_l,
+                er: zeros_r,
+            },
+        }
+    }
+
+    pub fn instance(&self) -> &RelaxedInstance<P> {
+        &self.instance
+    }
+}
+
+/// Commitments of one fold: the fresh instance's `L` and `R` and the cross terms.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct FoldProof<P: Pairing> {
+    pub(crate) g1_affine_l: P::G1Affine,
+    pub(crate) g1_affine_r: P::G1Affine,
+    pub(crate) g1_affine_tl: P::G1Affine,
+    pub(crate) g1_affine_tr: P::G1Affine,
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DeciderProof<P: Pairing> {
+    pub(crate) g1_affine_ql: P::G1Affine,
+    pub(crate) g1_affine_qr: P::G1Affine,
+    pub(crate) batch_proof: P::G1Affine,
+    pub(crate) l_at_delta: P::ScalarField,
+    pub(crate) r_at_delta: P::ScalarField,
+    pub(crate) lv_at_delta: P::ScalarField,
+    pub(crate) rv_at_delta: P::ScalarField,
+    pub(crate) el_at_delta: P::ScalarField,
+    pub(crate) er_at_delta: P::ScalarField,
+    pub(crate) pl_at_delta: P::ScalarField,
+    pub(crate) pr_at_delta: P::ScalarField,
+    pub(crate) pm_at_delta: P::ScalarField,
+    /// Only for parameters with [`PublicParameters::has_coefficients`].
+    pub(crate) pa_at_delta: Option<P::ScalarField>,
+    pub(crate) pb_at_delta: Option<P::ScalarField>,
+    /// Only for parameters with [`PublicParameters::has_constants`].
+    pub(crate) ps_at_delta: Option<P::ScalarField>,
+    pub(crate) pc_at_delta: Option<P::ScalarField>,
+    pub(crate) l_at_zero: P::ScalarField,
+    pub(crate) r_at_zero: P::ScalarField,
+}
+
+impl<P: Pairing> DeciderProof<P> {
+    /// The evaluations in the order they enter the transcript.
+    fn evaluations(&self) -> Vec<(Label, P::ScalarField)> {
+        let mut evaluations = vec![
+            (Label::FrLAtDelta, self.l_at_delta),
+            (Label::FrRAtDelta, self.r_at_delta),
+            (Label::FrLvAtDelta, self.lv_at_delta),
+            (Label::FrRvAtDelta, self.rv_at_delta),
+            (Label::FrElAtDelta, self.el_at_delta),
+            (Label::FrErAtDelta, self.er_at_delta),
+            (Label::FrPlAtDelta, self.pl_at_delta),
+            (Label::FrPrAtDelta, self.pr_at_delta),
+            (Label::FrPmAtDelta, self.pm_at_delta),
+        ];
+        evaluations.extend(self.pa_at_delta.map(|pa_at_delta| (Label::FrPaAtDelta, pa_at_delta)));
+        evaluations.extend(self.pb_at_delta.map(|pb_at_delta| (Label::FrPbAtDelta, pb_at_delta)));
+        evaluations.extend(self.ps_at_delta.map(|ps_at_delta| (Label::FrPsAtDelta, ps_at_delta)));
+        evaluations.extend(self.pc_at_delta.map(|pc_at_delta| (Label::FrPcAtDelta, pc_at_delta)));
+        evaluations.extend([
+            (Label::FrLAtZero, self.l_at_zero),
+            (Label::FrRAtZero, self.r_at_zero),
+        ]);
+
+        evaluations
+    }
+}
+
+/// Folds the instance of a single-column witness and its statement into the accumulator.
+pub fn fold<P: Pairing>(
+    pp: &PublicParameters<P>,
+    accumulator: &Accumulator<P>,
+    witness: &Witness<P>,
+    statement: &Statement<P>,
+) -> Result<(Accumulator<P>, FoldProof<P>), Error> {
+    if witness.num_columns() != 1 {
+        return Err(Error::WrongNumberOfColumns(witness.num_columns()));
+    }
+    let mut transcript = new_transcript(pp, &accumulator.instance, statement)?;
+
+    // Sample random beta, gamma of the fresh instance.
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    let vectors = PublicVectors::new(pp);
+    let left_values = &witness.left_values[0];
+    let right_values = &witness.right_values[0];
+    let l = fractions(
+        &pp.positions_left,
+        pp.size_left_values,
+        |i| beta + vectors.a[i] * left_values[i] + vectors.b[i] + gamma * vectors.m[i],
+    )?;
+    let r = fractions(
+        &pp.positions_right,
+        pp.size_right_values,
+        |i| beta + right_values[i] + gamma * vectors.x[i],
+    )?;
+    let g1_affine_l = commit_evals(pp, &pp.domain_l, &l);
+    let g1_affine_r = commit_evals(pp, &pp.domain_r, &r);
+
+    // Cross terms of the quadratic identities, with `u = 1` for the fresh instance.
+    let acc = &accumulator.witness;
+    let (u, beta_acc, gamma_acc) = (accumulator.instance.u, accumulator.instance.beta, accumulator.instance.gamma);
+    let tl: Vec<P::ScalarField> = (0..pp.size_left_values)
+        .map(|i| {
+            let (a, b, m) = (vectors.a[i], vectors.b[i], vectors.m[i]);
+            acc.l[i] * (beta + a * left_values[i] + b + gamma * m)
+                + l[i] * (beta_acc + a * acc.left_values[i] + u * b + gamma_acc * m)
+                - (u + u) * vectors.pl[i]
+        })
+        .collect();
+    let tr: Vec<P::ScalarField> = (0..pp.size_right_values)
+        .map(|i| {
+            let x = vectors.x[i];
+            acc.r[i] * (beta + right_values[i] + gamma * x)
+                + r[i] * (beta_acc + acc.right_values[i] + gamma_acc * x)
+                - (u + u) * vectors.pr[i]
+        })
+        .collect();
+    let g1_affine_tl = commit_evals(pp, &pp.domain_l, &tl);
+    let g1_affine_tr = commit_evals(pp, &pp.domain_r, &tr);
+
+    let proof = FoldProof {
+        g1_affine_l,
+        g1_affine_r,
+        g1_affine_tl,
+        g1_affine_tr,
+    };
+    let rho = fold_challenge(&mut transcript, &proof)?;
+
+    let fresh_instance = fresh_instance(statement, &proof, beta, gamma);
+    let fresh_witness = RelaxedWitness {
+        left_values: left_values.clone(),
+        right_values: right_values.clone(),
+        l,
+        r,
+        el: vec![P::ScalarField::zero(); pp.size_left_values],
+        er: vec![P::ScalarField::zero(); pp.size_right_values],
+    };
+    let accumulator = Accumulator {
+        instance: accumulator.instance.fold(&fresh_instance, g1_affine_tl, g1_affine_tr, rho),
+        witness: acc.fold(&fresh_witness, &tl, &tr, rho),
+    };
+
+    Ok((accumulator, proof))
+}
+
+/// Folds a statement into the accumulated instance as [`fold`] did, without the witnesses.
+pub fn fold_instance<P: Pairing>(
+    pp: &PublicParameters<P>,
+    instance: &RelaxedInstance<P>,
+    statement: &Statement<P>,
+    proof: &FoldProof<P>,
+) -> Result<RelaxedInstance<P>, Error> {
+    if statement.num_columns() != 1 || statement.g1_affine_right_values.len() != 1 {
+        return Err(Error::WrongNumberOfColumns(statement.num_columns()));
+    }
+    let mut transcript = new_transcript(pp, instance, statement)?;
+
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+    let rho = fold_challenge(&mut transcript, proof)?;
+
+    let fresh_instance = fresh_instance(statement, proof, beta, gamma);
+
+    Ok(instance.fold(&fresh_instance, proof.g1_affine_tl, proof.g1_affine_tr, rho))
+}
+
+/// Proves that the accumulator satisfies the relaxed relation.
+pub fn decide<P: Pairing>(
+    pp: &PublicParameters<P>,
+    accumulator: &Accumulator<P>,
+) -> Result<DeciderProof<P>, Error> {
+    let instance = &accumulator.instance;
+    let acc = &accumulator.witness;
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_element(Label::PublicParameters, &pp.hash_representation)?;
+    transcript.append_element(Label::Accumulator, instance)?;
+
+    // Sample random eta to add the public constants to the left quotient.
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+
+    let interpolate = |domain: &Radix2EvaluationDomain<P::ScalarField>, evals: &[P::ScalarField]| {
+        DensePolynomial::from_coefficients_vec(domain.ifft(evals))
+    };
+    let poly_l = interpolate(&pp.domain_l, &acc.l);
+    let poly_r = interpolate(&pp.domain_r, &acc.r);
+    let poly_left_values = interpolate(&pp.domain_l, &acc.left_values);
+    let poly_right_values = interpolate(&pp.domain_r, &acc.right_values);
+    let poly_el = interpolate(&pp.domain_l, &acc.el);
+    let poly_er = interpolate(&pp.domain_r, &acc.er);
+
+    let (u, beta, gamma) = (instance.u, instance.beta, instance.gamma);
+    let fr_u_squared = u.square();
+
+    // Construct the quotient polynomial of the left half.
+    let coset_eval_list_l = pp.domain_coset_l.fft(&poly_l);
+    let coset_eval_list_left_values = pp.domain_coset_l.fft(&poly_left_values);
+    let coset_eval_list_el = pp.domain_coset_l.fft(&poly_el);
+    let coset_eval_list_ql: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_l)
+        .zip(cfg_iter!(coset_eval_list_left_values))
+        .zip(cfg_iter!(coset_eval_list_el))
+        .zip(cfg_iter!(pp.coset_eval_list_positions_left))
+        .zip(cfg_iter!(pp.coset_eval_list_position_mappings))
+        .zip(cfg_iter!(pp.coset_eval_list_coefficients_a))
+        .zip(cfg_iter!(pp.coset_eval_list_coefficients_b))
+        .map(|((((((&l, &v), &e), &p), &m), &a), &b)| l * (beta + a * v + u * b + gamma * m) - fr_u_squared * p - e)
+        .zip(cfg_iter!(coset_eval_list_left_values))
+        .zip(cfg_iter!(pp.coset_eval_list_constant_positions))
+        .zip(cfg_iter!(pp.coset_eval_list_constants))
+        .map(|(((n, &v), &s), &c)| n + eta * (s * v - u * c))
+        .collect();
+    let poly_ql = quotient_poly::<P>(&pp.domain_l, &pp.domain_coset_l, coset_eval_list_ql)?;
+    let g1_affine_ql = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_ql).into_affine();
+
+    // Construct the quotient polynomial of the right half.
+    let coset_eval_list_r = pp.domain_coset_r.fft(&poly_r);
+    let coset_eval_list_right_values = pp.domain_coset_r.fft(&poly_right_values);
+    let coset_eval_list_er = pp.domain_coset_r.fft(&poly_er);
+    let coset_eval_list_qr: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_r)
+        .zip(cfg_iter!(coset_eval_list_right_values))
+        .zip(cfg_iter!(coset_eval_list_er))
+        .zip(cfg_iter!(pp.coset_eval_list_positions_right))
+        .zip(cfg_iter!(pp.roots_of_unity_coset_r))
+        .map(|((((&r, &w), &e), &p), &x)| r * (beta + w + gamma * x) - fr_u_squared * p - e)
+        .collect();
+    let poly_qr = quotient_poly::<P>(&pp.domain_r, &pp.domain_coset_r, coset_eval_list_qr)?;
+    let g1_affine_qr = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_qr).into_affine();
+
+    transcript.append_elements(&[(Label::G1Ql, g1_affine_ql), (Label::G1Qr, g1_affine_qr)])?;
+
+    // Sample random delta.
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    let fr_zero = P::ScalarField::zero();
+    let proof = DeciderProof {
+        g1_affine_ql,
+        g1_affine_qr,
+        batch_proof: P::G1Affine::zero(),
+        l_at_delta: poly_l.evaluate(&delta),
+        r_at_delta: poly_r.evaluate(&delta),
+        lv_at_delta: poly_left_values.evaluate(&delta),
+        rv_at_delta: poly_right_values.evaluate(&delta),
+        el_at_delta: poly_el.evaluate(&delta),
+        er_at_delta: poly_er.evaluate(&delta),
+        pl_at_delta: pp.poly_positions_left.evaluate(&delta),
+        pr_at_delta: pp.poly_positions_right.evaluate(&delta),
+        pm_at_delta: pp.poly_position_mappings.evaluate(&delta),
+        pa_at_delta: pp.has_coefficients().then(|| pp.poly_coefficients_a.evaluate(&delta)),
+        pb_at_delta: pp.has_coefficients().then(|| pp.poly_coefficients_b.evaluate(&delta)),
+        ps_at_delta: pp.has_constants().then(|| pp.poly_constant_positions.evaluate(&delta)),
+        pc_at_delta: pp.has_constants().then(|| pp.poly_constants.evaluate(&delta)),
+        l_at_zero: poly_l.evaluate(&fr_zero),
+        r_at_zero: poly_r.evaluate(&fr_zero),
+    };
+    transcript.append_elements(&proof.evaluations())?;
+
+    // Sample random epsilon to separate the opened polynomials.
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let mut poly_list_at_delta = vec![
+        &poly_l,
+        &poly_r,
+        &poly_ql,
+        &poly_qr,
+        &poly_left_values,
+        &poly_right_values,
+        &poly_el,
+        &poly_er,
+        &pp.poly_positions_left,
+        &pp.poly_positions_right,
+        &pp.poly_position_mappings,
+    ];
+    if pp.has_coefficients() {
+        poly_list_at_delta.extend([&pp.poly_coefficients_a, &pp.poly_coefficients_b]);
+    }
+    if pp.has_constants() {
+        poly_list_at_delta.extend([&pp.poly_constant_positions, &pp.poly_constants]);
+    }
+    let batch_proof = Kzg::<P::G1>::multi_point_batch_open(
+        &pp.g1_affine_srs,
+        &[&poly_list_at_delta, &[&poly_l, &poly_r]],
+        &[delta, fr_zero],
+        epsilon,
+    );
+
+    Ok(DeciderProof { batch_proof, ..proof })
+}
+
+/// Checks a proof of [`decide`] against the accumulated instance, which the verifier obtained
+/// from [`fold_instance`].
+pub fn verify_decider<P: Pairing>(
+    pp: &PublicParameters<P>,
+    instance: &RelaxedInstance<P>,
+    proof: &DeciderProof<P>,
+) -> Result<(), Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_element(Label::PublicParameters, &pp.hash_representation)?;
+    transcript.append_element(Label::Accumulator, instance)?;
+
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+
+    transcript.append_elements(&[(Label::G1Ql, proof.g1_affine_ql), (Label::G1Qr, proof.g1_affine_qr)])?;
+
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    transcript.append_elements(&proof.evaluations())?;
+
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let (u, beta, gamma) = (instance.u, instance.beta, instance.gamma);
+    let fr_u_squared = u.square();
+    let fr_one = P::ScalarField::one();
+    let has_coefficients = pp.has_coefficients();
+    let [pa_at_delta, pb_at_delta] = opened_or(
+        has_coefficients,
+        [proof.pa_at_delta, proof.pb_at_delta],
+        [fr_one, P::ScalarField::zero()],
+        "coefficient evaluations",
+    )?;
+    let has_constants = pp.has_constants();
+    let [ps_at_delta, pc_at_delta] = opened_or(
+        has_constants,
+        [proof.ps_at_delta, proof.pc_at_delta],
+        [P::ScalarField::zero(); 2],
+        "constant evaluations",
+    )?;
+
+    let fr_zl_at_delta = delta.pow([pp.size_left_values as u64]) - fr_one;
+    let fr_inv_zl_at_delta = fr_zl_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let fr_ql_at_delta = proof.l_at_delta
+        * (beta + pa_at_delta * proof.lv_at_delta + u * pb_at_delta + gamma * proof.pm_at_delta)
+        - fr_u_squared * proof.pl_at_delta
+        - proof.el_at_delta
+        + eta * (ps_at_delta * proof.lv_at_delta - u * pc_at_delta);
+    let fr_ql_at_delta = fr_ql_at_delta * fr_inv_zl_at_delta;
+
+    let fr_zr_at_delta = delta.pow([pp.size_right_values as u64]) - fr_one;
+    let fr_inv_zr_at_delta = fr_zr_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let fr_qr_at_delta = proof.r_at_delta * (beta + proof.rv_at_delta + gamma * delta)
+        - fr_u_squared * proof.pr_at_delta
+        - proof.er_at_delta;
+    let fr_qr_at_delta = fr_qr_at_delta * fr_inv_zr_at_delta;
+
+    // Pairing check of the batch proof at delta and zero.
+    let mut g1_list_at_delta = vec![
+        instance.g1_affine_l,
+        instance.g1_affine_r,
+        proof.g1_affine_ql,
+        proof.g1_affine_qr,
+        instance.g1_affine_left_values,
+        instance.g1_affine_right_values,
+        instance.g1_affine_el,
+        instance.g1_affine_er,
+        pp.g1_affine_positions_left,
+        pp.g1_affine_positions_right,
+        pp.g1_affine_position_mappings,
+    ];
+    let mut fr_list_at_delta = vec![
+        proof.l_at_delta,
+        proof.r_at_delta,
+        fr_ql_at_delta,
+        fr_qr_at_delta,
+        proof.lv_at_delta,
+        proof.rv_at_delta,
+        proof.el_at_delta,
+        proof.er_at_delta,
+        proof.pl_at_delta,
+        proof.pr_at_delta,
+        proof.pm_at_delta,
+    ];
+    if has_coefficients {
+        g1_list_at_delta.extend([pp.g1_affine_coefficients_a, pp.g1_affine_coefficients_b]);
+        fr_list_at_delta.extend([pa_at_delta, pb_at_delta]);
+    }
+    if has_constants {
+        g1_list_at_delta.extend([pp.g1_affine_constant_positions, pp.g1_affine_constants]);
+        fr_list_at_delta.extend([ps_at_delta, pc_at_delta]);
+    }
+    let g1_list_at_zero = [instance.g1_affine_l, instance.g1_affine_r];
+    let fr_list_at_zero = [proof.l_at_zero, proof.r_at_zero];
+
+    if !multi_point_batch_verify::<P>(
+        pp.g1_affine_srs[0],
+        &pp.g2_affine_srs,
+        &[&g1_list_at_delta, &g1_list_at_zero],
+        &[&fr_list_at_delta, &fr_list_at_zero],
+        &[delta, P::ScalarField::zero()],
+        epsilon,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("batch opening at delta and zero"));
+    }
+
+    // Sumcheck Lemma, which is linear and holds for the accumulator as for every instance.
+    if proof.l_at_zero * P::ScalarField::from(pp.size_left_values as u64) != proof.r_at_zero * P::ScalarField::from(pp.size_right_values as u64) {
+        return Err(Error::EqualityCheckFailed("sumcheck of left and right halves"));
+    }
+
+    Ok(())
+}
+
+/// Evaluations of the public polynomials over the left and right domains.
+struct PublicVectors<F: FftField> {
+    a: Vec<F>,
+    b: Vec<F>,
+    m: Vec<F>,
+    pl: Vec<F>,
+    pr: Vec<F>,
+    x: Vec<F>,
+}
+
+impl<F: FftField> PublicVectors<F> {
+    fn new<P: Pairing<ScalarField = F>>(pp: &PublicParameters<P>) -> Self {
+        Self {
+            a: pp.domain_l.fft(&pp.poly_coefficients_a),
+            b: pp.domain_l.fft(&pp.poly_coefficients_b),
+            m: pp.domain_l.fft(&pp.poly_position_mappings),
+            pl: pp.domain_l.fft(&pp.poly_positions_left),
+            pr: pp.domain_r.fft(&pp.poly_positions_right),
+            x: roots_of_unity::<P>(&pp.domain_r),
+        }
+    }
+}
+
+/// Evaluations over a domain of size `size` that are `1 / denominator(i)` at each position and
+/// zero elsewhere.
+fn fractions<F: Field>(
+    positions: &[usize],
+    size: usize,
+    denominator: impl Fn(usize) -> F,
+) -> Result<Vec<F>, Error> {
+    let mut denominators: Vec<F> = positions.iter().map(|&i| denominator(i)).collect();
+    if denominators.iter().any(|d| d.is_zero()) {
+        return Err(Error::FailedToInverseFieldElement);
+    }
+    batch_inversion(&mut denominators);
+
+    let mut evals = vec![F::zero(); size];
+    positions.iter().zip(denominators.iter()).for_each(|(&i, &inv)| evals[i] = inv);
+
+    Ok(evals)
+}
+
+fn commit_evals<P: Pairing>(
+    pp: &PublicParameters<P>,
+    domain: &Radix2EvaluationDomain<P::ScalarField>,
+    evals: &[P::ScalarField],
+) -> P::G1Affine {
+    let poly = DensePolynomial::from_coefficients_vec(domain.ifft(evals));
+
+    Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly).into_affine()
+}
+
+fn fresh_instance<P: Pairing>(
+    statement: &Statement<P>,
+    proof: &FoldProof<P>,
+    beta: P::ScalarField,
+    gamma: P::ScalarField,
+) -> RelaxedInstance<P> {
+    RelaxedInstance {
+        g1_affine_left_values: statement.g1_affine_left_values[0],
+        g1_affine_right_values: statement.g1_affine_right_values[0],
+        g1_affine_l: proof.g1_affine_l,
+        g1_affine_r: proof.g1_affine_r,
+        g1_affine_el: P::G1Affine::zero(),
+        g1_affine_er: P::G1Affine::zero(),
+        u: P::ScalarField::one(),
+        beta,
+        gamma,
+    }
+}
+
+/// Binds the parameters, the accumulated instance and the fresh statement.
+fn new_transcript<P: Pairing>(
+    pp: &PublicParameters<P>,
+    instance: &RelaxedInstance<P>,
+    statement: &Statement<P>,
+) -> Result<Transcript<P::ScalarField>, Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+    transcript.append_element(Label::Accumulator, instance)?;
+
+    Ok(transcript)
+}
+
+fn fold_challenge<P: Pairing>(
+    transcript: &mut Transcript<P::ScalarField>,
+    proof: &FoldProof<P>,
+) -> Result<P::ScalarField, Error> {
+    transcript.append_elements(&[(Label::G1L, proof.g1_affine_l), (Label::G1R, proof.g1_affine_r)])?;
+    transcript.append_element(Label::CrossTerms, &(proof.g1_affine_tl, proof.g1_affine_tr))?;
+
+    transcript.squeeze_challenge(Label::ChallengeRho)
+}
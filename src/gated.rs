@@ -0,0 +1,418 @@
+//! Proves the mapping of the public parameters only where a committed selector is set.
+//!
+//! The prover commits to a boolean selector `s` over the left domain, and the verifier learns
+//! only its commitment. The argument gates the left values to `u_i = s_i * v_i` and the right
+//! values to `u'_j = t_j * w_j`, where `t_j` is the selector carried over to the mapped right
+//! position, and proves the tuples `(u, s)` equal at every mapped pair. A pair with `s_i = 1`
+//! thus has equal values, and a pair with `s_i = 0` holds nothing. The coefficients and
+//! constants of the public parameters are not used.
+
+use crate::error::Error;
+use crate::kzg::{multi_point_batch_verify, powers_of_scalars, Kzg};
+use crate::prover::{fractional_poly, quotient_poly};
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::transcript::{Label, Transcript};
+use crate::witness::{fold_columns, Witness};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Polynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{cfg_iter, vec, vec::Vec, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// A boolean vector over the left domain that activates mapped positions.
+pub struct Selector<P: Pairing> {
+    pub(crate) values: Vec<P::ScalarField>,
+    pub(crate) poly_selector: DensePolynomial<P::ScalarField>,
+    pub g1_affine_selector: P::G1Affine,
+}
+
+impl<P: Pairing> Selector<P> {
+    /// Sets the selector at the given mapped left positions.
+    pub fn new(pp: &PublicParameters<P>, active_positions: &[usize]) -> Result<Self, Error> {
+        if let Some(&i) = active_positions.iter().find(|&i| !pp.position_mappings.contains_key(i)) {
+            return Err(Error::UnmappedPosition(i));
+        }
+
+        let mut values = vec![P::ScalarField::zero(); pp.size_left_values];
+        active_positions.iter().for_each(|&i| values[i] = P::ScalarField::one());
+        let poly_selector = DensePolynomial::from_coefficients_vec(pp.domain_l.ifft(&values));
+        let g1_affine_selector = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_selector).into_affine();
+
+        Ok(Self {
+            values,
+            poly_selector,
+            g1_affine_selector,
+        })
+    }
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GatedProof<P: Pairing> {
+    pub(crate) g1_affine_ul: P::G1Affine,
+    pub(crate) g1_affine_ur: P::G1Affine,
+    pub(crate) g1_affine_t: P::G1Affine,
+    pub(crate) g1_affine_l: P::G1Affine,
+    pub(crate) g1_affine_r: P::G1Affine,
+    pub(crate) g1_affine_ql: P::G1Affine,
+    pub(crate) g1_affine_qr: P::G1Affine,
+    pub(crate) batch_proof: P::G1Affine,
+    pub(crate) l_at_delta: P::ScalarField,
+    pub(crate) r_at_delta: P::ScalarField,
+    pub(crate) lv_at_delta: P::ScalarField,
+    pub(crate) rv_at_delta: P::ScalarField,
+    pub(crate) ul_at_delta: P::ScalarField,
+    pub(crate) ur_at_delta: P::ScalarField,
+    pub(crate) s_at_delta: P::ScalarField,
+    pub(crate) t_at_delta: P::ScalarField,
+    pub(crate) pl_at_delta: P::ScalarField,
+    pub(crate) pr_at_delta: P::ScalarField,
+    pub(crate) pm_at_delta: P::ScalarField,
+    pub(crate) l_at_zero: P::ScalarField,
+    pub(crate) r_at_zero: P::ScalarField,
+}
+
+pub fn prove<P: Pairing>(
+    pp: &PublicParameters<P>,
+    witness: &Witness<P>,
+    selector: &Selector<P>,
+    statement: &Statement<P>,
+) -> Result<GatedProof<P>, Error> {
+    pp.ensure_mapping()?;
+
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+    transcript.append_element(Label::G1S, &selector.g1_affine_selector)?;
+
+    // Sample random alpha to fold the columns.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+
+    let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+    let (left_values, poly_left_values) =
+        fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+    let (right_values, poly_right_values) =
+        fold_columns(&witness.right_values, &witness.poly_right_values, &powers_of_alpha);
+
+    // Gate the values and carry the selector over to the right positions.
+    let fr_zero = P::ScalarField::zero();
+    let gated_left_values: Vec<P::ScalarField> = cfg_iter!(left_values)
+        .zip(cfg_iter!(selector.values))
+        .map(|(&v, &s)| s * v)
+        .collect();
+    let mut right_selector = vec![fr_zero; pp.size_right_values];
+    pp.position_mappings.iter().zip(pp.positions_right.iter()).for_each(|((&i, _), &j)| {
+        right_selector[j] = selector.values[i];
+    });
+    let gated_right_values: Vec<P::ScalarField> = cfg_iter!(right_values)
+        .zip(cfg_iter!(right_selector))
+        .map(|(&w, &t)| t * w)
+        .collect();
+    let poly_ul = DensePolynomial::from_coefficients_vec(pp.domain_l.ifft(&gated_left_values));
+    let poly_ur = DensePolynomial::from_coefficients_vec(pp.domain_r.ifft(&gated_right_values));
+    let poly_t = DensePolynomial::from_coefficients_vec(pp.domain_r.ifft(&right_selector));
+    let g1_affine_ul = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_ul).into_affine();
+    let g1_affine_ur = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_ur).into_affine();
+    let g1_affine_t = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_t).into_affine();
+
+    transcript.append_element(Label::G1U, &[g1_affine_ul, g1_affine_ur])?;
+    transcript.append_element(Label::G1T, &g1_affine_t)?;
+
+    // Sample random beta, gamma.
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+    let gamma_square = gamma.square();
+
+    let fr_one = P::ScalarField::one();
+    let fraction_list_l = pp.positions_left.iter()
+        .map(|&i| {
+            let denominator = beta + gated_left_values[i] + gamma * pp.position_mappings[&i]
+                + gamma_square * selector.values[i];
+
+            (i, fr_one, denominator)
+        })
+        .collect();
+    let poly_l = fractional_poly(&pp.domain_l, fraction_list_l)?;
+    let g1_affine_l = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_l).into_affine();
+
+    let fraction_list_r = pp.positions_right.iter()
+        .map(|&j| {
+            let denominator = beta + gated_right_values[j] + gamma * pp.domain_r.element(j)
+                + gamma_square * right_selector[j];
+
+            (j, fr_one, denominator)
+        })
+        .collect();
+    let poly_r = fractional_poly(&pp.domain_r, fraction_list_r)?;
+    let g1_affine_r = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_r).into_affine();
+
+    transcript.append_elements(&[(Label::G1L, g1_affine_l), (Label::G1R, g1_affine_r)])?;
+
+    // Sample random eta to add the gating and booleanity identities to the quotients.
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+    let eta_square = eta.square();
+
+    // Construct the quotient polynomial of the left half.
+    let coset_eval_list_l = pp.domain_coset_l.fft(&poly_l);
+    let coset_eval_list_left_values = pp.domain_coset_l.fft(&poly_left_values);
+    let coset_eval_list_ul = pp.domain_coset_l.fft(&poly_ul);
+    let coset_eval_list_s = pp.domain_coset_l.fft(&selector.poly_selector);
+    let coset_eval_list_ql: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_l)
+        .zip(cfg_iter!(coset_eval_list_ul))
+        .zip(cfg_iter!(coset_eval_list_s))
+        .zip(cfg_iter!(pp.coset_eval_list_positions_left))
+        .zip(cfg_iter!(pp.coset_eval_list_position_mappings))
+        .zip(cfg_iter!(coset_eval_list_left_values))
+        .map(|(((((&l, &u), &s), &p), &m), &v)| {
+            l * (beta + u + gamma * m + gamma_square * s) - p + eta * (u - s * v) + eta_square * (s * s - s)
+        })
+        .collect();
+    let poly_ql = quotient_poly::<P>(&pp.domain_l, &pp.domain_coset_l, coset_eval_list_ql)?;
+    let g1_affine_ql = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_ql).into_affine();
+
+    // Construct the quotient polynomial of the right half.
+    let coset_eval_list_r = pp.domain_coset_r.fft(&poly_r);
+    let coset_eval_list_right_values = pp.domain_coset_r.fft(&poly_right_values);
+    let coset_eval_list_ur = pp.domain_coset_r.fft(&poly_ur);
+    let coset_eval_list_t = pp.domain_coset_r.fft(&poly_t);
+    let coset_eval_list_qr: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_r)
+        .zip(cfg_iter!(coset_eval_list_ur))
+        .zip(cfg_iter!(coset_eval_list_t))
+        .zip(cfg_iter!(pp.coset_eval_list_positions_right))
+        .zip(cfg_iter!(pp.roots_of_unity_coset_r))
+        .zip(cfg_iter!(coset_eval_list_right_values))
+        .map(|(((((&r, &u), &t), &p), &c), &w)| {
+            r * (beta + u + gamma * c + gamma_square * t) - p + eta * (u - t * w)
+        })
+        .collect();
+    let poly_qr = quotient_poly::<P>(&pp.domain_r, &pp.domain_coset_r, coset_eval_list_qr)?;
+    let g1_affine_qr = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_qr).into_affine();
+
+    transcript.append_elements(&[(Label::G1Ql, g1_affine_ql), (Label::G1Qr, g1_affine_qr)])?;
+
+    // Sample random delta.
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    let l_at_delta = poly_l.evaluate(&delta);
+    let r_at_delta = poly_r.evaluate(&delta);
+    let lv_at_delta = poly_left_values.evaluate(&delta);
+    let rv_at_delta = poly_right_values.evaluate(&delta);
+    let ul_at_delta = poly_ul.evaluate(&delta);
+    let ur_at_delta = poly_ur.evaluate(&delta);
+    let s_at_delta = selector.poly_selector.evaluate(&delta);
+    let t_at_delta = poly_t.evaluate(&delta);
+    let pl_at_delta = pp.poly_positions_left.evaluate(&delta);
+    let pr_at_delta = pp.poly_positions_right.evaluate(&delta);
+    let pm_at_delta = pp.poly_position_mappings.evaluate(&delta);
+    let l_at_zero = poly_l.evaluate(&fr_zero);
+    let r_at_zero = poly_r.evaluate(&fr_zero);
+
+    transcript.append_elements(
+        &[
+            (Label::FrLAtDelta, l_at_delta),
+            (Label::FrRAtDelta, r_at_delta),
+            (Label::FrLvAtDelta, lv_at_delta),
+            (Label::FrRvAtDelta, rv_at_delta),
+            (Label::FrUlAtDelta, ul_at_delta),
+            (Label::FrUrAtDelta, ur_at_delta),
+            (Label::FrSAtDelta, s_at_delta),
+            (Label::FrTAtDelta, t_at_delta),
+            (Label::FrPlAtDelta, pl_at_delta),
+            (Label::FrPrAtDelta, pr_at_delta),
+            (Label::FrPmAtDelta, pm_at_delta),
+            (Label::FrLAtZero, l_at_zero),
+            (Label::FrRAtZero, r_at_zero),
+        ]
+    )?;
+
+    // Sample random epsilon to separate the opened polynomials.
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let batch_proof = Kzg::<P::G1>::multi_point_batch_open(
+        &pp.g1_affine_srs,
+        &[
+            &[
+                &poly_l,
+                &poly_r,
+                &poly_ql,
+                &poly_qr,
+                &poly_left_values,
+                &poly_right_values,
+                &poly_ul,
+                &poly_ur,
+                &selector.poly_selector,
+                &poly_t,
+                &pp.poly_positions_left,
+                &pp.poly_positions_right,
+                &pp.poly_position_mappings,
+            ],
+            &[&poly_l, &poly_r],
+        ],
+        &[delta, fr_zero],
+        epsilon,
+    );
+
+    Ok(GatedProof {
+        g1_affine_ul,
+        g1_affine_ur,
+        g1_affine_t,
+        g1_affine_l,
+        g1_affine_r,
+        g1_affine_ql,
+        g1_affine_qr,
+        batch_proof,
+        l_at_delta,
+        r_at_delta,
+        lv_at_delta,
+        rv_at_delta,
+        ul_at_delta,
+        ur_at_delta,
+        s_at_delta,
+        t_at_delta,
+        pl_at_delta,
+        pr_at_delta,
+        pm_at_delta,
+        l_at_zero,
+        r_at_zero,
+    })
+}
+
+pub fn verify<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &Statement<P>,
+    g1_affine_selector: &P::G1Affine,
+    proof: &GatedProof<P>,
+) -> Result<(), Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+    transcript.append_element(Label::G1S, g1_affine_selector)?;
+
+    if statement.g1_affine_left_values.is_empty()
+        || statement.g1_affine_left_values.len() != statement.g1_affine_right_values.len()
+    {
+        return Err(Error::WrongNumberOfColumns(statement.g1_affine_right_values.len()));
+    }
+
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    transcript.append_element(Label::G1U, &[proof.g1_affine_ul, proof.g1_affine_ur])?;
+    transcript.append_element(Label::G1T, &proof.g1_affine_t)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+    transcript.append_elements(&[(Label::G1L, proof.g1_affine_l), (Label::G1R, proof.g1_affine_r)])?;
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+    transcript.append_elements(&[(Label::G1Ql, proof.g1_affine_ql), (Label::G1Qr, proof.g1_affine_qr)])?;
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    transcript.append_elements(
+        &[
+            (Label::FrLAtDelta, proof.l_at_delta),
+            (Label::FrRAtDelta, proof.r_at_delta),
+            (Label::FrLvAtDelta, proof.lv_at_delta),
+            (Label::FrRvAtDelta, proof.rv_at_delta),
+            (Label::FrUlAtDelta, proof.ul_at_delta),
+            (Label::FrUrAtDelta, proof.ur_at_delta),
+            (Label::FrSAtDelta, proof.s_at_delta),
+            (Label::FrTAtDelta, proof.t_at_delta),
+            (Label::FrPlAtDelta, proof.pl_at_delta),
+            (Label::FrPrAtDelta, proof.pr_at_delta),
+            (Label::FrPmAtDelta, proof.pm_at_delta),
+            (Label::FrLAtZero, proof.l_at_zero),
+            (Label::FrRAtZero, proof.r_at_zero),
+        ]
+    )?;
+
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let fr_one = P::ScalarField::one();
+    let gamma_square = gamma.square();
+    let eta_square = eta.square();
+
+    let fr_zl_at_delta = delta.pow([pp.size_left_values as u64]) - fr_one;
+    let fr_inv_zl_at_delta = fr_zl_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let s = proof.s_at_delta;
+    let fr_ql_at_delta = proof.l_at_delta
+        * (beta + proof.ul_at_delta + gamma * proof.pm_at_delta + gamma_square * s)
+        - proof.pl_at_delta
+        + eta * (proof.ul_at_delta - s * proof.lv_at_delta)
+        + eta_square * (s * s - s);
+    let fr_ql_at_delta = fr_ql_at_delta * fr_inv_zl_at_delta;
+
+    let fr_zr_at_delta = delta.pow([pp.size_right_values as u64]) - fr_one;
+    let fr_inv_zr_at_delta = fr_zr_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let t = proof.t_at_delta;
+    let fr_qr_at_delta = proof.r_at_delta * (beta + proof.ur_at_delta + gamma * delta + gamma_square * t)
+        - proof.pr_at_delta
+        + eta * (proof.ur_at_delta - t * proof.rv_at_delta);
+    let fr_qr_at_delta = fr_qr_at_delta * fr_inv_zr_at_delta;
+
+    // Fold the column commitments as the prover folded the columns.
+    let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+    let g1_affine_left_values = P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha)
+        .into_affine();
+    let g1_affine_right_values = P::G1::msm_unchecked(&statement.g1_affine_right_values, &powers_of_alpha)
+        .into_affine();
+
+    if !multi_point_batch_verify::<P>(
+        pp.g1_affine_srs[0],
+        &pp.g2_affine_srs,
+        &[
+            &[
+                proof.g1_affine_l,
+                proof.g1_affine_r,
+                proof.g1_affine_ql,
+                proof.g1_affine_qr,
+                g1_affine_left_values,
+                g1_affine_right_values,
+                proof.g1_affine_ul,
+                proof.g1_affine_ur,
+                *g1_affine_selector,
+                proof.g1_affine_t,
+                pp.g1_affine_positions_left,
+                pp.g1_affine_positions_right,
+                pp.g1_affine_position_mappings,
+            ],
+            &[proof.g1_affine_l, proof.g1_affine_r],
+        ],
+        &[
+            &[
+                proof.l_at_delta,
+                proof.r_at_delta,
+                fr_ql_at_delta,
+                fr_qr_at_delta,
+                proof.lv_at_delta,
+                proof.rv_at_delta,
+                proof.ul_at_delta,
+                proof.ur_at_delta,
+                proof.s_at_delta,
+                proof.t_at_delta,
+                proof.pl_at_delta,
+                proof.pr_at_delta,
+                proof.pm_at_delta,
+            ],
+            &[proof.l_at_zero, proof.r_at_zero],
+        ],
+        &[delta, P::ScalarField::zero()],
+        epsilon,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("batch opening at delta and zero"));
+    }
+
+    // Sumcheck Lemma.
+    if proof.l_at_zero * P::ScalarField::from(pp.size_left_values as u64)
+        != proof.r_at_zero * P::ScalarField::from(pp.size_right_values as u64)
+    {
+        return Err(Error::EqualityCheckFailed("sumcheck of left and right halves"));
+    }
+
+    Ok(())
+}
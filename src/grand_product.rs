@@ -0,0 +1,491 @@
+//! A grand-product backend for the relation of [`crate::prover::prove`], in the style of the
+//! Plonk permutation argument.
+//!
+//! Instead of comparing sums of fractions, it compares the products of the denominators. Over
+//! the left domain, `F_L` is `beta + a * v + b + gamma * m` at the mapped positions and one
+//! elsewhere, and the accumulator `Z_L` starts at one and satisfies
+//! `Z_L(omega * X) = Z_L(X) * F_L(X)`, except at the last point, where `Z_L(X) * F_L(X)` is the
+//! claimed product `pi`. The right domain is handled alike with `beta + w + gamma * X`, and both
+//! products are the same `pi`. The accumulators are opened at `delta` and at `delta` shifted by
+//! the generator of their domain. No field element is inverted while proving.
+
+use crate::error::Error;
+use crate::kzg::{multi_point_batch_verify, powers_of_scalars, Kzg};
+use crate::prover::quotient_poly;
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::transcript::{Label, Transcript};
+use crate::verifier::opened_or;
+use crate::witness::{fold_columns, Witness};
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{FftField, Field, One, Zero};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Polynomial, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{cfg_iter, vec, vec::Vec};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GrandProductProof<P: Pairing> {
+    pub(crate) g1_affine_fl: P::G1Affine,
+    pub(crate) g1_affine_fr: P::G1Affine,
+    pub(crate) g1_affine_zl: P::G1Affine,
+    pub(crate) g1_affine_zr: P::G1Affine,
+    pub(crate) g1_affine_ql: P::G1Affine,
+    pub(crate) g1_affine_qr: P::G1Affine,
+    pub(crate) batch_proof: P::G1Affine,
+    pub(crate) product: P::ScalarField,
+    pub(crate) fl_at_delta: P::ScalarField,
+    pub(crate) fr_at_delta: P::ScalarField,
+    pub(crate) zl_at_delta: P::ScalarField,
+    pub(crate) zr_at_delta: P::ScalarField,
+    pub(crate) zl_at_shifted_delta: P::ScalarField,
+    pub(crate) zr_at_shifted_delta: P::ScalarField,
+    pub(crate) lv_at_delta: P::ScalarField,
+    pub(crate) rv_at_delta: P::ScalarField,
+    pub(crate) pl_at_delta: P::ScalarField,
+    pub(crate) pr_at_delta: P::ScalarField,
+    pub(crate) pm_at_delta: P::ScalarField,
+    /// Only for parameters with [`PublicParameters::has_coefficients`].
+    pub(crate) pa_at_delta: Option<P::ScalarField>,
+    pub(crate) pb_at_delta: Option<P::ScalarField>,
+    /// Only for parameters with [`PublicParameters::has_constants`].
+    pub(crate) lv0_at_delta: Option<P::ScalarField>,
+    pub(crate) ps_at_delta: Option<P::ScalarField>,
+    pub(crate) pc_at_delta: Option<P::ScalarField>,
+}
+
+pub fn prove<P: Pairing>(
+    pp: &PublicParameters<P>,
+    witness: &Witness<P>,
+    statement: &Statement<P>,
+) -> Result<GrandProductProof<P>, Error> {
+    pp.ensure_mapping()?;
+
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    // Sample random alpha, beta, gamma.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    // Fold the columns of each side into one, so that whole tuples are compared.
+    let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+    let (left_values, poly_left_values) =
+        fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+    let (right_values, poly_right_values) =
+        fold_columns(&witness.right_values, &witness.poly_right_values, &powers_of_alpha);
+    let fr_sum_of_alpha: P::ScalarField = powers_of_alpha.iter().sum();
+
+    // Construct the factors and the accumulators of both halves.
+    let fr_one = P::ScalarField::one();
+    let mut eval_list_fl = vec![fr_one; pp.size_left_values];
+    pp.positions_left.iter().for_each(|&i| {
+        let (a, b) = pp.coefficients(i);
+        eval_list_fl[i] = beta + a * left_values[i] + fr_sum_of_alpha * b + gamma * pp.position_mappings[&i];
+    });
+    let mut eval_list_fr = vec![fr_one; pp.size_right_values];
+    pp.positions_right.iter().for_each(|&i| {
+        eval_list_fr[i] = beta + right_values[i] + gamma * pp.domain_r.element(i);
+    });
+    let (eval_list_zl, product) = accumulate(&eval_list_fl);
+    let (eval_list_zr, _) = accumulate(&eval_list_fr);
+
+    let poly_fl = DensePolynomial::from_coefficients_vec(pp.domain_l.ifft(&eval_list_fl));
+    let poly_fr = DensePolynomial::from_coefficients_vec(pp.domain_r.ifft(&eval_list_fr));
+    let poly_zl = DensePolynomial::from_coefficients_vec(pp.domain_l.ifft(&eval_list_zl));
+    let poly_zr = DensePolynomial::from_coefficients_vec(pp.domain_r.ifft(&eval_list_zr));
+    let g1_affine_fl = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_fl).into_affine();
+    let g1_affine_fr = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_fr).into_affine();
+    let g1_affine_zl = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_zl).into_affine();
+    let g1_affine_zr = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_zr).into_affine();
+
+    transcript.append_elements(&[
+        (Label::G1F, (g1_affine_fl, g1_affine_fr)),
+        (Label::G1Z, (g1_affine_zl, g1_affine_zr)),
+    ])?;
+    transcript.append_element(Label::FrProduct, &product)?;
+
+    // Sample random eta to combine the identities of each half.
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+    let eta_squared = eta.square();
+    let eta_cubed = eta_squared * eta;
+
+    // Construct the quotient polynomial of the left half.
+    let coset_eval_list_fl = pp.domain_coset_l.fft(&poly_fl);
+    let coset_eval_list_zl = pp.domain_coset_l.fft(&poly_zl);
+    let coset_eval_list_shifted_zl = pp.domain_coset_l.fft(&shift_poly(&poly_zl, pp.domain_l.group_gen));
+    let coset_eval_list_left_values = pp.domain_coset_l.fft(&poly_left_values);
+    let coset_eval_list_first_column = pp.domain_coset_l.fft(&witness.poly_left_values[0]);
+    let (coset_eval_list_first_l, coset_eval_list_last_l) = lagrange_first_and_last(&pp.domain_l, &pp.domain_coset_l);
+    let coset_eval_list_ql: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_fl)
+        .zip(cfg_iter!(coset_eval_list_left_values))
+        .zip(cfg_iter!(pp.coset_eval_list_positions_left))
+        .zip(cfg_iter!(pp.coset_eval_list_position_mappings))
+        .zip(cfg_iter!(pp.coset_eval_list_coefficients_a))
+        .zip(cfg_iter!(pp.coset_eval_list_coefficients_b))
+        .map(|(((((&f, &v), &p), &m), &a), &b)| f - fr_one - p * (beta + a * v + fr_sum_of_alpha * b + gamma * m - fr_one))
+        .zip(cfg_iter!(coset_eval_list_fl))
+        .zip(cfg_iter!(coset_eval_list_zl))
+        .zip(cfg_iter!(coset_eval_list_shifted_zl))
+        .zip(cfg_iter!(coset_eval_list_first_l))
+        .zip(cfg_iter!(coset_eval_list_last_l))
+        .map(|(((((n, &f), &z), &zs), &first), &last)| {
+            n + eta * (zs * (fr_one + last * (product - fr_one)) - z * f) + eta_squared * first * (z - fr_one)
+        })
+        .zip(cfg_iter!(coset_eval_list_first_column))
+        .zip(cfg_iter!(pp.coset_eval_list_constant_positions))
+        .zip(cfg_iter!(pp.coset_eval_list_constants))
+        .map(|(((n, &v0), &s), &c)| n + eta_cubed * (s * v0 - c))
+        .collect();
+    let poly_ql = quotient_poly::<P>(&pp.domain_l, &pp.domain_coset_l, coset_eval_list_ql)?;
+    let g1_affine_ql = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_ql).into_affine();
+
+    // Construct the quotient polynomial of the right half.
+    let coset_eval_list_fr = pp.domain_coset_r.fft(&poly_fr);
+    let coset_eval_list_zr = pp.domain_coset_r.fft(&poly_zr);
+    let coset_eval_list_shifted_zr = pp.domain_coset_r.fft(&shift_poly(&poly_zr, pp.domain_r.group_gen));
+    let coset_eval_list_right_values = pp.domain_coset_r.fft(&poly_right_values);
+    let (coset_eval_list_first_r, coset_eval_list_last_r) = lagrange_first_and_last(&pp.domain_r, &pp.domain_coset_r);
+    let coset_eval_list_qr: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_fr)
+        .zip(cfg_iter!(coset_eval_list_right_values))
+        .zip(cfg_iter!(pp.coset_eval_list_positions_right))
+        .zip(cfg_iter!(pp.roots_of_unity_coset_r))
+        .map(|(((&f, &w), &p), &x)| f - fr_one - p * (beta + w + gamma * x - fr_one))
+        .zip(cfg_iter!(coset_eval_list_fr))
+        .zip(cfg_iter!(coset_eval_list_zr))
+        .zip(cfg_iter!(coset_eval_list_shifted_zr))
+        .zip(cfg_iter!(coset_eval_list_first_r))
+        .zip(cfg_iter!(coset_eval_list_last_r))
+        .map(|(((((n, &f), &z), &zs), &first), &last)| {
+            n + eta * (zs * (fr_one + last * (product - fr_one)) - z * f) + eta_squared * first * (z - fr_one)
+        })
+        .collect();
+    let poly_qr = quotient_poly::<P>(&pp.domain_r, &pp.domain_coset_r, coset_eval_list_qr)?;
+    let g1_affine_qr = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_qr).into_affine();
+
+    transcript.append_elements(&[(Label::G1Ql, g1_affine_ql), (Label::G1Qr, g1_affine_qr)])?;
+
+    // Sample random delta.
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+    let shifted_delta_l = delta * pp.domain_l.group_gen;
+    let shifted_delta_r = delta * pp.domain_r.group_gen;
+
+    let mut proof = GrandProductProof {
+        g1_affine_fl,
+        g1_affine_fr,
+        g1_affine_zl,
+        g1_affine_zr,
+        g1_affine_ql,
+        g1_affine_qr,
+        batch_proof: P::G1Affine::zero(),
+        product,
+        fl_at_delta: poly_fl.evaluate(&delta),
+        fr_at_delta: poly_fr.evaluate(&delta),
+        zl_at_delta: poly_zl.evaluate(&delta),
+        zr_at_delta: poly_zr.evaluate(&delta),
+        zl_at_shifted_delta: poly_zl.evaluate(&shifted_delta_l),
+        zr_at_shifted_delta: poly_zr.evaluate(&shifted_delta_r),
+        lv_at_delta: poly_left_values.evaluate(&delta),
+        rv_at_delta: poly_right_values.evaluate(&delta),
+        pl_at_delta: pp.poly_positions_left.evaluate(&delta),
+        pr_at_delta: pp.poly_positions_right.evaluate(&delta),
+        pm_at_delta: pp.poly_position_mappings.evaluate(&delta),
+        pa_at_delta: pp.has_coefficients().then(|| pp.poly_coefficients_a.evaluate(&delta)),
+        pb_at_delta: pp.has_coefficients().then(|| pp.poly_coefficients_b.evaluate(&delta)),
+        lv0_at_delta: pp.has_constants().then(|| witness.poly_left_values[0].evaluate(&delta)),
+        ps_at_delta: pp.has_constants().then(|| pp.poly_constant_positions.evaluate(&delta)),
+        pc_at_delta: pp.has_constants().then(|| pp.poly_constants.evaluate(&delta)),
+    };
+    append_evaluations(&mut transcript, &proof)?;
+
+    // Sample random epsilon to separate the opened polynomials.
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    // Open all polynomials at delta and the accumulators at their shifted points with one
+    // proof. The shifted points coincide when both domains have the same generator.
+    let mut poly_list_at_delta = vec![
+        &poly_fl,
+        &poly_fr,
+        &poly_zl,
+        &poly_zr,
+        &poly_ql,
+        &poly_qr,
+        &poly_left_values,
+        &poly_right_values,
+        &pp.poly_positions_left,
+        &pp.poly_positions_right,
+        &pp.poly_position_mappings,
+    ];
+    if pp.has_coefficients() {
+        poly_list_at_delta.extend([&pp.poly_coefficients_a, &pp.poly_coefficients_b]);
+    }
+    if pp.has_constants() {
+        poly_list_at_delta.extend([
+            &witness.poly_left_values[0],
+            &pp.poly_constant_positions,
+            &pp.poly_constants,
+        ]);
+    }
+    proof.batch_proof = if shifted_delta_l == shifted_delta_r {
+        Kzg::<P::G1>::multi_point_batch_open(
+            &pp.g1_affine_srs,
+            &[&poly_list_at_delta, &[&poly_zl, &poly_zr]],
+            &[delta, shifted_delta_l],
+            epsilon,
+        )
+    } else {
+        Kzg::<P::G1>::multi_point_batch_open(
+            &pp.g1_affine_srs,
+            &[&poly_list_at_delta, &[&poly_zl], &[&poly_zr]],
+            &[delta, shifted_delta_l, shifted_delta_r],
+            epsilon,
+        )
+    };
+
+    Ok(proof)
+}
+
+pub fn verify<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &Statement<P>,
+    proof: &GrandProductProof<P>,
+) -> Result<(), Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    if statement.g1_affine_left_values.is_empty()
+        || statement.g1_affine_left_values.len() != statement.g1_affine_right_values.len()
+    {
+        return Err(Error::WrongNumberOfColumns(statement.g1_affine_right_values.len()));
+    }
+
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    transcript.append_elements(&[
+        (Label::G1F, (proof.g1_affine_fl, proof.g1_affine_fr)),
+        (Label::G1Z, (proof.g1_affine_zl, proof.g1_affine_zr)),
+    ])?;
+    transcript.append_element(Label::FrProduct, &proof.product)?;
+
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+    let eta_squared = eta.square();
+    let eta_cubed = eta_squared * eta;
+
+    transcript.append_elements(&[(Label::G1Ql, proof.g1_affine_ql), (Label::G1Qr, proof.g1_affine_qr)])?;
+
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+    let shifted_delta_l = delta * pp.domain_l.group_gen;
+    let shifted_delta_r = delta * pp.domain_r.group_gen;
+
+    append_evaluations(&mut transcript, proof)?;
+
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let fr_one = P::ScalarField::one();
+    let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+    let fr_sum_of_alpha: P::ScalarField = powers_of_alpha.iter().sum();
+    let has_coefficients = pp.has_coefficients();
+    let [pa_at_delta, pb_at_delta] = opened_or(
+        has_coefficients,
+        [proof.pa_at_delta, proof.pb_at_delta],
+        [fr_one, P::ScalarField::zero()],
+        "coefficient evaluations",
+    )?;
+    let has_constants = pp.has_constants();
+    let [lv0_at_delta, ps_at_delta, pc_at_delta] = opened_or(
+        has_constants,
+        [proof.lv0_at_delta, proof.ps_at_delta, proof.pc_at_delta],
+        [P::ScalarField::zero(); 3],
+        "constant evaluations",
+    )?;
+
+    let fr_zl_at_delta = pp.domain_l.evaluate_vanishing_polynomial(delta);
+    let fr_inv_zl_at_delta = fr_zl_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let (fr_first_l, fr_last_l) = lagrange_first_and_last_at(&pp.domain_l, delta, fr_zl_at_delta)?;
+    let fr_ql_at_delta = proof.fl_at_delta - fr_one
+        - proof.pl_at_delta * (beta + pa_at_delta * proof.lv_at_delta + fr_sum_of_alpha * pb_at_delta
+            + gamma * proof.pm_at_delta - fr_one)
+        + eta * (proof.zl_at_shifted_delta * (fr_one + fr_last_l * (proof.product - fr_one))
+            - proof.zl_at_delta * proof.fl_at_delta)
+        + eta_squared * fr_first_l * (proof.zl_at_delta - fr_one)
+        + eta_cubed * (ps_at_delta * lv0_at_delta - pc_at_delta);
+    let fr_ql_at_delta = fr_ql_at_delta * fr_inv_zl_at_delta;
+
+    let fr_zr_at_delta = pp.domain_r.evaluate_vanishing_polynomial(delta);
+    let fr_inv_zr_at_delta = fr_zr_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let (fr_first_r, fr_last_r) = lagrange_first_and_last_at(&pp.domain_r, delta, fr_zr_at_delta)?;
+    let fr_qr_at_delta = proof.fr_at_delta - fr_one
+        - proof.pr_at_delta * (beta + proof.rv_at_delta + gamma * delta - fr_one)
+        + eta * (proof.zr_at_shifted_delta * (fr_one + fr_last_r * (proof.product - fr_one))
+            - proof.zr_at_delta * proof.fr_at_delta)
+        + eta_squared * fr_first_r * (proof.zr_at_delta - fr_one);
+    let fr_qr_at_delta = fr_qr_at_delta * fr_inv_zr_at_delta;
+
+    // Fold the column commitments as the prover folded the columns.
+    let g1_affine_left_values = P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha)
+        .into_affine();
+    let g1_affine_right_values = P::G1::msm_unchecked(&statement.g1_affine_right_values, &powers_of_alpha)
+        .into_affine();
+
+    // Pairing check of the batch proof at delta and the shifted points.
+    let mut g1_list_at_delta = vec![
+        proof.g1_affine_fl,
+        proof.g1_affine_fr,
+        proof.g1_affine_zl,
+        proof.g1_affine_zr,
+        proof.g1_affine_ql,
+        proof.g1_affine_qr,
+        g1_affine_left_values,
+        g1_affine_right_values,
+        pp.g1_affine_positions_left,
+        pp.g1_affine_positions_right,
+        pp.g1_affine_position_mappings,
+    ];
+    let mut fr_list_at_delta = vec![
+        proof.fl_at_delta,
+        proof.fr_at_delta,
+        proof.zl_at_delta,
+        proof.zr_at_delta,
+        fr_ql_at_delta,
+        fr_qr_at_delta,
+        proof.lv_at_delta,
+        proof.rv_at_delta,
+        proof.pl_at_delta,
+        proof.pr_at_delta,
+        proof.pm_at_delta,
+    ];
+    if has_coefficients {
+        g1_list_at_delta.extend([pp.g1_affine_coefficients_a, pp.g1_affine_coefficients_b]);
+        fr_list_at_delta.extend([pa_at_delta, pb_at_delta]);
+    }
+    if has_constants {
+        g1_list_at_delta.extend([
+            statement.g1_affine_left_values[0],
+            pp.g1_affine_constant_positions,
+            pp.g1_affine_constants,
+        ]);
+        fr_list_at_delta.extend([lv0_at_delta, ps_at_delta, pc_at_delta]);
+    }
+    let is_valid = if shifted_delta_l == shifted_delta_r {
+        multi_point_batch_verify::<P>(
+            pp.g1_affine_srs[0],
+            &pp.g2_affine_srs,
+            &[&g1_list_at_delta, &[proof.g1_affine_zl, proof.g1_affine_zr]],
+            &[&fr_list_at_delta, &[proof.zl_at_shifted_delta, proof.zr_at_shifted_delta]],
+            &[delta, shifted_delta_l],
+            epsilon,
+            proof.batch_proof,
+        )
+    } else {
+        multi_point_batch_verify::<P>(
+            pp.g1_affine_srs[0],
+            &pp.g2_affine_srs,
+            &[&g1_list_at_delta, &[proof.g1_affine_zl], &[proof.g1_affine_zr]],
+            &[&fr_list_at_delta, &[proof.zl_at_shifted_delta], &[proof.zr_at_shifted_delta]],
+            &[delta, shifted_delta_l, shifted_delta_r],
+            epsilon,
+            proof.batch_proof,
+        )
+    };
+    if !is_valid {
+        return Err(Error::PairingCheckFailed("batch opening at delta and the shifted points"));
+    }
+
+    Ok(())
+}
+
+/// Running products of `factors`, starting at one, and the product of all of them.
+fn accumulate<F: Field>(factors: &[F]) -> (Vec<F>, F) {
+    let mut accumulator = Vec::with_capacity(factors.len());
+    let mut product = F::one();
+    for &factor in factors {
+        accumulator.push(product);
+        product *= factor;
+    }
+
+    (accumulator, product)
+}
+
+/// Returns `poly(shift * X)`.
+fn shift_poly<F: FftField>(poly: &DensePolynomial<F>, shift: F) -> DensePolynomial<F> {
+    let mut fr_pow = F::one();
+    let coeffs = poly.coeffs.iter()
+        .map(|&c| {
+            let shifted = c * fr_pow;
+            fr_pow *= shift;
+            shifted
+        })
+        .collect();
+
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
+/// Evaluations over `domain_coset` of the Lagrange polynomials of the first and the last point
+/// of `domain`.
+fn lagrange_first_and_last<F: FftField>(
+    domain: &Radix2EvaluationDomain<F>,
+    domain_coset: &Radix2EvaluationDomain<F>,
+) -> (Vec<F>, Vec<F>) {
+    let size = domain.size();
+    let mut eval_list = vec![F::zero(); size];
+    eval_list[0] = F::one();
+    let first = domain_coset.fft(&domain.ifft(&eval_list));
+    eval_list[0] = F::zero();
+    eval_list[size - 1] = F::one();
+    let last = domain_coset.fft(&domain.ifft(&eval_list));
+
+    (first, last)
+}
+
+/// Evaluates the Lagrange polynomials of the first and the last point of `domain` at `point`,
+/// given the vanishing polynomial of `domain` at `point`, with
+/// `L_i(X) = omega^i * Z_H(X) / (n * (X - omega^i))`.
+fn lagrange_first_and_last_at<F: FftField>(
+    domain: &Radix2EvaluationDomain<F>,
+    point: F,
+    vanishing_at_point: F,
+) -> Result<(F, F), Error> {
+    let fr_last_root = domain.group_gen_inv;
+    let fr_n = domain.size_as_field_element;
+    let inv_first = (fr_n * (point - F::one())).inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let inv_last = (fr_n * (point - fr_last_root)).inverse().ok_or(Error::FailedToInverseFieldElement)?;
+
+    Ok((vanishing_at_point * inv_first, fr_last_root * vanishing_at_point * inv_last))
+}
+
+fn append_evaluations<P: Pairing>(
+    transcript: &mut Transcript<P::ScalarField>,
+    proof: &GrandProductProof<P>,
+) -> Result<(), Error> {
+    let mut evaluations = vec![
+        (Label::FrFlAtDelta, proof.fl_at_delta),
+        (Label::FrFrAtDelta, proof.fr_at_delta),
+        (Label::FrZlAtDelta, proof.zl_at_delta),
+        (Label::FrZrAtDelta, proof.zr_at_delta),
+        (Label::FrZlAtShiftedDelta, proof.zl_at_shifted_delta),
+        (Label::FrZrAtShiftedDelta, proof.zr_at_shifted_delta),
+        (Label::FrLvAtDelta, proof.lv_at_delta),
+        (Label::FrRvAtDelta, proof.rv_at_delta),
+        (Label::FrPlAtDelta, proof.pl_at_delta),
+        (Label::FrPrAtDelta, proof.pr_at_delta),
+        (Label::FrPmAtDelta, proof.pm_at_delta),
+    ];
+    evaluations.extend(proof.pa_at_delta.map(|pa_at_delta| (Label::FrPaAtDelta, pa_at_delta)));
+    evaluations.extend(proof.pb_at_delta.map(|pb_at_delta| (Label::FrPbAtDelta, pb_at_delta)));
+    evaluations.extend(proof.lv0_at_delta.map(|lv0_at_delta| (Label::FrLv0AtDelta, lv0_at_delta)));
+    evaluations.extend(proof.ps_at_delta.map(|ps_at_delta| (Label::FrPsAtDelta, ps_at_delta)));
+    evaluations.extend(proof.pc_at_delta.map(|pc_at_delta| (Label::FrPcAtDelta, pc_at_delta)));
+    transcript.append_elements(&evaluations)
+}
@@ -4,10 +4,12 @@ use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::{FftField, One};
 use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial};
 use ark_std::rand::Rng;
+use ark_std::marker::PhantomData;
+use ark_std::ops::Mul;
+use ark_std::{cfg_into_iter, cfg_iter, cfg_iter_mut, vec, vec::Vec};
 use ark_std::{UniformRand, Zero};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
-use std::marker::PhantomData;
-use std::ops::Mul;
 
 /// Minimal KZG functionalities needed for the lookup argument.
 ///
@@ -62,19 +64,7 @@ impl<C: CurveGroup> Kzg<C> {
         let num_polys = poly_list.len();
         let powers_of_sep = powers_of_scalars::<C::ScalarField>(fr_separation, num_polys);
 
-        let mut batched = poly_list[0].clone();
-        let rest_batched: DensePolynomial<C::ScalarField> = poly_list[1..]
-            .par_iter()
-            .zip(powers_of_sep.par_iter().skip(1))
-            .map(|(&p_i, &fr_sep_pow_i)| {
-                p_i * fr_sep_pow_i // Multiply each polynomial by its
-                // corresponding power of `fr_separation`
-            })
-            .reduce(
-                || DensePolynomial::from_coefficients_slice(&[C::ScalarField::zero()]),
-                |a, b| a + b,
-            );
-        batched += &rest_batched;
+        let batched = linear_combination(poly_list, &powers_of_sep);
 
         let q = &batched
             / &DensePolynomial::from_coefficients_slice(&[-fr_opening, C::ScalarField::one()]);
@@ -89,6 +79,168 @@ impl<C: CurveGroup> Kzg<C> {
 
         Self::commit(affine_srs, &q).into()
     }
+
+    /// Opens several groups of polynomials, each group at its own point, with a single group
+    /// element.
+    ///
+    /// This is the first multi-point scheme of Boneh-Drake-Fisch-Gabizon
+    /// ([eprint 2020/081](https://eprint.iacr.org/2020/081)) specialised to singleton opening
+    /// sets. The powers of `fr_separation` run continuously across the groups, and the proof is
+    /// the commitment to `h(X) = sum_i (f_i(X) - f_i(z_i)) / (X - z_i)`, where `f_i` is the
+    /// separated sum of the polynomials in group `i` and `z_i` its opening point.
+    pub fn multi_point_batch_open(
+        affine_srs: &[C::Affine],
+        poly_lists: &[&[&DensePolynomial<C::ScalarField>]],
+        fr_openings: &[C::ScalarField],
+        fr_separation: C::ScalarField,
+    ) -> C::Affine {
+        let num_polys = poly_lists.iter().map(|list| list.len()).sum();
+        let powers_of_sep = powers_of_scalars::<C::ScalarField>(fr_separation, num_polys);
+
+        let mut h = DensePolynomial::from_coefficients_slice(&[C::ScalarField::zero()]);
+        let mut offset = 0;
+        for (poly_list, &fr_opening) in poly_lists.iter().zip(fr_openings.iter()) {
+            let batched = linear_combination(poly_list, &powers_of_sep[offset..]);
+            offset += poly_list.len();
+
+            // The remainder of the division is the evaluation at the opening point.
+            h += &(&batched
+                / &DensePolynomial::from_coefficients_slice(&[-fr_opening, C::ScalarField::one()]));
+        }
+
+        if affine_srs.len() - 1 < h.degree() {
+            panic!(
+                "Multi-point batch open g1: SRS size to small! Can't commit to polynomial of degree {} with srs of size {}",
+                h.degree(),
+                affine_srs.len()
+            );
+        }
+
+        Self::commit(affine_srs, &h).into()
+    }
+
+    /// Opens one polynomial at a set of points with a single group element, the commitment to
+    /// `(f(X) - I(X)) / Z_S(X)`, where `I` interpolates the evaluations over the points and
+    /// `Z_S` is their vanishing polynomial.
+    pub fn open_at_points(
+        affine_srs: &[C::Affine],
+        poly: &DensePolynomial<C::ScalarField>,
+        fr_points: &[C::ScalarField],
+    ) -> (Vec<C::ScalarField>, C::Affine) {
+        let evaluations: Vec<C::ScalarField> = fr_points.iter().map(|x| poly.evaluate(x)).collect();
+        let q = &(poly - &interpolate(fr_points, &evaluations)) / &vanishing_poly(fr_points);
+
+        (evaluations, Self::commit(affine_srs, &q).into())
+    }
+}
+
+/// Verifies a proof produced by [`Kzg::open_at_points`] by checking
+/// `e(commitment - [I(tau)], [1]) = e(proof, [Z_S(tau)])`. `g2_affine_srs` must hold at least
+/// `fr_points.len() + 1` powers of tau.
+pub fn verify_at_points<P: Pairing>(
+    g1_affine_srs: &[P::G1Affine],
+    g2_affine_srs: &[P::G2Affine],
+    commitment: P::G1Affine,
+    fr_points: &[P::ScalarField],
+    evaluations: &[P::ScalarField],
+    proof: P::G1Affine,
+) -> bool {
+    let interpolation = interpolate(fr_points, evaluations);
+    let g1_interpolation: P::G1 = Kzg::<P::G1>::commit(g1_affine_srs, &interpolation);
+
+    P::multi_pairing(
+        P::G1::normalize_batch(&[commitment.into_group() - g1_interpolation, -proof.into_group()]),
+        P::G2::normalize_batch(&[
+            g2_affine_srs[0].into_group(),
+            g2_commit::<P>(g2_affine_srs, fr_points, None),
+        ]),
+    )
+    .is_zero()
+}
+
+/// Verifies a proof produced by [`Kzg::multi_point_batch_open`].
+///
+/// With `Z_T(X) = prod_i (X - z_i)`, it checks
+/// `e(proof, [Z_T(tau)]) = prod_i e(F_i - [v_i], [Z_T(tau) / (tau - z_i)])`,
+/// where `F_i` and `v_i` are the separated sums of the commitments and evaluations of group `i`.
+/// The opening points must be pairwise distinct and `g2_affine_srs` must hold at least
+/// `fr_openings.len() + 1` powers of tau.
+pub fn multi_point_batch_verify<P: Pairing>(
+    g1_affine_generator: P::G1Affine,
+    g2_affine_srs: &[P::G2Affine],
+    commitment_lists: &[&[P::G1Affine]],
+    evaluation_lists: &[&[P::ScalarField]],
+    fr_openings: &[P::ScalarField],
+    fr_separation: P::ScalarField,
+    proof: P::G1Affine,
+) -> bool {
+    let fr_one = P::ScalarField::one();
+    let mut g1_list = vec![-proof.into_group()];
+    let mut g2_list = vec![g2_commit::<P>(g2_affine_srs, fr_openings, None)];
+
+    let mut fr_pow_sep = fr_one;
+    for (i, (commitments, evaluations)) in commitment_lists
+        .iter()
+        .zip(evaluation_lists.iter())
+        .enumerate()
+    {
+        let mut fr_sep_list = Vec::with_capacity(commitments.len());
+        let mut fr_batched = P::ScalarField::zero();
+        for &fr_eval in evaluations.iter() {
+            fr_sep_list.push(fr_pow_sep);
+            fr_batched += fr_eval * fr_pow_sep;
+            fr_pow_sep *= fr_separation;
+        }
+        let g1_batched: P::G1 = VariableBaseMSM::msm_unchecked(commitments, &fr_sep_list);
+        g1_list.push(g1_batched - g1_affine_generator.mul(fr_batched));
+        g2_list.push(g2_commit::<P>(g2_affine_srs, fr_openings, Some(i)));
+    }
+
+    P::multi_pairing(
+        P::G1::normalize_batch(&g1_list),
+        P::G2::normalize_batch(&g2_list),
+    )
+    .is_zero()
+}
+
+/// Commits in G2 to the vanishing polynomial of `fr_points`, leaving out the point at
+/// `skip` if given.
+pub(crate) fn g2_commit<P: Pairing>(
+    g2_affine_srs: &[P::G2Affine],
+    fr_points: &[P::ScalarField],
+    skip: Option<usize>,
+) -> P::G2 {
+    let fr_points: Vec<P::ScalarField> = fr_points
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != skip)
+        .map(|(_, &fr_point)| fr_point)
+        .collect();
+    let vanishing = vanishing_poly(&fr_points);
+
+    VariableBaseMSM::msm_unchecked(&g2_affine_srs[..vanishing.coeffs.len()], &vanishing.coeffs)
+}
+
+/// Computes `prod_i (X - fr_points[i])`.
+fn vanishing_poly<F: FftField>(fr_points: &[F]) -> DensePolynomial<F> {
+    fr_points.iter().fold(
+        DensePolynomial::from_coefficients_slice(&[F::one()]),
+        |vanishing, &fr_point| &vanishing * &DensePolynomial::from_coefficients_slice(&[-fr_point, F::one()]),
+    )
+}
+
+/// Lagrange interpolation of `evaluations` over the pairwise distinct `fr_points`.
+pub(crate) fn interpolate<F: FftField>(fr_points: &[F], evaluations: &[F]) -> DensePolynomial<F> {
+    let vanishing = vanishing_poly(fr_points);
+    let mut coeffs = vec![F::zero(); fr_points.len()];
+    for (&fr_point, &fr_eval) in fr_points.iter().zip(evaluations.iter()) {
+        // `Z_S(X) / (X - x_i)`, scaled so that it is one at `x_i`.
+        let basis = &vanishing / &DensePolynomial::from_coefficients_slice(&[-fr_point, F::one()]);
+        let fr_scale = fr_eval * basis.evaluate(&fr_point).inverse().unwrap_or_default();
+        coeffs.iter_mut().zip(basis.coeffs.iter()).for_each(|(c, &b)| *c += b * fr_scale);
+    }
+
+    DensePolynomial::from_coefficients_vec(coeffs)
 }
 
 /// Create srs from rng
@@ -121,17 +273,32 @@ pub fn unsafe_setup_from_tau<P: Pairing, R: Rng + ?Sized>(
     (g1_srs, g2_srs)
 }
 
+/// Computes `sum_i scalars[i] * poly_list[i]`.
+pub(crate) fn linear_combination<F: FftField>(
+    poly_list: &[&DensePolynomial<F>],
+    scalars: &[F],
+) -> DensePolynomial<F> {
+    let len = poly_list.iter().map(|p| p.coeffs.len()).max().unwrap_or(0);
+    let mut coeffs = vec![F::zero(); len];
+    poly_list.iter().zip(scalars.iter()).for_each(|(p_i, &fr_i)| {
+        cfg_iter_mut!(coeffs[..p_i.coeffs.len()])
+            .zip(cfg_iter!(p_i.coeffs))
+            .for_each(|(c, &p)| *c += p * fr_i);
+    });
+
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
 const CHUNK_SIZE: usize = 1024;
 
-fn powers_of_scalars<F: FftField>(s: F, size: usize) -> Vec<F> {
-    let num_chunks = (size + CHUNK_SIZE - 1) / CHUNK_SIZE;
+pub(crate) fn powers_of_scalars<F: FftField>(s: F, size: usize) -> Vec<F> {
+    let num_chunks = size.div_ceil(CHUNK_SIZE);
 
-    let mut result: Vec<F> = (0..num_chunks)
-        .into_par_iter()
+    let mut result: Vec<F> = cfg_into_iter!(0..num_chunks)
         .flat_map(|chunk_index| {
             let start_power = chunk_index * CHUNK_SIZE;
             let mut chunk = Vec::with_capacity(CHUNK_SIZE.min(size - start_power));
-            let mut power = s.pow(&[start_power as u64]);
+            let mut power = s.pow([start_power as u64]);
 
             for _ in 0..CHUNK_SIZE.min(size - start_power) {
                 chunk.push(power);
@@ -149,9 +316,128 @@ fn powers_of_scalars<F: FftField>(s: F, size: usize) -> Vec<F> {
 fn srs<C: CurveGroup>(powers_of_tau: &[C::ScalarField], max_power: usize) -> Vec<C::Affine> {
     let generator = C::Affine::generator();
 
-    powers_of_tau
-        .par_iter()
+    cfg_iter!(powers_of_tau)
         .take(max_power + 1)
         .map(|tp| generator.mul(tp).into())
         .collect()
 }
+
+#[cfg(all(test, feature = "bn254"))]
+mod tests {
+    use super::{multi_point_batch_verify, unsafe_setup_from_rng, Kzg};
+    use ark_bn254::{Bn254, Fr, G1Affine, G1Projective, G2Affine};
+    use ark_ec::CurveGroup;
+    use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial};
+    use ark_std::{test_rng, vec::Vec, UniformRand};
+
+    struct Opening {
+        g1_affine_srs: Vec<G1Affine>,
+        g2_affine_srs: Vec<G2Affine>,
+        polys: [DensePolynomial<Fr>; 3],
+        commitments: [G1Affine; 3],
+        points: [Fr; 2],
+        epsilon: Fr,
+        proof: G1Affine,
+    }
+
+    /// Opens `f_0, f_1` at the first point and `f_2` at the second one.
+    fn open() -> Opening {
+        let rng = &mut test_rng();
+        let (g1_affine_srs, g2_affine_srs) = unsafe_setup_from_rng::<Bn254, _>(8, rng);
+        let polys = [(); 3].map(|_| DensePolynomial::<Fr>::rand(7, rng));
+        let commitments = [0, 1, 2]
+            .map(|i| Kzg::<G1Projective>::commit(&g1_affine_srs, &polys[i]).into_affine());
+        let points = [Fr::rand(rng), Fr::rand(rng)];
+        let epsilon = Fr::rand(rng);
+        let proof = Kzg::<G1Projective>::multi_point_batch_open(
+            &g1_affine_srs,
+            &[&[&polys[0], &polys[1]], &[&polys[2]]],
+            &points,
+            epsilon,
+        );
+
+        Opening { g1_affine_srs, g2_affine_srs, polys, commitments, points, epsilon, proof }
+    }
+
+    impl Opening {
+        fn verify(
+            &self,
+            commitment_lists: &[&[G1Affine]],
+            evaluation_lists: &[&[Fr]],
+            points: &[Fr],
+        ) -> bool {
+            multi_point_batch_verify::<Bn254>(
+                self.g1_affine_srs[0],
+                &self.g2_affine_srs,
+                commitment_lists,
+                evaluation_lists,
+                points,
+                self.epsilon,
+                self.proof,
+            )
+        }
+
+        fn eval(&self, poly: usize, point: usize) -> Fr {
+            self.polys[poly].evaluate(&self.points[point])
+        }
+    }
+
+    #[test]
+    fn multi_point_batch_open_verifies() {
+        let o = open();
+        let c = o.commitments;
+
+        assert!(o.verify(
+            &[&[c[0], c[1]], &[c[2]]],
+            &[&[o.eval(0, 0), o.eval(1, 0)], &[o.eval(2, 1)]],
+            &o.points,
+        ));
+    }
+
+    #[test]
+    fn multi_point_batch_rejects_tampered_evaluation_at_one_point() {
+        let o = open();
+        let c = o.commitments;
+        let fr_one = Fr::from(1u64);
+
+        // Every evaluation at the first point is correct, only the second point is off.
+        assert!(!o.verify(
+            &[&[c[0], c[1]], &[c[2]]],
+            &[&[o.eval(0, 0), o.eval(1, 0)], &[o.eval(2, 1) + fr_one]],
+            &o.points,
+        ));
+        // And the other way around.
+        assert!(!o.verify(
+            &[&[c[0], c[1]], &[c[2]]],
+            &[&[o.eval(0, 0), o.eval(1, 0) + fr_one], &[o.eval(2, 1)]],
+            &o.points,
+        ));
+    }
+
+    #[test]
+    fn multi_point_batch_rejects_polynomial_swapped_between_points() {
+        let o = open();
+        let c = o.commitments;
+
+        // `f_1` and `f_2` trade places, each with its true evaluation at its new point.
+        assert!(!o.verify(
+            &[&[c[0], c[2]], &[c[1]]],
+            &[&[o.eval(0, 0), o.eval(2, 0)], &[o.eval(1, 1)]],
+            &o.points,
+        ));
+    }
+
+    #[test]
+    fn multi_point_batch_rejects_wrong_point() {
+        let o = open();
+        let c = o.commitments;
+        let fr_wrong = o.points[1] + Fr::from(1u64);
+
+        // The evaluations are honest for the wrong point, but the proof was made for another one.
+        assert!(!o.verify(
+            &[&[c[0], c[1]], &[c[2]]],
+            &[&[o.eval(0, 0), o.eval(1, 0)], &[o.polys[2].evaluate(&fr_wrong)]],
+            &[o.points[0], fr_wrong],
+        ));
+    }
+}
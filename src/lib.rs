@@ -1,3 +1,5 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
 use ark_serialize::Compress;
 
 pub mod kzg;
@@ -8,22 +10,160 @@ pub mod prover;
 pub mod verifier;
 pub mod witness;
 pub mod statement;
+pub mod link;
+pub mod disclosure;
+pub mod private_mapping;
+pub mod lookup;
+pub mod shuffle;
+pub mod gated;
+pub mod sum;
+pub mod transition;
+pub mod pedersen;
+pub mod aggregate;
+pub mod batch;
+pub mod folding;
+pub mod grand_product;
+mod sumcheck;
 mod transcript;
 
+#[cfg(feature = "bn254")]
+pub use ark_bn254;
+#[cfg(feature = "bls12_377")]
+pub use ark_bls12_377;
+#[cfg(feature = "bls12_381")]
+pub use ark_bls12_381;
+
 const COMPRESS_MOD: Compress = Compress::No;
 
 #[cfg(test)]
+#[cfg_attr(
+    not(any(feature = "bn254", feature = "bls12_381", feature = "bls12_377")),
+    allow(dead_code, unused_imports)
+)]
 mod tests {
     use crate::prover::prove;
     use crate::public_parameters::PublicParameters;
     use crate::verifier::verify;
     use crate::witness::Witness;
-    use ark_bn254::{Bn254, Fr};
+    use ark_ec::pairing::Pairing;
     use ark_std::{test_rng, UniformRand};
-    use std::collections::BTreeMap;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::collections::BTreeMap;
+    use ark_std::{vec, vec::Vec};
+
+    /// Instantiates the generic test scenarios below for a curve behind a cargo feature.
+    macro_rules! curve_tests {
+        ($name:ident, $feature:literal, $curve:ty) => {
+            #[cfg(feature = $feature)]
+            mod $name {
+                #[test]
+                fn end_to_end() {
+                    super::end_to_end::<$curve>();
+                }
+
+                #[test]
+                fn multi_column() {
+                    super::multi_column::<$curve>();
+                }
+
+                #[test]
+                fn affine_relation() {
+                    super::affine_relation::<$curve>();
+                }
+
+                #[test]
+                fn left_constants() {
+                    super::left_constants::<$curve>();
+                }
+
+                #[test]
+                fn selective_disclosure() {
+                    super::selective_disclosure::<$curve>();
+                }
+
+                #[test]
+                fn many_to_one() {
+                    super::many_to_one::<$curve>();
+                }
+
+                #[test]
+                fn scatter() {
+                    super::scatter::<$curve>();
+                }
+
+                #[test]
+                fn private_mapping() {
+                    super::private_mapping::<$curve>();
+                }
+
+                #[test]
+                fn unindexed_lookup() {
+                    super::unindexed_lookup::<$curve>();
+                }
+
+                #[test]
+                fn shuffle() {
+                    super::shuffle::<$curve>();
+                }
+
+                #[test]
+                fn chain() {
+                    super::chain::<$curve>();
+                }
+
+                #[test]
+                fn copy_constraints() {
+                    super::copy_constraints::<$curve>();
+                }
+
+                #[test]
+                fn selector_gated() {
+                    super::selector_gated::<$curve>();
+                }
+
+                #[test]
+                fn sum_equality() {
+                    super::sum_equality::<$curve>();
+                }
+
+                #[test]
+                fn state_transition() {
+                    super::state_transition::<$curve>();
+                }
+
+                #[test]
+                fn pedersen_link() {
+                    super::pedersen_link::<$curve>();
+                }
 
-    #[test]
-    fn end_to_end() {
+                #[test]
+                fn aggregate() {
+                    super::aggregate::<$curve>();
+                }
+
+                #[test]
+                fn batch() {
+                    super::batch::<$curve>();
+                }
+
+                #[test]
+                fn folding() {
+                    super::folding::<$curve>();
+                }
+
+                #[test]
+                fn grand_product() {
+                    super::grand_product::<$curve>();
+                }
+            }
+        };
+    }
+
+    curve_tests!(bn254, "bn254", ark_bn254::Bn254);
+    curve_tests!(bls12_381, "bls12_381", ark_bls12_381::Bls12_381);
+    curve_tests!(bls12_377, "bls12_377", ark_bls12_377::Bls12_377);
+
+    fn end_to_end<P: Pairing>() {
         let rng = &mut test_rng();
         let mut mappings = BTreeMap::new();
         mappings.insert(0, 0);
@@ -31,15 +171,15 @@ mod tests {
         mappings.insert(4, 8);
         mappings.insert(6, 12);
 
-        let pp = PublicParameters::<Bn254>::builder()
+        let pp = PublicParameters::<P>::builder()
             .size_left_values(8)
             .size_right_values(16)
             .position_mappings(&mappings)
             .build(rng).unwrap();
 
         // Correct verification.
-        let left_witness_values = (0..8).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
-        let mut right_witness_values = (0..16).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let left_witness_values = (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let mut right_witness_values = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
         right_witness_values[0] = left_witness_values[0];
         right_witness_values[4] = left_witness_values[2];
         right_witness_values[8] = left_witness_values[4];
@@ -48,18 +188,1038 @@ mod tests {
         let witness = Witness::new(&pp, &left_witness_values, &right_witness_values).unwrap();
         let statement = witness.generate_statement(&pp).unwrap();
 
-        let proof = prove::<Bn254>(&pp, &witness, &statement).unwrap();
-        verify::<Bn254>(&pp, &statement, &proof).unwrap();
+        let proof = prove::<P>(&pp, &witness, &statement).unwrap();
+        verify::<P>(&pp, &statement, &proof).unwrap();
+        // Without coefficients or constants, the proof does not open their polynomials.
+        assert!(proof.pa_at_delta.is_none() && proof.pb_at_delta.is_none());
+        assert!(proof.lv0_at_delta.is_none() && proof.ps_at_delta.is_none() && proof.pc_at_delta.is_none());
+
+        // Verification after a serialization round trip.
+        let mut buf = Vec::new();
+        pp.serialize_compressed(&mut buf).unwrap();
+        statement.serialize_compressed(&mut buf).unwrap();
+        proof.serialize_compressed(&mut buf).unwrap();
+        let mut reader = &buf[..];
+        let pp = PublicParameters::<P>::deserialize_compressed(&mut reader).unwrap();
+        let statement = crate::statement::Statement::<P>::deserialize_compressed(&mut reader).unwrap();
+        let proof = crate::prover::Proof::<P>::deserialize_compressed(&mut reader).unwrap();
+        verify::<P>(&pp, &statement, &proof).unwrap();
+
+        // The hash is recomputed from the deserialized parameters, so parameters with another
+        // mapping commitment no longer match the proof.
+        let mut pp_other = PublicParameters::<P>::deserialize_compressed(&buf[..]).unwrap();
+        assert_eq!(pp_other.hash_representation, pp.hash_representation);
+        pp_other.g1_affine_position_mappings = pp.g1_affine_positions_left;
+        let mut buf_other = Vec::new();
+        pp_other.serialize_compressed(&mut buf_other).unwrap();
+        let pp_other = PublicParameters::<P>::deserialize_compressed(&buf_other[..]).unwrap();
+        assert_ne!(pp_other.hash_representation, pp.hash_representation);
+        assert!(verify::<P>(&pp_other, &statement, &proof).is_err());
 
         // Wrong common witness value.
         let mut left_witness_values = left_witness_values;
-        left_witness_values[4] = Fr::from(42u64);
-        right_witness_values[8] = Fr::from(12u64);
+        left_witness_values[4] = P::ScalarField::from(42u64);
+        right_witness_values[8] = P::ScalarField::from(12u64);
 
         let witness = Witness::new(&pp, &left_witness_values, &right_witness_values).unwrap();
         let statement = witness.generate_statement(&pp).unwrap();
 
-        let proof = prove::<Bn254>(&pp, &witness, &statement).unwrap();
-        assert!(verify::<Bn254>(&pp, &statement, &proof).is_err());
+        let proof = prove::<P>(&pp, &witness, &statement).unwrap();
+        assert!(verify::<P>(&pp, &statement, &proof).is_err());
+    }
+
+    fn multi_column<P: Pairing>() {
+        let rng = &mut test_rng();
+        let mut mappings = BTreeMap::new();
+        mappings.insert(1, 3);
+        mappings.insert(2, 9);
+        mappings.insert(7, 0);
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .position_mappings(&mappings)
+            .build(rng).unwrap();
+
+        // Three columns, e.g. account id, balance and nonce.
+        let left_columns = (0..3)
+            .map(|_| (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut right_columns = (0..3)
+            .map(|_| (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        for (left_column, right_column) in left_columns.iter().zip(right_columns.iter_mut()) {
+            mappings.iter().for_each(|(&i, &j)| right_column[j] = left_column[i]);
+        }
+        let witness = Witness::new_multi_column(
+            &pp,
+            &left_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            &right_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        ).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let proof = prove::<P>(&pp, &witness, &statement).unwrap();
+        verify::<P>(&pp, &statement, &proof).unwrap();
+
+        // One component of one tuple differs.
+        right_columns[2][9] += P::ScalarField::from(1u64);
+        let witness = Witness::new_multi_column(
+            &pp,
+            &left_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            &right_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        ).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let proof = prove::<P>(&pp, &witness, &statement).unwrap();
+        assert!(verify::<P>(&pp, &statement, &proof).is_err());
+
+        // Left and right must have the same number of columns.
+        assert!(Witness::new_multi_column(&pp, &[&left_columns[0]], &[&right_columns[0], &right_columns[1]]).is_err());
+    }
+
+    fn affine_relation<P: Pairing>() {
+        use crate::error::Error;
+
+        let rng = &mut test_rng();
+        let mut mappings = BTreeMap::new();
+        mappings.insert(0, 2);
+        mappings.insert(3, 5);
+        mappings.insert(6, 11);
+        // An amount converted at a public rate plus a fee, and a fee-only adjustment. Position
+        // 0 keeps the default equality.
+        let rate = P::ScalarField::from(3u64);
+        let fee = P::ScalarField::from(7u64);
+        let mut coefficients = BTreeMap::new();
+        coefficients.insert(3, (rate, fee));
+        coefficients.insert(6, (P::ScalarField::from(1u64), fee));
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .position_mappings(&mappings)
+            .position_coefficients(&coefficients)
+            .build(rng).unwrap();
+
+        let left_values = (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let mut right_values = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        right_values[2] = left_values[0];
+        right_values[5] = rate * left_values[3] + fee;
+        right_values[11] = left_values[6] + fee;
+
+        let witness = Witness::new(&pp, &left_values, &right_values).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let mut proof = prove::<P>(&pp, &witness, &statement).unwrap();
+        verify::<P>(&pp, &statement, &proof).unwrap();
+
+        // The coefficient evaluations cannot be left out.
+        proof.pa_at_delta = None;
+        proof.pb_at_delta = None;
+        assert!(matches!(
+            verify::<P>(&pp, &statement, &proof),
+            Err(Error::MalformedProof(_))
+        ));
+
+        // Plain equality no longer satisfies a position with coefficients.
+        right_values[5] = left_values[3];
+        let witness = Witness::new(&pp, &left_values, &right_values).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let proof = prove::<P>(&pp, &witness, &statement).unwrap();
+        assert!(verify::<P>(&pp, &statement, &proof).is_err());
+
+        // Coefficients must belong to mapped positions.
+        coefficients.insert(1, (rate, fee));
+        assert!(matches!(
+            PublicParameters::<P>::builder()
+                .size_left_values(8)
+                .size_right_values(16)
+                .position_mappings(&mappings)
+                .position_coefficients(&coefficients)
+                .build(rng),
+            Err(Error::CoefficientsForUnmappedPosition(1))
+        ));
+    }
+
+    fn left_constants<P: Pairing>() {
+        use crate::error::Error;
+
+        let rng = &mut test_rng();
+        let mut mappings = BTreeMap::new();
+        mappings.insert(0, 0);
+        mappings.insert(1, 3);
+        // A protocol version at a mapped position and a zero padding slot that is not mapped.
+        let version = P::ScalarField::from(2u64);
+        let mut constants = BTreeMap::new();
+        constants.insert(1, version);
+        constants.insert(7, P::ScalarField::from(0u64));
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .position_mappings(&mappings)
+            .left_constants(&constants)
+            .build(rng).unwrap();
+
+        let mut left_values = (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        left_values[1] = version;
+        left_values[7] = P::ScalarField::from(0u64);
+        let mut right_values = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        right_values[0] = left_values[0];
+        right_values[3] = left_values[1];
+
+        let witness = Witness::new(&pp, &left_values, &right_values).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let mut proof = prove::<P>(&pp, &witness, &statement).unwrap();
+        verify::<P>(&pp, &statement, &proof).unwrap();
+
+        // The constant evaluations cannot be left out.
+        proof.lv0_at_delta = None;
+        proof.ps_at_delta = None;
+        proof.pc_at_delta = None;
+        assert!(matches!(
+            verify::<P>(&pp, &statement, &proof),
+            Err(Error::MalformedProof(_))
+        ));
+
+        // The padding slot is not zero.
+        left_values[7] = P::ScalarField::from(1u64);
+        let witness = Witness::new(&pp, &left_values, &right_values).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let proof = prove::<P>(&pp, &witness, &statement).unwrap();
+        assert!(verify::<P>(&pp, &statement, &proof).is_err());
+
+        // Pinned positions must lie in the left vector.
+        constants.insert(8, version);
+        assert!(matches!(
+            PublicParameters::<P>::builder()
+                .size_left_values(8)
+                .size_right_values(16)
+                .position_mappings(&mappings)
+                .left_constants(&constants)
+                .build(rng),
+            Err(Error::ConstantPositionOutOfRange(8))
+        ));
+    }
+
+    fn selective_disclosure<P: Pairing>() {
+        use crate::disclosure::{open_positions, verify_positions, DisclosureProof};
+
+        let rng = &mut test_rng();
+        let mut mappings = BTreeMap::new();
+        mappings.insert(0, 0);
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .position_mappings(&mappings)
+            .build(rng).unwrap();
+
+        let left_columns = (0..2)
+            .map(|_| (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let right_columns = (0..2)
+            .map(|_| (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let witness = Witness::new_multi_column(
+            &pp,
+            &left_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            &right_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        ).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+
+        let proof = open_positions(&pp, &witness, &statement, &[6, 1, 3]).unwrap();
+        assert_eq!(proof.indices, vec![1, 3, 6]);
+        assert_eq!(proof.values[1][2], left_columns[1][6]);
+        verify_positions(&pp, &statement, &proof).unwrap();
+
+        // Verification after a serialization round trip.
+        let mut buf = Vec::new();
+        proof.serialize_compressed(&mut buf).unwrap();
+        let mut proof = DisclosureProof::<P>::deserialize_compressed(&buf[..]).unwrap();
+        verify_positions(&pp, &statement, &proof).unwrap();
+
+        // A disclosed value is changed.
+        proof.values[0][1] += P::ScalarField::from(1u64);
+        assert!(verify_positions(&pp, &statement, &proof).is_err());
+
+        assert!(open_positions(&pp, &witness, &statement, &[]).is_err());
+        assert!(open_positions(&pp, &witness, &statement, &[8]).is_err());
+    }
+
+    fn many_to_one<P: Pairing>() {
+        use crate::link::{self, LinkPublicParameters, LinkStatement, LinkProof, LinkWitness};
+        use crate::error::Error;
+
+        let rng = &mut test_rng();
+        // Three segments of size 8 mapped into one shared vector of size 16. Position 5 of
+        // the shared vector is referenced by two segments.
+        let segment_mappings: Vec<BTreeMap<usize, usize>> = [
+            vec![(0, 1), (3, 5)],
+            vec![(2, 5), (7, 9)],
+            vec![(1, 15), (4, 0), (5, 2)],
+        ].into_iter().map(|pairs| pairs.into_iter().collect()).collect();
+
+        let pp = segment_mappings.iter().enumerate()
+            .fold(LinkPublicParameters::<P>::builder().vector_sizes(&[8, 8, 8, 16]), |builder, (k, mappings)| {
+                builder.edge(k, 3, mappings)
+            })
+            .build(rng).unwrap();
+
+        let mut segments = (0..3)
+            .map(|_| (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let shared_value = P::ScalarField::rand(rng);
+        segments[0][3] = shared_value;
+        segments[1][2] = shared_value;
+        let mut shared = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        for (segment, mappings) in segments.iter().zip(segment_mappings.iter()) {
+            mappings.iter().for_each(|(&i, &j)| shared[j] = segment[i]);
+        }
+
+        let vectors = |segments: &[Vec<P::ScalarField>], shared: &[P::ScalarField]| {
+            let mut vectors = segments.to_vec();
+            vectors.push(shared.to_vec());
+            vectors
+        };
+        let all = vectors(&segments, &shared);
+        let witness = LinkWitness::new(&pp, &all.iter().map(Vec::as_slice).collect::<Vec<_>>()).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let proof = link::prove::<P>(&pp, &witness, &statement).unwrap();
+        link::verify::<P>(&pp, &statement, &proof).unwrap();
+
+        // Verification after a serialization round trip.
+        let mut buf = Vec::new();
+        pp.serialize_compressed(&mut buf).unwrap();
+        statement.serialize_compressed(&mut buf).unwrap();
+        proof.serialize_compressed(&mut buf).unwrap();
+        let mut reader = &buf[..];
+        let pp = LinkPublicParameters::<P>::deserialize_compressed(&mut reader).unwrap();
+        let statement = LinkStatement::<P>::deserialize_compressed(&mut reader).unwrap();
+        let proof = LinkProof::<P>::deserialize_compressed(&mut reader).unwrap();
+        link::verify::<P>(&pp, &statement, &proof).unwrap();
+
+        // One segment disagrees with the shared vector.
+        segments[1][7] += P::ScalarField::from(1u64);
+        let all = vectors(&segments, &shared);
+        let witness = LinkWitness::new(&pp, &all.iter().map(Vec::as_slice).collect::<Vec<_>>()).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let proof = link::prove::<P>(&pp, &witness, &statement).unwrap();
+        assert!(link::verify::<P>(&pp, &statement, &proof).is_err());
+
+        // Every vector must take part in an edge.
+        assert!(matches!(
+            LinkPublicParameters::<P>::builder()
+                .vector_sizes(&[8, 8, 16])
+                .edge(0, 2, &segment_mappings[0])
+                .build(rng),
+            Err(Error::UnlinkedVector(1))
+        ));
+    }
+
+    fn scatter<P: Pairing>() {
+        use crate::link::{self, LinkPublicParameters, LinkWitness};
+
+        let rng = &mut test_rng();
+        // One left vector of size 16 whose values live in three right vectors of sizes 4, 8
+        // and 32, given as `left_index -> (right_vector_id, right_index)`.
+        let mappings: BTreeMap<usize, (usize, usize)> = [
+            (0, (1, 3)),
+            (5, (2, 0)),
+            (6, (2, 7)),
+            (9, (3, 31)),
+            (12, (3, 4)),
+            (15, (1, 0)),
+        ].into_iter().collect();
+
+        let pp = LinkPublicParameters::<P>::builder()
+            .vector_sizes(&[16, 4, 8, 32])
+            .scatter(0, &mappings)
+            .build(rng).unwrap();
+        assert_eq!(pp.edges.len(), 3);
+
+        let left = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let mut rights = [4, 8, 32].into_iter()
+            .map(|size| (0..size).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        mappings.iter().for_each(|(&i, &(v, j))| rights[v - 1][j] = left[i]);
+
+        let prove_and_verify = |rights: &[Vec<P::ScalarField>]| {
+            let mut vectors = vec![left.as_slice()];
+            vectors.extend(rights.iter().map(Vec::as_slice));
+            let witness = LinkWitness::new(&pp, &vectors).unwrap();
+            let statement = witness.generate_statement(&pp).unwrap();
+            let proof = link::prove::<P>(&pp, &witness, &statement).unwrap();
+            link::verify::<P>(&pp, &statement, &proof)
+        };
+        prove_and_verify(&rights).unwrap();
+
+        // A value in one of the right vectors differs.
+        rights[2][4] += P::ScalarField::from(1u64);
+        assert!(prove_and_verify(&rights).is_err());
+    }
+
+    fn private_mapping<P: Pairing>() {
+        use crate::error::Error;
+        use crate::private_mapping::{self, PrivateMapping, PrivateParameters};
+
+        let rng = &mut test_rng();
+        let mappings: BTreeMap<usize, usize> = [(1, 5), (2, 0), (6, 9)].into_iter().collect();
+
+        let pp = PrivateParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .max_proofs(2)
+            .build(rng).unwrap();
+        let mapping = PrivateMapping::new(&pp, &mappings, rng).unwrap();
+        let commitment = mapping.commitment().clone();
+        let mapping_proof = mapping.prove_well_formed(&pp, rng).unwrap();
+        private_mapping::verify_mapping(&pp, &commitment, &mapping_proof).unwrap();
+
+        // The well-formedness proof of one mapping does not carry over to another.
+        let other_mapping = PrivateMapping::new(&pp, &mappings, rng).unwrap();
+        assert!(private_mapping::verify_mapping(&pp, other_mapping.commitment(), &mapping_proof).is_err());
+
+        let left = (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let mut right = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        mappings.iter().for_each(|(&i, &j)| right[j] = left[i]);
+
+        let mut prove_and_verify = |right: &[P::ScalarField]| {
+            let witness = Witness::new_private(&pp, &[&left], &[right]).unwrap();
+            let statement = witness.generate_private_statement(&pp).unwrap();
+            let proof = private_mapping::prove(&pp, &mapping, &witness, &statement, rng)?;
+            private_mapping::verify(&pp, &commitment, &statement, &proof)
+        };
+        prove_and_verify(&right).unwrap();
+
+        // A mapped right value differs.
+        right[9] += P::ScalarField::from(1u64);
+        assert!(prove_and_verify(&right).is_err());
+
+        // The blinders only hide the mapping over `max_proofs` proofs.
+        right[9] -= P::ScalarField::from(1u64);
+        assert!(matches!(prove_and_verify(&right), Err(Error::OpeningLimitReached)));
+
+        // Two left positions mapped to the same right position.
+        let non_injective: BTreeMap<usize, usize> = [(0, 3), (4, 3)].into_iter().collect();
+        assert!(matches!(
+            PrivateMapping::new(&pp, &non_injective, rng),
+            Err(Error::MappingIsNotInjective(3))
+        ));
+    }
+
+    fn unindexed_lookup<P: Pairing>() {
+        use crate::error::Error;
+        use crate::lookup;
+
+        let rng = &mut test_rng();
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .lookup_positions(&[1, 3, 4])
+            .build(rng).unwrap();
+
+        // Two selected positions look up the same right value, none at its mapped index.
+        let right = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let mut left = (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        left[1] = right[11];
+        left[3] = right[5];
+        left[4] = right[11];
+
+        let witness = Witness::new(&pp, &left, &right).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let proof = lookup::prove(&pp, &witness, &statement).unwrap();
+        lookup::verify(&pp, &statement, &proof).unwrap();
+
+        // Verification after a serialization round trip.
+        let mut buf = Vec::new();
+        proof.serialize_compressed(&mut buf).unwrap();
+        let proof = lookup::LookupProof::<P>::deserialize_compressed(&buf[..]).unwrap();
+        lookup::verify(&pp, &statement, &proof).unwrap();
+
+        // The proof does not carry over to a different right vector.
+        let mut other_right = right.clone();
+        other_right[5] += P::ScalarField::from(1u64);
+        let other_statement = Witness::new(&pp, &left, &other_right).unwrap().generate_statement(&pp).unwrap();
+        assert!(lookup::verify(&pp, &other_statement, &proof).is_err());
+
+        // A selected left value missing from the right vector.
+        let witness = Witness::new(&pp, &left, &other_right).unwrap();
+        assert!(lookup::prove(&pp, &witness, &other_statement).is_err());
+
+        // Lookup parameters hold no mapping for the mapping argument.
+        assert!(matches!(
+            prove::<P>(&pp, &witness, &other_statement),
+            Err(Error::IndexMappingCannotBeNone)
+        ));
+    }
+
+    fn shuffle<P: Pairing>() {
+        use crate::error::Error;
+        use crate::shuffle;
+
+        let rng = &mut test_rng();
+        let positions_left = [0, 2, 5, 7];
+        let positions_right = [3, 4, 9, 15];
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .unpaired_positions(&positions_left, &positions_right)
+            .build(rng).unwrap();
+
+        // The selected right values are the selected left values in another order.
+        let left = (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let mut right = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        [(0, 15), (2, 3), (5, 9), (7, 4)].iter().for_each(|&(i, j)| right[j] = left[i]);
+
+        let prove_and_verify = |right: &[P::ScalarField]| {
+            let witness = Witness::new(&pp, &left, right).unwrap();
+            let statement = witness.generate_statement(&pp).unwrap();
+            let proof = shuffle::prove(&pp, &witness, &statement).unwrap();
+            shuffle::verify(&pp, &statement, &proof)
+        };
+        prove_and_verify(&right).unwrap();
+
+        // A selected right value is replaced by another selected left value.
+        right[9] = left[0];
+        assert!(prove_and_verify(&right).is_err());
+
+        assert!(matches!(
+            PublicParameters::<P>::builder()
+                .size_left_values(8)
+                .size_right_values(16)
+                .unpaired_positions(&positions_left, &positions_right[1..])
+                .build(rng),
+            Err(Error::UnequalNumberOfPositions(4, 3))
+        ));
+    }
+
+    fn chain<P: Pairing>() {
+        use crate::link::{self, LinkPublicParameters, LinkWitness};
+
+        let rng = &mut test_rng();
+        // Input segment -> intermediate table -> output.
+        let mappings_ab: BTreeMap<usize, usize> = [(0, 9), (3, 2), (5, 14)].into_iter().collect();
+        let mappings_bc: BTreeMap<usize, usize> = [(2, 1), (9, 6), (11, 0)].into_iter().collect();
+
+        let pp = LinkPublicParameters::<P>::builder()
+            .vector_sizes(&[8, 16, 8])
+            .chain(&[mappings_ab.clone(), mappings_bc.clone()])
+            .build(rng).unwrap();
+        assert_eq!(pp.edges.len(), 2);
+
+        let mut vectors = [8, 16, 8].into_iter()
+            .map(|size| (0..size).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        mappings_ab.iter().for_each(|(&i, &j)| vectors[1][j] = vectors[0][i]);
+        mappings_bc.iter().for_each(|(&i, &j)| vectors[2][j] = vectors[1][i]);
+
+        let prove_and_verify = |vectors: &[Vec<P::ScalarField>]| {
+            let vectors = vectors.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let witness = LinkWitness::new(&pp, &vectors).unwrap();
+            let statement = witness.generate_statement(&pp).unwrap();
+            assert_eq!(statement.num_vectors(), 3);
+            let proof = link::prove::<P>(&pp, &witness, &statement).unwrap();
+            link::verify::<P>(&pp, &statement, &proof)
+        };
+        prove_and_verify(&vectors).unwrap();
+
+        // The intermediate value is changed on both edges, so only the ends disagree.
+        vectors[1][9] += P::ScalarField::from(1u64);
+        vectors[2][6] = vectors[1][9];
+        assert!(prove_and_verify(&vectors).is_err());
+    }
+
+    fn copy_constraints<P: Pairing>() {
+        use crate::link::{self, LinkPublicParameters, LinkWitness};
+
+        let rng = &mut test_rng();
+        // Two classes of equal values: {1, 4, 7, 12} and {3, 9}.
+        let pairs = [(4, 1), (7, 12), (1, 12), (3, 9)];
+
+        let pp = LinkPublicParameters::<P>::builder()
+            .vector_sizes(&[16])
+            .copy_constraints(0, &pairs)
+            .build(rng).unwrap();
+        assert_eq!(pp.domains.len(), 1);
+        assert_eq!(pp.edges[0].position_mappings, [(1, 4), (3, 9), (4, 7), (7, 12)].into_iter().collect());
+
+        let mut values = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        [4, 7, 12].iter().for_each(|&i| values[i] = values[1]);
+        values[9] = values[3];
+
+        let prove_and_verify = |values: &[P::ScalarField]| {
+            let witness = LinkWitness::new(&pp, &[values]).unwrap();
+            let statement = witness.generate_statement(&pp).unwrap();
+            // The vector is committed once.
+            assert_eq!(statement.g1_affine_values.len(), 1);
+            let proof = link::prove::<P>(&pp, &witness, &statement).unwrap();
+            link::verify::<P>(&pp, &statement, &proof)
+        };
+        prove_and_verify(&values).unwrap();
+
+        // A second vector is not part of the statement.
+        assert!(LinkWitness::new(&pp, &[&values, &values]).is_err());
+
+        // One position of a class differs from the others.
+        values[12] += P::ScalarField::from(1u64);
+        assert!(prove_and_verify(&values).is_err());
+    }
+
+    fn selector_gated<P: Pairing>() {
+        use crate::gated::{self, Selector};
+        use crate::kzg::Kzg;
+        use ark_ec::CurveGroup;
+        use ark_poly::univariate::DensePolynomial;
+        use ark_poly::{DenseUVPolynomial, EvaluationDomain};
+
+        let rng = &mut test_rng();
+        let mappings: BTreeMap<usize, usize> = [(0, 4), (2, 7), (5, 1), (6, 12)].into_iter().collect();
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .position_mappings(&mappings)
+            .build(rng).unwrap();
+
+        // Only the pairs at left positions 2 and 6 are active, the others hold unrelated values.
+        let selector = Selector::new(&pp, &[2, 6]).unwrap();
+        let left = (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let mut right = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        right[7] = left[2];
+        right[12] = left[6];
+
+        let prove_and_verify = |right: &[P::ScalarField], g1_affine_selector: &P::G1Affine| {
+            let witness = Witness::new(&pp, &left, right).unwrap();
+            let statement = witness.generate_statement(&pp).unwrap();
+            let proof = gated::prove(&pp, &witness, &selector, &statement).unwrap();
+            gated::verify(&pp, &statement, g1_affine_selector, &proof)
+        };
+        prove_and_verify(&right, &selector.g1_affine_selector).unwrap();
+
+        // The proof does not hold for another selector commitment.
+        let other_selector = Selector::new(&pp, &[0, 2, 6]).unwrap();
+        assert!(prove_and_verify(&right, &other_selector.g1_affine_selector).is_err());
+
+        // An active pair differs.
+        right[12] += P::ScalarField::from(1u64);
+        assert!(prove_and_verify(&right, &selector.g1_affine_selector).is_err());
+
+        assert!(Selector::new(&pp, &[3]).is_err());
+
+        // A selector value other than zero or one.
+        right[12] = left[6];
+        let mut values = selector.values.clone();
+        values[6] = P::ScalarField::from(2u64);
+        let poly_selector = DensePolynomial::from_coefficients_vec(pp.domain_l.ifft(&values));
+        let g1_affine_selector = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_selector).into_affine();
+        let selector = Selector { values, poly_selector, g1_affine_selector };
+        let witness = Witness::new(&pp, &left, &right).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+        let proof = gated::prove(&pp, &witness, &selector, &statement).unwrap();
+        assert!(gated::verify(&pp, &statement, &selector.g1_affine_selector, &proof).is_err());
+    }
+
+    fn sum_equality<P: Pairing>() {
+        use crate::sum::{self, SumParameters};
+
+        let rng = &mut test_rng();
+        let positions_left = [1, 2, 6];
+        let positions_right = [0, 5, 9, 13];
+
+        let pp = SumParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .positions_left(&positions_left)
+            .positions_right(&positions_right)
+            .build(rng).unwrap();
+
+        // The hash is recomputed from the deserialized parameters.
+        let mut buf = Vec::new();
+        pp.serialize_compressed(&mut buf).unwrap();
+        let pp_read = SumParameters::<P>::deserialize_compressed(&buf[..]).unwrap();
+        assert_eq!(pp_read.hash_representation, pp.hash_representation);
+
+        // Two columns, each with equal sums over the selected positions.
+        let left_columns = (0..2)
+            .map(|_| (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut right_columns = (0..2)
+            .map(|_| (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        for (left, right) in left_columns.iter().zip(right_columns.iter_mut()) {
+            let left_sum: P::ScalarField = positions_left.iter().map(|&i| left[i]).sum();
+            let right_sum: P::ScalarField = positions_right[1..].iter().map(|&j| right[j]).sum();
+            right[0] = left_sum - right_sum;
+        }
+
+        let prove_and_verify = |right_columns: &[Vec<P::ScalarField>]| {
+            let witness = Witness::new_sum(
+                &pp,
+                &left_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+                &right_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            ).unwrap();
+            let statement = witness.generate_sum_statement(&pp).unwrap();
+            let proof = sum::prove(&pp, &witness, &statement).unwrap();
+            sum::verify(&pp, &statement, &proof)
+        };
+        prove_and_verify(&right_columns).unwrap();
+
+        // Values move between selected positions, but an unselected value does not count.
+        right_columns[0][5] += P::ScalarField::from(3u64);
+        right_columns[0][9] -= P::ScalarField::from(3u64);
+        right_columns[0][10] += P::ScalarField::from(1u64);
+        prove_and_verify(&right_columns).unwrap();
+
+        // The sums of the second column differ.
+        right_columns[1][13] += P::ScalarField::from(1u64);
+        assert!(prove_and_verify(&right_columns).is_err());
+    }
+
+    fn state_transition<P: Pairing>() {
+        use crate::transition::{self, TransitionStatement, TransitionWitness};
+
+        let rng = &mut test_rng();
+        let mappings: BTreeMap<usize, usize> = [(0, 3), (2, 8), (7, 14)].into_iter().collect();
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .position_mappings(&mappings)
+            .build(rng).unwrap();
+
+        let left = (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let old_right = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let mut new_right = old_right.clone();
+        mappings.iter().for_each(|(&i, &j)| new_right[j] = left[i]);
+
+        let prove_and_verify = |new_right: &[P::ScalarField]| {
+            let witness = TransitionWitness::new(&pp, &[&left], &[&old_right], &[new_right]).unwrap();
+            let statement = witness.generate_statement(&pp).unwrap();
+            let proof = transition::prove(&pp, &witness, &statement).unwrap();
+
+            // Verification after a serialization round trip of the statement.
+            let mut buf = Vec::new();
+            statement.serialize_compressed(&mut buf).unwrap();
+            let statement = TransitionStatement::<P>::deserialize_compressed(&buf[..]).unwrap();
+            transition::verify(&pp, &statement, &proof)
+        };
+        prove_and_verify(&new_right).unwrap();
+
+        // An untouched position changed.
+        new_right[5] += P::ScalarField::from(1u64);
+        assert!(prove_and_verify(&new_right).is_err());
+
+        // A mapped position kept its old value.
+        new_right[5] = old_right[5];
+        new_right[8] = old_right[8];
+        assert!(prove_and_verify(&new_right).is_err());
+    }
+
+    fn pedersen_link<P: Pairing>() {
+        use crate::pedersen::{prove_link, verify_link, PedersenGenerators, PedersenLinkProof};
+        use ark_ec::CurveGroup;
+
+        let rng = &mut test_rng();
+        let mut mappings = BTreeMap::new();
+        mappings.insert(0, 0);
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .position_mappings(&mappings)
+            .build(rng).unwrap();
+
+        let left_columns = (0..2)
+            .map(|_| (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let right_columns = (0..2)
+            .map(|_| (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let witness = Witness::new_multi_column(
+            &pp,
+            &left_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            &right_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        ).unwrap();
+        let statement = witness.generate_statement(&pp).unwrap();
+
+        // Amounts of the second column held in standalone Pedersen commitments.
+        let generators = PedersenGenerators::<P> {
+            g: P::G1::rand(rng).into_affine(),
+            h: P::G1::rand(rng).into_affine(),
+        };
+        let openings = [6, 1, 4].into_iter().map(|i| (i, P::ScalarField::rand(rng))).collect::<Vec<_>>();
+        let mut commitments = openings.iter()
+            .map(|&(i, r)| (i, generators.commit(left_columns[1][i], r)))
+            .collect::<Vec<_>>();
+
+        let proof = prove_link(&pp, &witness, &statement, &generators, 1, &openings, rng).unwrap();
+        verify_link(&pp, &statement, &generators, 1, &commitments, &proof).unwrap();
+
+        // Verification after a serialization round trip.
+        let mut buf = Vec::new();
+        proof.serialize_compressed(&mut buf).unwrap();
+        let proof = PedersenLinkProof::<P>::deserialize_compressed(&buf[..]).unwrap();
+        verify_link(&pp, &statement, &generators, 1, &commitments, &proof).unwrap();
+
+        // The values are those of the second column, not the first.
+        assert!(verify_link(&pp, &statement, &generators, 0, &commitments, &proof).is_err());
+
+        // A Pedersen commitment holds another value.
+        commitments[2].1 = generators.commit(left_columns[1][4] + P::ScalarField::from(1u64), openings[2].1);
+        assert!(verify_link(&pp, &statement, &generators, 1, &commitments, &proof).is_err());
+    }
+
+    fn aggregate<P: Pairing>() {
+        use crate::aggregate::{self, AggregateProof};
+
+        let rng = &mut test_rng();
+        let mut mappings = BTreeMap::new();
+        mappings.insert(1, 3);
+        mappings.insert(5, 10);
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .position_mappings(&mappings)
+            .build(rng).unwrap();
+
+        // Instances with one, two and one columns.
+        let mut witnesses = Vec::new();
+        for num_columns in [1, 2, 1] {
+            let left_columns = (0..num_columns)
+                .map(|_| (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let right_columns = left_columns.iter()
+                .map(|left| {
+                    let mut right = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+                    mappings.iter().for_each(|(&i, &j)| right[j] = left[i]);
+                    right
+                })
+                .collect::<Vec<_>>();
+            witnesses.push(Witness::new_multi_column(
+                &pp,
+                &left_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+                &right_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            ).unwrap());
+        }
+        let statements = witnesses.iter().map(|w| w.generate_statement(&pp).unwrap()).collect::<Vec<_>>();
+        let witness_refs = witnesses.iter().collect::<Vec<_>>();
+        let statement_refs = statements.iter().collect::<Vec<_>>();
+
+        let proof = aggregate::prove(&pp, &witness_refs, &statement_refs).unwrap();
+        aggregate::verify(&pp, &statement_refs, &proof).unwrap();
+
+        // Verification after a serialization round trip.
+        let mut buf = Vec::new();
+        proof.serialize_compressed(&mut buf).unwrap();
+        let proof = AggregateProof::<P>::deserialize_compressed(&buf[..]).unwrap();
+        aggregate::verify(&pp, &statement_refs, &proof).unwrap();
+
+        // The statements are bound in order and all of them are needed.
+        let swapped = [statement_refs[1], statement_refs[0], statement_refs[2]];
+        assert!(aggregate::verify(&pp, &swapped, &proof).is_err());
+        assert!(aggregate::verify(&pp, &statement_refs[..2], &proof).is_err());
+
+        // One instance breaks the mapping.
+        let left = (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let right = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let wrong_witness = Witness::new(&pp, &left, &right).unwrap();
+        let wrong_statement = wrong_witness.generate_statement(&pp).unwrap();
+        let witness_refs = [witness_refs[0], &wrong_witness, witness_refs[2]];
+        let statement_refs = [statement_refs[0], &wrong_statement, statement_refs[2]];
+        let proof = aggregate::prove(&pp, &witness_refs, &statement_refs).unwrap();
+        assert!(aggregate::verify(&pp, &statement_refs, &proof).is_err());
+    }
+
+    fn batch<P: Pairing>() {
+        use crate::batch::{self, BatchProof};
+        use crate::error::Error;
+
+        let rng = &mut test_rng();
+        let tau = P::ScalarField::rand(rng);
+        let build = |size_left, size_right, mappings: &BTreeMap<usize, usize>, tau, rng: &mut _| {
+            PublicParameters::<P>::builder()
+                .size_left_values(size_left)
+                .size_right_values(size_right)
+                .position_mappings(mappings)
+                .tau(tau)
+                .build(rng).unwrap()
+        };
+        let witness = |pp: &PublicParameters<P>, mappings: &BTreeMap<usize, usize>, rng: &mut _| {
+            let left = (0..pp.size_left_values).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+            let mut right = (0..pp.size_right_values).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+            mappings.iter().for_each(|(&i, &j)| right[j] = left[i]);
+            Witness::new(pp, &left, &right).unwrap()
+        };
+
+        // Two sets of parameters with different sizes and mappings over the same tau.
+        let mappings_a = BTreeMap::from([(0, 2), (3, 9)]);
+        let mappings_b = BTreeMap::from([(1, 0), (2, 3), (3, 1)]);
+        let pp_a = build(8, 16, &mappings_a, tau, rng);
+        let pp_b = build(4, 4, &mappings_b, tau, rng);
+        let witness_a = witness(&pp_a, &mappings_a, rng);
+        let witness_b = witness(&pp_b, &mappings_b, rng);
+        let statement_a = witness_a.generate_statement(&pp_a).unwrap();
+        let statement_b = witness_b.generate_statement(&pp_b).unwrap();
+
+        let proof = batch::prove(&[(&pp_a, &witness_a, &statement_a), (&pp_b, &witness_b, &statement_b)]).unwrap();
+        batch::verify(&[(&pp_a, &statement_a), (&pp_b, &statement_b)], &proof).unwrap();
+
+        // Verification after a serialization round trip.
+        let mut buf = Vec::new();
+        proof.serialize_compressed(&mut buf).unwrap();
+        let proof = BatchProof::<P>::deserialize_compressed(&buf[..]).unwrap();
+        batch::verify(&[(&pp_a, &statement_a), (&pp_b, &statement_b)], &proof).unwrap();
+
+        // Each statement is bound to its parameters.
+        let statement_c = witness(&pp_a, &mappings_a, rng).generate_statement(&pp_a).unwrap();
+        assert!(batch::verify(&[(&pp_a, &statement_c), (&pp_b, &statement_b)], &proof).is_err());
+        assert!(batch::verify(&[(&pp_b, &statement_b), (&pp_a, &statement_a)], &proof).is_err());
+
+        // Parameters from another tau are rejected.
+        let pp_c = build(4, 4, &mappings_b, P::ScalarField::rand(rng), rng);
+        assert!(matches!(
+            batch::verify(&[(&pp_a, &statement_a), (&pp_c, &statement_b)], &proof),
+            Err(Error::SrsMismatch(1))
+        ));
+
+        // A G2 SRS longer than that of the parameters with the longest G1 SRS is a mismatch.
+        let mut pp_d = build(4, 4, &mappings_b, tau, rng);
+        pp_d.g2_affine_srs = build(32, 32, &mappings_b, tau, rng).g2_affine_srs;
+        assert!(matches!(
+            batch::verify(&[(&pp_a, &statement_a), (&pp_d, &statement_b)], &proof),
+            Err(Error::SrsMismatch(1))
+        ));
+    }
+
+    fn folding<P: Pairing>() {
+        use crate::folding::{self, Accumulator, DeciderProof, RelaxedInstance};
+
+        let rng = &mut test_rng();
+        let mappings = BTreeMap::from([(0, 5), (2, 1), (3, 14)]);
+        let constants = BTreeMap::from([(2, P::ScalarField::from(7u64))]);
+
+        let pp = PublicParameters::<P>::builder()
+            .size_left_values(4)
+            .size_right_values(16)
+            .position_mappings(&mappings)
+            .left_constants(&constants)
+            .build(rng).unwrap();
+
+        let block = |valid: bool, rng: &mut _| {
+            let mut left = (0..4).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+            left[2] = constants[&2];
+            let mut right = (0..16).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>();
+            mappings.iter().for_each(|(&i, &j)| right[j] = left[i]);
+            if !valid {
+                right[14] += P::ScalarField::from(1u64);
+            }
+            let witness = Witness::new(&pp, &left, &right).unwrap();
+            let statement = witness.generate_statement(&pp).unwrap();
+            (witness, statement)
+        };
+
+        // The prover folds every block, and the verifier folds the statements alongside.
+        let mut accumulator = Accumulator::new(&pp);
+        let mut instance = RelaxedInstance::<P>::zero();
+        for _ in 0..3 {
+            let (witness, statement) = block(true, rng);
+            let (next, fold_proof) = folding::fold(&pp, &accumulator, &witness, &statement).unwrap();
+            instance = folding::fold_instance(&pp, &instance, &statement, &fold_proof).unwrap();
+            assert_eq!(next.instance(), &instance);
+            accumulator = next;
+        }
+
+        let proof = folding::decide(&pp, &accumulator).unwrap();
+        folding::verify_decider(&pp, &instance, &proof).unwrap();
+
+        // Verification after a serialization round trip.
+        let mut buf = Vec::new();
+        instance.serialize_compressed(&mut buf).unwrap();
+        proof.serialize_compressed(&mut buf).unwrap();
+        let mut reader = &buf[..];
+        let instance = RelaxedInstance::<P>::deserialize_compressed(&mut reader).unwrap();
+        let proof = DeciderProof::<P>::deserialize_compressed(&mut reader).unwrap();
+        folding::verify_decider(&pp, &instance, &proof).unwrap();
+
+        // The proof is bound to the accumulated instance.
+        assert!(folding::verify_decider(&pp, &RelaxedInstance::zero(), &proof).is_err());
+
+        // A block that breaks the mapping spoils the accumulator for good.
+        let (witness, statement) = block(false, rng);
+        let (accumulator, fold_proof) = folding::fold(&pp, &accumulator, &witness, &statement).unwrap();
+        let instance = folding::fold_instance(&pp, &instance, &statement, &fold_proof).unwrap();
+        let (witness, statement) = block(true, rng);
+        let (accumulator, fold_proof) = folding::fold(&pp, &accumulator, &witness, &statement).unwrap();
+        let instance = folding::fold_instance(&pp, &instance, &statement, &fold_proof).unwrap();
+        let proof = folding::decide(&pp, &accumulator).unwrap();
+        assert!(folding::verify_decider(&pp, &instance, &proof).is_err());
+    }
+
+    fn grand_product<P: Pairing>() {
+        use crate::prover::{prove_with_backend, Backend, BackendProof};
+        use crate::verifier::verify_with_backend;
+
+        let rng = &mut test_rng();
+        let rate = P::ScalarField::from(3u64);
+        let fee = P::ScalarField::from(7u64);
+        let constant = P::ScalarField::from(11u64);
+
+        // Different and equal domain sizes, the latter with one shifted opening point.
+        for size_right in [16, 8] {
+            let mappings = BTreeMap::from([(0, 2), (3, 5), (6, 7)]);
+            let pp = PublicParameters::<P>::builder()
+                .size_left_values(8)
+                .size_right_values(size_right)
+                .position_mappings(&mappings)
+                .position_coefficients(&BTreeMap::from([(3, (rate, fee))]))
+                .left_constants(&BTreeMap::from([(1, constant)]))
+                .build(rng).unwrap();
+
+            let mut left_columns = (0..2)
+                .map(|_| (0..8).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            left_columns[0][1] = constant;
+            let mut right_columns = (0..2)
+                .map(|_| (0..size_right).map(|_| P::ScalarField::rand(rng)).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            for (left, right) in left_columns.iter().zip(right_columns.iter_mut()) {
+                right[2] = left[0];
+                right[5] = rate * left[3] + fee;
+                right[7] = left[6];
+            }
+            let witness = |left_columns: &[Vec<P::ScalarField>], right_columns: &[Vec<P::ScalarField>]| {
+                Witness::new_multi_column(
+                    &pp,
+                    &left_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+                    &right_columns.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+                ).unwrap()
+            };
+            let valid_witness = witness(&left_columns, &right_columns);
+            let statement = valid_witness.generate_statement(&pp).unwrap();
+
+            // Both backends prove the same statement.
+            for backend in [Backend::LogDerivative, Backend::GrandProduct] {
+                let proof = prove_with_backend(&pp, &valid_witness, &statement, backend).unwrap();
+                assert_eq!(proof.backend(), backend);
+                verify_with_backend(&pp, &statement, &proof).unwrap();
+
+                // Verification after a serialization round trip.
+                let mut buf = Vec::new();
+                proof.serialize_compressed(&mut buf).unwrap();
+                let proof = BackendProof::<P>::deserialize_compressed(&buf[..]).unwrap();
+                verify_with_backend(&pp, &statement, &proof).unwrap();
+            }
+
+            // A wrong value in the second column, and a wrong public constant.
+            let mut wrong_right_columns = right_columns.clone();
+            wrong_right_columns[1][7] += P::ScalarField::from(1u64);
+            let mut wrong_left_columns = left_columns.clone();
+            wrong_left_columns[0][1] = P::ScalarField::from(12u64);
+            for (left, right) in [(&left_columns, &wrong_right_columns), (&wrong_left_columns, &right_columns)] {
+                let wrong_witness = witness(left, right);
+                let statement = wrong_witness.generate_statement(&pp).unwrap();
+                let proof = prove_with_backend(&pp, &wrong_witness, &statement, Backend::GrandProduct).unwrap();
+                assert!(verify_with_backend(&pp, &statement, &proof).is_err());
+            }
+        }
     }
 }
@@ -0,0 +1,19 @@
+//! Links any number of committed vectors with one proof.
+//!
+//! The public parameters hold a list of vectors and a list of edges, each mapping positions
+//! of a source vector to positions of a target vector. The statement holds one commitment per
+//! vector. For example, `k` segments mapped into one shared vector are `k + 1` vectors with
+//! one edge from each segment to the shared vector; their sums are combined into the sum of
+//! the shared vector, so verification costs one pairing check whatever `k` is.
+
+pub mod public_parameters;
+pub mod witness;
+pub mod statement;
+pub mod prover;
+pub mod verifier;
+
+pub use prover::{prove, LinkProof};
+pub use public_parameters::{LinkEdge, LinkPublicParameters, LinkPublicParametersBuilder, LinkTarget};
+pub use statement::LinkStatement;
+pub use verifier::verify;
+pub use witness::LinkWitness;
@@ -0,0 +1,209 @@
+use crate::domain::roots_of_unity;
+use crate::error::Error;
+use crate::kzg::{powers_of_scalars, Kzg};
+use crate::link::public_parameters::LinkPublicParameters;
+use crate::link::statement::LinkStatement;
+use crate::link::witness::LinkWitness;
+use crate::prover::{fractional_poly, quotient_poly};
+use crate::transcript::{Label, Transcript};
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_ff::One;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{EvaluationDomain, Polynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{cfg_iter, cfg_iter_mut, vec, vec::Vec, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Proof that every edge of the public parameters holds.
+///
+/// Per-edge elements follow the order of `pp.edges`, per-target elements that of `pp.targets`,
+/// quotients that of `pp.domains`, and value evaluations that of the vectors.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LinkProof<P: Pairing> {
+    pub(crate) g1_affine_l: Vec<P::G1Affine>,
+    pub(crate) g1_affine_r: Vec<P::G1Affine>,
+    pub(crate) g1_affine_q: Vec<P::G1Affine>,
+    pub(crate) batch_proof: P::G1Affine,
+    pub(crate) l_at_delta: Vec<P::ScalarField>,
+    pub(crate) r_at_delta: Vec<P::ScalarField>,
+    pub(crate) v_at_delta: Vec<P::ScalarField>,
+    pub(crate) p_at_delta: Vec<P::ScalarField>,
+    pub(crate) m_at_delta: Vec<P::ScalarField>,
+    pub(crate) c_at_delta: Vec<P::ScalarField>,
+    pub(crate) l_at_zero: Vec<P::ScalarField>,
+    pub(crate) r_at_zero: Vec<P::ScalarField>,
+}
+
+/// Proves that the values of each edge's source positions equal those of the target
+/// positions they are mapped to.
+///
+/// Each edge `e` gets a polynomial `L_e` that is `1 / (beta + v_i + gamma * w^j)` at its mapped
+/// source positions, and each target `t` a polynomial `R_t` that is
+/// `c_j / (beta + v_j + gamma * w^j)` at position `j` mapped `c_j` times. The sums of the `L_e`
+/// into a target match the sum of its `R_t`, so the verifier checks one equation per target.
+pub fn prove<P: Pairing>(
+    pp: &LinkPublicParameters<P>,
+    witness: &LinkWitness<P>,
+    statement: &LinkStatement<P>,
+) -> Result<LinkProof<P>, Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    // Sample random beta, gamma.
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    let fr_one = P::ScalarField::one();
+    let roots_of_unity_list: Vec<Vec<P::ScalarField>> = pp.domains.iter().map(roots_of_unity::<P>).collect();
+
+    // Construct the polynomial of the source side of each edge.
+    let mut poly_l_list = Vec::with_capacity(pp.edges.len());
+    for edge in pp.edges.iter() {
+        let roots_of_unity_target = &roots_of_unity_list[pp.vector_domains[edge.target]];
+        let values = &witness.values[edge.source];
+        let fraction_list = edge.position_mappings.iter()
+            .map(|(&i, &j)| (i, fr_one, beta + values[i] + gamma * roots_of_unity_target[j]))
+            .collect();
+        poly_l_list.push(fractional_poly(&pp.domains[pp.vector_domains[edge.source]], fraction_list)?);
+    }
+
+    // Construct the polynomial of each target.
+    let mut poly_r_list = Vec::with_capacity(pp.targets.len());
+    for target in pp.targets.iter() {
+        let roots_of_unity_target = &roots_of_unity_list[pp.vector_domains[target.vector]];
+        let values = &witness.values[target.vector];
+        let fraction_list = target.multiplicities.iter()
+            .map(|(&j, &count)| {
+                let numerator = P::ScalarField::from(count as u64);
+                (j, numerator, beta + values[j] + gamma * roots_of_unity_target[j])
+            })
+            .collect();
+        poly_r_list.push(fractional_poly(&pp.domains[pp.vector_domains[target.vector]], fraction_list)?);
+    }
+
+    let g1_affine_l: Vec<P::G1Affine> = poly_l_list.iter()
+        .map(|poly| Kzg::<P::G1>::commit(&pp.g1_affine_srs, poly).into_affine())
+        .collect();
+    let g1_affine_r: Vec<P::G1Affine> = poly_r_list.iter()
+        .map(|poly| Kzg::<P::G1>::commit(&pp.g1_affine_srs, poly).into_affine())
+        .collect();
+    transcript.append_elements(&[(Label::G1L, g1_affine_l.as_slice()), (Label::G1R, g1_affine_r.as_slice())])?;
+
+    // Sample random eta to combine the identities over each domain into one quotient.
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+    let powers_of_eta = powers_of_scalars(eta, pp.edges.len() + pp.targets.len());
+
+    let coset_eval_list_values: Vec<Vec<P::ScalarField>> = witness.poly_values.iter()
+        .zip(pp.vector_domains.iter())
+        .map(|(poly, &d)| pp.domain_cosets[d].fft(poly))
+        .collect();
+    let mut coset_eval_list_numerators: Vec<Vec<P::ScalarField>> = pp.domain_sizes.iter()
+        .map(|&size| vec![P::ScalarField::zero(); size])
+        .collect();
+    for ((edge, poly_l), &fr_pow) in pp.edges.iter().zip(poly_l_list.iter()).zip(powers_of_eta.iter()) {
+        let d = pp.vector_domains[edge.source];
+        let coset_eval_list_l = pp.domain_cosets[d].fft(poly_l);
+        cfg_iter_mut!(coset_eval_list_numerators[d])
+            .zip(cfg_iter!(coset_eval_list_l))
+            .zip(cfg_iter!(coset_eval_list_values[edge.source]))
+            .zip(cfg_iter!(edge.coset_eval_list_positions))
+            .zip(cfg_iter!(edge.coset_eval_list_position_mappings))
+            .for_each(|((((n, &l), &v), &p), &m)| *n += fr_pow * (l * (beta + v + gamma * m) - p));
+    }
+    for ((target, poly_r), &fr_pow) in pp.targets.iter().zip(poly_r_list.iter()).zip(powers_of_eta[pp.edges.len()..].iter()) {
+        let d = pp.vector_domains[target.vector];
+        let coset_eval_list_r = pp.domain_cosets[d].fft(poly_r);
+        cfg_iter_mut!(coset_eval_list_numerators[d])
+            .zip(cfg_iter!(coset_eval_list_r))
+            .zip(cfg_iter!(coset_eval_list_values[target.vector]))
+            .zip(cfg_iter!(target.coset_eval_list_multiplicities))
+            .zip(cfg_iter!(pp.roots_of_unity_cosets[d]))
+            .for_each(|((((n, &r), &v), &c), &x)| *n += fr_pow * (r * (beta + v + gamma * x) - c));
+    }
+    let mut poly_q_list = Vec::with_capacity(pp.domains.len());
+    for (d, coset_eval_list) in coset_eval_list_numerators.into_iter().enumerate() {
+        poly_q_list.push(quotient_poly::<P>(&pp.domains[d], &pp.domain_cosets[d], coset_eval_list)?);
+    }
+    let g1_affine_q: Vec<P::G1Affine> = poly_q_list.iter()
+        .map(|poly| Kzg::<P::G1>::commit(&pp.g1_affine_srs, poly).into_affine())
+        .collect();
+    transcript.append_element(Label::G1Q, &g1_affine_q)?;
+
+    // Sample random delta.
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    let evaluate = |poly_list: &[&DensePolynomial<P::ScalarField>], point| -> Vec<P::ScalarField> {
+        poly_list.iter().map(|poly| poly.evaluate(point)).collect()
+    };
+    let poly_l_refs: Vec<_> = poly_l_list.iter().collect();
+    let poly_r_refs: Vec<_> = poly_r_list.iter().collect();
+    let poly_q_refs: Vec<_> = poly_q_list.iter().collect();
+    let poly_v_refs: Vec<_> = witness.poly_values.iter().collect();
+    let poly_p_refs: Vec<_> = pp.edges.iter().map(|edge| &edge.poly_positions).collect();
+    let poly_m_refs: Vec<_> = pp.edges.iter().map(|edge| &edge.poly_position_mappings).collect();
+    let poly_c_refs: Vec<_> = pp.targets.iter().map(|target| &target.poly_multiplicities).collect();
+
+    let l_at_delta = evaluate(&poly_l_refs, &delta);
+    let r_at_delta = evaluate(&poly_r_refs, &delta);
+    let v_at_delta = evaluate(&poly_v_refs, &delta);
+    let p_at_delta = evaluate(&poly_p_refs, &delta);
+    let m_at_delta = evaluate(&poly_m_refs, &delta);
+    let c_at_delta = evaluate(&poly_c_refs, &delta);
+
+    let fr_zero = P::ScalarField::zero();
+    let l_at_zero = evaluate(&poly_l_refs, &fr_zero);
+    let r_at_zero = evaluate(&poly_r_refs, &fr_zero);
+
+    transcript.append_elements(
+        &[
+            (Label::FrLAtDelta, l_at_delta.as_slice()),
+            (Label::FrRAtDelta, r_at_delta.as_slice()),
+            (Label::FrVAtDelta, v_at_delta.as_slice()),
+            (Label::FrPlAtDelta, p_at_delta.as_slice()),
+            (Label::FrPmAtDelta, m_at_delta.as_slice()),
+            (Label::FrCAtDelta, c_at_delta.as_slice()),
+            (Label::FrLAtZero, l_at_zero.as_slice()),
+            (Label::FrRAtZero, r_at_zero.as_slice()),
+        ]
+    )?;
+
+    // Sample random epsilon to separate the opened polynomials.
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let poly_list_at_delta: Vec<_> = [
+        &poly_l_refs,
+        &poly_r_refs,
+        &poly_q_refs,
+        &poly_v_refs,
+        &poly_p_refs,
+        &poly_m_refs,
+        &poly_c_refs,
+    ].into_iter().flatten().copied().collect();
+    let poly_list_at_zero: Vec<_> = [&poly_l_refs, &poly_r_refs].into_iter().flatten().copied().collect();
+    let batch_proof = Kzg::<P::G1>::multi_point_batch_open(
+        &pp.g1_affine_srs,
+        &[&poly_list_at_delta, &poly_list_at_zero],
+        &[delta, fr_zero],
+        epsilon,
+    );
+
+    Ok(LinkProof {
+        g1_affine_l,
+        g1_affine_r,
+        g1_affine_q,
+        batch_proof,
+        l_at_delta,
+        r_at_delta,
+        v_at_delta,
+        p_at_delta,
+        m_at_delta,
+        c_at_delta,
+        l_at_zero,
+        r_at_zero,
+    })
+}
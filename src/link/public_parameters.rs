@@ -0,0 +1,402 @@
+use crate::domain::roots_of_unity;
+use crate::error::Error;
+use crate::kzg::{unsafe_setup_from_tau, Kzg};
+use crate::COMPRESS_MOD;
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_ff::FftField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::collections::BTreeMap;
+use ark_std::io::{Read, Write};
+use ark_std::rand::Rng;
+use ark_std::{vec, vec::Vec};
+use ark_std::{One, UniformRand, Zero};
+use blake2::{Blake2b512, Digest};
+
+/// Public parameters linking several committed vectors through position mappings.
+///
+/// Each edge maps positions of its source vector to positions of its target vector. Vectors
+/// of the same size share one evaluation domain, and all identities over a domain are proven
+/// with a single quotient.
+#[derive(Debug)]
+pub struct LinkPublicParameters<P: Pairing> {
+    pub vector_sizes: Vec<usize>,
+
+    pub g1_affine_srs: Vec<P::G1Affine>,
+    pub g2_affine_srs: Vec<P::G2Affine>,
+
+    /// Distinct vector sizes in ascending order, one per domain.
+    pub domain_sizes: Vec<usize>,
+    pub domains: Vec<Radix2EvaluationDomain<P::ScalarField>>,
+    pub domain_cosets: Vec<Radix2EvaluationDomain<P::ScalarField>>,
+    pub roots_of_unity_cosets: Vec<Vec<P::ScalarField>>,
+    /// Index into `domains` of each vector.
+    pub vector_domains: Vec<usize>,
+
+    pub edges: Vec<LinkEdge<P>>,
+    pub targets: Vec<LinkTarget<P>>,
+
+    pub(crate) hash_representation: Vec<u8>,
+}
+
+/// The positions of the source vector that are mapped, and where they are mapped to.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LinkEdge<P: Pairing> {
+    pub source: usize,
+    pub target: usize,
+    pub position_mappings: BTreeMap<usize, usize>,
+
+    pub poly_positions: DensePolynomial<P::ScalarField>,
+    pub poly_position_mappings: DensePolynomial<P::ScalarField>,
+    pub g1_affine_positions: P::G1Affine,
+    pub g1_affine_position_mappings: P::G1Affine,
+    pub coset_eval_list_positions: Vec<P::ScalarField>,
+    pub coset_eval_list_position_mappings: Vec<P::ScalarField>,
+}
+
+/// A vector that is the target of at least one edge, with the number of source positions
+/// mapped to each of its positions.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LinkTarget<P: Pairing> {
+    pub vector: usize,
+    pub multiplicities: BTreeMap<usize, usize>,
+
+    pub poly_multiplicities: DensePolynomial<P::ScalarField>,
+    pub g1_affine_multiplicities: P::G1Affine,
+    pub coset_eval_list_multiplicities: Vec<P::ScalarField>,
+}
+
+impl<P: Pairing> LinkPublicParameters<P> {
+    pub fn builder() -> LinkPublicParametersBuilder<P> {
+        LinkPublicParametersBuilder::<P>::default()
+    }
+
+    pub fn num_vectors(&self) -> usize {
+        self.vector_sizes.len()
+    }
+
+    /// The commitments bind the public polynomials, from which everything else is derived.
+    fn compute_hash_representation(&self) -> Result<Vec<u8>, Error> {
+        let mut buf = Vec::new();
+        self.vector_sizes.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("vector_sizes", e))?;
+        self.g1_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_srs", e))?;
+        self.g2_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g2_affine_srs", e))?;
+        self.domains.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("domains", e))?;
+        for edge in self.edges.iter() {
+            edge.source.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+                Error::FailedToSerializeElement("source", e))?;
+            edge.target.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+                Error::FailedToSerializeElement("target", e))?;
+            edge.position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+                Error::FailedToSerializeElement("position_mappings", e))?;
+            edge.g1_affine_positions.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+                Error::FailedToSerializeElement("g1_affine_positions", e))?;
+            edge.g1_affine_position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+                Error::FailedToSerializeElement("g1_affine_position_mappings", e))?;
+        }
+        for target in self.targets.iter() {
+            target.g1_affine_multiplicities.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+                Error::FailedToSerializeElement("g1_affine_multiplicities", e))?;
+        }
+
+        Ok(Blake2b512::digest(&buf).to_vec())
+    }
+}
+
+// As for [`crate::public_parameters::PublicParameters`], the hash representation is recomputed
+// on deserialization instead of being read.
+impl<P: Pairing> CanonicalSerialize for LinkPublicParameters<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.vector_sizes.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_srs.serialize_with_mode(&mut writer, compress)?;
+        self.g2_affine_srs.serialize_with_mode(&mut writer, compress)?;
+        self.domain_sizes.serialize_with_mode(&mut writer, compress)?;
+        self.domains.serialize_with_mode(&mut writer, compress)?;
+        self.domain_cosets.serialize_with_mode(&mut writer, compress)?;
+        self.roots_of_unity_cosets.serialize_with_mode(&mut writer, compress)?;
+        self.vector_domains.serialize_with_mode(&mut writer, compress)?;
+        self.edges.serialize_with_mode(&mut writer, compress)?;
+        self.targets.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.vector_sizes.serialized_size(compress)
+            + self.g1_affine_srs.serialized_size(compress)
+            + self.g2_affine_srs.serialized_size(compress)
+            + self.domain_sizes.serialized_size(compress)
+            + self.domains.serialized_size(compress)
+            + self.domain_cosets.serialized_size(compress)
+            + self.roots_of_unity_cosets.serialized_size(compress)
+            + self.vector_domains.serialized_size(compress)
+            + self.edges.serialized_size(compress)
+            + self.targets.serialized_size(compress)
+    }
+}
+
+impl<P: Pairing> Valid for LinkPublicParameters<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.g1_affine_srs.check()?;
+        self.g2_affine_srs.check()?;
+        self.domains.check()?;
+        self.domain_cosets.check()?;
+        self.roots_of_unity_cosets.check()?;
+        self.edges.check()?;
+        self.targets.check()
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for LinkPublicParameters<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut pp = LinkPublicParameters {
+            vector_sizes: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            g1_affine_srs: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            g2_affine_srs: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_sizes: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            domains: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_cosets: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            roots_of_unity_cosets: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            vector_domains: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            edges: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            targets: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            hash_representation: Vec::new(),
+        };
+        pp.hash_representation = pp.compute_hash_representation()
+            .map_err(|_| SerializationError::InvalidData)?;
+
+        Ok(pp)
+    }
+}
+
+pub struct LinkPublicParametersBuilder<P: Pairing> {
+    vector_sizes: Option<Vec<usize>>,
+    tau: Option<P::ScalarField>,
+    edges: Vec<(usize, usize, BTreeMap<usize, usize>)>,
+}
+
+impl<P: Pairing> LinkPublicParametersBuilder<P> {
+    fn default() -> Self {
+        Self {
+            vector_sizes: None,
+            tau: None,
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn vector_sizes(mut self, sizes: &[usize]) -> Self {
+        self.vector_sizes = Some(sizes.to_vec());
+        self
+    }
+
+    pub fn tau(mut self, tau: P::ScalarField) -> Self {
+        self.tau = Some(tau);
+        self
+    }
+
+    /// Adds an edge requiring `vectors[target][j] == vectors[source][i]` for each `(i, j)`
+    /// in `mappings`.
+    pub fn edge(mut self, source: usize, target: usize, mappings: &BTreeMap<usize, usize>) -> Self {
+        self.edges.push((source, target, mappings.clone()));
+        self
+    }
+
+    /// Adds one edge from `source` to each vector referenced in `mappings`, which maps a
+    /// position of `source` to a `(target, position)` pair.
+    pub fn scatter(mut self, source: usize, mappings: &BTreeMap<usize, (usize, usize)>) -> Self {
+        let mut grouped: BTreeMap<usize, BTreeMap<usize, usize>> = BTreeMap::new();
+        mappings.iter().for_each(|(&i, &(target, j))| {
+            grouped.entry(target).or_default().insert(i, j);
+        });
+        grouped.into_iter().for_each(|(target, mappings)| {
+            self.edges.push((source, target, mappings));
+        });
+        self
+    }
+
+    /// Adds an edge from each vector to the next, `mappings[k]` mapping positions of vector
+    /// `k` to positions of vector `k + 1`. Intermediate vectors are committed once, as both the
+    /// target of one edge and the source of the next.
+    pub fn chain(mut self, mappings: &[BTreeMap<usize, usize>]) -> Self {
+        mappings.iter().enumerate().for_each(|(k, mappings)| {
+            self.edges.push((k, k + 1, mappings.clone()));
+        });
+        self
+    }
+
+    /// Adds copy constraints `vectors[vector][i] == vectors[vector][j]` for each `(i, j)` in
+    /// `pairs`. The positions are grouped into classes of equal values, and each class is
+    /// linked in ascending order by one edge from the vector to itself.
+    ///
+    /// This is the single-vector mode: with `.vector_sizes(&[n]).copy_constraints(0, &pairs)`
+    /// the parameters hold one domain and the statement one commitment, instead of committing
+    /// the same vector as both left and right values of [`crate::public_parameters::PublicParameters`].
+    pub fn copy_constraints(mut self, vector: usize, pairs: &[(usize, usize)]) -> Self {
+        let mut parents: BTreeMap<usize, usize> = BTreeMap::new();
+        pairs.iter().for_each(|&(i, j)| {
+            let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+            parents.insert(ark_std::cmp::max(root_i, root_j), ark_std::cmp::min(root_i, root_j));
+        });
+
+        let mut classes: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        let positions: Vec<usize> = parents.keys().copied().collect();
+        positions.into_iter().for_each(|i| classes.entry(find_root(&mut parents, i)).or_default().push(i));
+        let mappings = classes.values()
+            .flat_map(|class| class.windows(2).map(|pair| (pair[0], pair[1])))
+            .collect();
+        self.edges.push((vector, vector, mappings));
+        self
+    }
+
+    pub fn build<R: Rng + ?Sized>(self, rng: &mut R) -> Result<LinkPublicParameters<P>, Error> {
+        let vector_sizes = self.vector_sizes.ok_or(Error::MissingParameter("Vector Sizes"))?;
+        if let Some(&size) = vector_sizes.iter().find(|size| !size.is_power_of_two()) {
+            return Err(Error::InputShouldBePowerOfTwo(size));
+        }
+        if self.edges.is_empty() {
+            return Err(Error::IndexMappingCannotBeNone);
+        }
+        for (source, target, mappings) in self.edges.iter() {
+            if let Some(&index) = [source, target].into_iter().find(|&&v| v >= vector_sizes.len()) {
+                return Err(Error::InvalidVectorIndex(index));
+            }
+            if mappings.is_empty() {
+                return Err(Error::IndexMappingCannotBeNone);
+            }
+            if let Some((&i, _)) = mappings.iter().find(|(&i, _)| i >= vector_sizes[*source]) {
+                return Err(Error::InputIsTooLarge(i));
+            }
+            if let Some((_, &j)) = mappings.iter().find(|(_, &j)| j >= vector_sizes[*target]) {
+                return Err(Error::InputIsTooLarge(j));
+            }
+        }
+        if let Some(index) = (0..vector_sizes.len()).find(|&v| {
+            !self.edges.iter().any(|(source, target, _)| *source == v || *target == v)
+        }) {
+            return Err(Error::UnlinkedVector(index));
+        }
+
+        let mut domain_sizes = vector_sizes.clone();
+        domain_sizes.sort_unstable();
+        domain_sizes.dedup();
+        let vector_domains: Vec<usize> = vector_sizes.iter()
+            .map(|size| domain_sizes.binary_search(size).unwrap_or_default())
+            .collect();
+
+        let tau = self.tau.unwrap_or(P::ScalarField::rand(rng));
+        let max_size = domain_sizes[domain_sizes.len() - 1];
+        let (g1_affine_srs, g2_affine_srs) = unsafe_setup_from_tau::<P, R>(max_size, tau);
+
+        let mut domains = Vec::with_capacity(domain_sizes.len());
+        let mut domain_cosets = Vec::with_capacity(domain_sizes.len());
+        for &size in domain_sizes.iter() {
+            let domain = Radix2EvaluationDomain::<P::ScalarField>::new(size)
+                .ok_or(Error::FailedToCreateEvaluationDomain)?;
+            let domain_coset = domain.get_coset(P::ScalarField::GENERATOR)
+                .ok_or(Error::FailedToCreateCosetOfEvaluationDomain)?;
+            domains.push(domain);
+            domain_cosets.push(domain_coset);
+        }
+        let roots_of_unity_cosets = domain_cosets.iter().map(roots_of_unity::<P>).collect();
+
+        let fr_zero = P::ScalarField::zero();
+        let fr_one = P::ScalarField::one();
+        let mut target_multiplicities: BTreeMap<usize, BTreeMap<usize, usize>> = BTreeMap::new();
+        let mut edges: Vec<LinkEdge<P>> = Vec::with_capacity(self.edges.len());
+        for (source, target, position_mappings) in self.edges {
+            let domain = &domains[vector_domains[source]];
+            let domain_coset = &domain_cosets[vector_domains[source]];
+            let roots_of_unity_target = roots_of_unity::<P>(&domains[vector_domains[target]]);
+
+            let mut poly_eval_positions = vec![fr_zero; vector_sizes[source]];
+            let mut poly_eval_position_mappings = vec![fr_zero; vector_sizes[source]];
+            let multiplicities = target_multiplicities.entry(target).or_default();
+            position_mappings.iter().for_each(|(&i, &j)| {
+                poly_eval_positions[i] = fr_one;
+                poly_eval_position_mappings[i] = roots_of_unity_target[j];
+                *multiplicities.entry(j).or_default() += 1;
+            });
+            let poly_positions = DensePolynomial::from_coefficients_vec(domain.ifft(&poly_eval_positions));
+            let poly_position_mappings =
+                DensePolynomial::from_coefficients_vec(domain.ifft(&poly_eval_position_mappings));
+
+            edges.push(LinkEdge {
+                source,
+                target,
+                position_mappings,
+                g1_affine_positions: Kzg::<P::G1>::commit(&g1_affine_srs, &poly_positions).into_affine(),
+                g1_affine_position_mappings: Kzg::<P::G1>::commit(&g1_affine_srs, &poly_position_mappings)
+                    .into_affine(),
+                coset_eval_list_positions: domain_coset.fft(&poly_positions),
+                coset_eval_list_position_mappings: domain_coset.fft(&poly_position_mappings),
+                poly_positions,
+                poly_position_mappings,
+            });
+        }
+
+        let targets: Vec<LinkTarget<P>> = target_multiplicities.into_iter()
+            .map(|(vector, multiplicities)| {
+                let domain = &domains[vector_domains[vector]];
+                let domain_coset = &domain_cosets[vector_domains[vector]];
+                let mut poly_eval_multiplicities = vec![fr_zero; vector_sizes[vector]];
+                multiplicities.iter().for_each(|(&j, &count)| {
+                    poly_eval_multiplicities[j] = P::ScalarField::from(count as u64);
+                });
+                let poly_multiplicities =
+                    DensePolynomial::from_coefficients_vec(domain.ifft(&poly_eval_multiplicities));
+
+                LinkTarget {
+                    vector,
+                    multiplicities,
+                    g1_affine_multiplicities: Kzg::<P::G1>::commit(&g1_affine_srs, &poly_multiplicities)
+                        .into_affine(),
+                    coset_eval_list_multiplicities: domain_coset.fft(&poly_multiplicities),
+                    poly_multiplicities,
+                }
+            })
+            .collect();
+
+        let mut pp = LinkPublicParameters {
+            vector_sizes,
+            g1_affine_srs,
+            g2_affine_srs,
+            domain_sizes,
+            domains,
+            domain_cosets,
+            roots_of_unity_cosets,
+            vector_domains,
+            edges,
+            targets,
+            hash_representation: Vec::new(),
+        };
+        pp.hash_representation = pp.compute_hash_representation()?;
+
+        Ok(pp)
+    }
+}
+
+/// Returns the smallest position of the class of `i`, where each position points to a smaller
+/// one of its class or to itself.
+fn find_root(parents: &mut BTreeMap<usize, usize>, i: usize) -> usize {
+    let mut root = i;
+    while let Some(&parent) = parents.get(&root).filter(|&&parent| parent != root) {
+        root = parent;
+    }
+    parents.insert(i, root);
+
+    root
+}
@@ -0,0 +1,83 @@
+use crate::error::Error;
+use crate::kzg::Kzg;
+use crate::link::public_parameters::LinkPublicParameters;
+use crate::link::witness::LinkWitness;
+use crate::COMPRESS_MOD;
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::io::{Read, Write};
+use ark_std::vec::Vec;
+use blake2::{Blake2b512, Digest};
+
+/// One commitment per linked vector.
+#[derive(Debug)]
+pub struct LinkStatement<P: Pairing> {
+    pub(crate) g1_affine_values: Vec<P::G1Affine>,
+    pub(crate) hash_representation: Vec<u8>,
+}
+
+impl<P: Pairing> LinkStatement<P> {
+    fn from_commitments(g1_affine_values: Vec<P::G1Affine>) -> Result<Self, Error> {
+        let mut buf = Vec::new();
+        g1_affine_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e| Error::FailedToSerializeElement("g1_affine_values", e))?;
+        let mut hasher = Blake2b512::new();
+        hasher.update(&buf);
+        let hash_representation = hasher.finalize().to_vec();
+
+        Ok(LinkStatement {
+            g1_affine_values,
+            hash_representation,
+        })
+    }
+
+    pub fn num_vectors(&self) -> usize {
+        self.g1_affine_values.len()
+    }
+}
+
+impl<P: Pairing> LinkWitness<P> {
+    pub fn generate_statement(&self, pp: &LinkPublicParameters<P>) -> Result<LinkStatement<P>, Error> {
+        let g1_affine_values = self.poly_values.iter()
+            .map(|poly| Kzg::<P::G1>::commit(&pp.g1_affine_srs, poly).into_affine())
+            .collect();
+
+        LinkStatement::from_commitments(g1_affine_values)
+    }
+}
+
+// As for the two-vector statement, only the commitments are serialized.
+impl<P: Pairing> CanonicalSerialize for LinkStatement<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.g1_affine_values.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.g1_affine_values.serialized_size(compress)
+    }
+}
+
+impl<P: Pairing> Valid for LinkStatement<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.g1_affine_values.check()
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for LinkStatement<P> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let g1_affine_values = Vec::deserialize_with_mode(reader, compress, validate)?;
+
+        LinkStatement::from_commitments(g1_affine_values)
+            .map_err(|_| SerializationError::InvalidData)
+    }
+}
@@ -0,0 +1,145 @@
+use crate::error::Error;
+use crate::kzg::{multi_point_batch_verify, powers_of_scalars};
+use crate::link::prover::LinkProof;
+use crate::link::public_parameters::LinkPublicParameters;
+use crate::link::statement::LinkStatement;
+use crate::transcript::{Label, Transcript};
+use ark_ec::pairing::Pairing;
+use ark_ff::Field;
+use ark_std::{vec, vec::Vec, One, Zero};
+
+pub fn verify<P: Pairing>(
+    pp: &LinkPublicParameters<P>,
+    statement: &LinkStatement<P>,
+    proof: &LinkProof<P>,
+) -> Result<(), Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    if statement.num_vectors() != pp.num_vectors() {
+        return Err(Error::WrongNumberOfVectors(statement.num_vectors()));
+    }
+    check_shape(pp, proof)?;
+
+    // Sample random beta, gamma.
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    transcript.append_elements(&[(Label::G1L, proof.g1_affine_l.as_slice()), (Label::G1R, proof.g1_affine_r.as_slice())])?;
+
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+    let powers_of_eta = powers_of_scalars(eta, pp.edges.len() + pp.targets.len());
+
+    transcript.append_element(Label::G1Q, &proof.g1_affine_q)?;
+
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    transcript.append_elements(
+        &[
+            (Label::FrLAtDelta, proof.l_at_delta.as_slice()),
+            (Label::FrRAtDelta, proof.r_at_delta.as_slice()),
+            (Label::FrVAtDelta, proof.v_at_delta.as_slice()),
+            (Label::FrPlAtDelta, proof.p_at_delta.as_slice()),
+            (Label::FrPmAtDelta, proof.m_at_delta.as_slice()),
+            (Label::FrCAtDelta, proof.c_at_delta.as_slice()),
+            (Label::FrLAtZero, proof.l_at_zero.as_slice()),
+            (Label::FrRAtZero, proof.r_at_zero.as_slice()),
+        ]
+    )?;
+
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    // Combine the identities over each domain as the prover did, and divide by the vanishing
+    // polynomial of the domain.
+    let mut q_at_delta = vec![P::ScalarField::zero(); pp.domains.len()];
+    for (e, edge) in pp.edges.iter().enumerate() {
+        let identity = proof.l_at_delta[e] * (beta + proof.v_at_delta[edge.source] + gamma * proof.m_at_delta[e])
+            - proof.p_at_delta[e];
+        q_at_delta[pp.vector_domains[edge.source]] += powers_of_eta[e] * identity;
+    }
+    for (t, target) in pp.targets.iter().enumerate() {
+        let identity = proof.r_at_delta[t] * (beta + proof.v_at_delta[target.vector] + gamma * delta)
+            - proof.c_at_delta[t];
+        q_at_delta[pp.vector_domains[target.vector]] += powers_of_eta[pp.edges.len() + t] * identity;
+    }
+    for (q, &size) in q_at_delta.iter_mut().zip(pp.domain_sizes.iter()) {
+        let fr_z_at_delta = delta.pow([size as u64]) - P::ScalarField::one();
+        *q *= fr_z_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    }
+
+    // Pairing check of the batch proof at delta and zero.
+    let g1_list_at_delta: Vec<P::G1Affine> = [
+        &proof.g1_affine_l,
+        &proof.g1_affine_r,
+        &proof.g1_affine_q,
+        &statement.g1_affine_values,
+    ].into_iter().flatten().copied()
+        .chain(pp.edges.iter().map(|edge| edge.g1_affine_positions))
+        .chain(pp.edges.iter().map(|edge| edge.g1_affine_position_mappings))
+        .chain(pp.targets.iter().map(|target| target.g1_affine_multiplicities))
+        .collect();
+    let fr_list_at_delta: Vec<P::ScalarField> = [
+        &proof.l_at_delta,
+        &proof.r_at_delta,
+        &q_at_delta,
+        &proof.v_at_delta,
+        &proof.p_at_delta,
+        &proof.m_at_delta,
+        &proof.c_at_delta,
+    ].into_iter().flatten().copied().collect();
+    let g1_list_at_zero: Vec<P::G1Affine> = [&proof.g1_affine_l, &proof.g1_affine_r]
+        .into_iter().flatten().copied().collect();
+    let fr_list_at_zero: Vec<P::ScalarField> = [&proof.l_at_zero, &proof.r_at_zero]
+        .into_iter().flatten().copied().collect();
+
+    if !multi_point_batch_verify::<P>(
+        pp.g1_affine_srs[0],
+        &pp.g2_affine_srs,
+        &[&g1_list_at_delta, &g1_list_at_zero],
+        &[&fr_list_at_delta, &fr_list_at_zero],
+        &[delta, P::ScalarField::zero()],
+        epsilon,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("batch opening at delta and zero"));
+    }
+
+    // Sumcheck Lemma, once per target: the edges into it sum to its own sum.
+    for (t, target) in pp.targets.iter().enumerate() {
+        let fr_sum_l: P::ScalarField = pp.edges.iter().enumerate()
+            .filter(|(_, edge)| edge.target == target.vector)
+            .map(|(e, edge)| proof.l_at_zero[e] * P::ScalarField::from(pp.vector_sizes[edge.source] as u64))
+            .sum();
+        let fr_sum_r = proof.r_at_zero[t] * P::ScalarField::from(pp.vector_sizes[target.vector] as u64);
+        if fr_sum_l != fr_sum_r {
+            return Err(Error::EqualityCheckFailed("sumcheck of edges into a target"));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_shape<P: Pairing>(pp: &LinkPublicParameters<P>, proof: &LinkProof<P>) -> Result<(), Error> {
+    let num_edges = pp.edges.len();
+    let num_targets = pp.targets.len();
+    let shapes = [
+        ("g1_affine_l", proof.g1_affine_l.len(), num_edges),
+        ("g1_affine_r", proof.g1_affine_r.len(), num_targets),
+        ("g1_affine_q", proof.g1_affine_q.len(), pp.domains.len()),
+        ("l_at_delta", proof.l_at_delta.len(), num_edges),
+        ("r_at_delta", proof.r_at_delta.len(), num_targets),
+        ("v_at_delta", proof.v_at_delta.len(), pp.num_vectors()),
+        ("p_at_delta", proof.p_at_delta.len(), num_edges),
+        ("m_at_delta", proof.m_at_delta.len(), num_edges),
+        ("c_at_delta", proof.c_at_delta.len(), num_targets),
+        ("l_at_zero", proof.l_at_zero.len(), num_edges),
+        ("r_at_zero", proof.r_at_zero.len(), num_targets),
+    ];
+    match shapes.iter().find(|(_, len, expected)| len != expected) {
+        Some((name, _, _)) => Err(Error::MalformedProof(name)),
+        None => Ok(()),
+    }
+}
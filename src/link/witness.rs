@@ -0,0 +1,35 @@
+use crate::error::Error;
+use crate::link::public_parameters::LinkPublicParameters;
+use ark_ec::pairing::Pairing;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain};
+use ark_std::vec::Vec;
+
+pub struct LinkWitness<P: Pairing> {
+    pub(crate) values: Vec<Vec<P::ScalarField>>,
+    pub(crate) poly_values: Vec<DensePolynomial<P::ScalarField>>,
+}
+
+impl<P: Pairing> LinkWitness<P> {
+    /// Creates a witness from one vector of values per vector of the public parameters.
+    pub fn new(pp: &LinkPublicParameters<P>, vectors: &[&[P::ScalarField]]) -> Result<Self, Error> {
+        if vectors.len() != pp.num_vectors() {
+            return Err(Error::WrongNumberOfVectors(vectors.len()));
+        }
+
+        if let Some(vector) = vectors.iter().zip(pp.vector_sizes.iter()).find(|(v, &size)| v.len() != size) {
+            return Err(Error::WrongNumberOfValues(vector.0.len()));
+        }
+
+        let poly_values = vectors
+            .iter()
+            .zip(pp.vector_domains.iter())
+            .map(|(vector, &d)| DensePolynomial::from_coefficients_vec(pp.domains[d].ifft(vector)))
+            .collect();
+
+        Ok(Self {
+            values: vectors.iter().map(|vector| vector.to_vec()).collect(),
+            poly_values,
+        })
+    }
+}
@@ -0,0 +1,159 @@
+//! Proves that the left values at `positions_left` appear somewhere in the right vector.
+//!
+//! Unlike [`crate::prover::prove`], no right position is fixed: the prover commits to the
+//! multiplicity `m_j` of each right position, and the argument shows
+//! `sum_{i in positions_left} 1 / (beta + v_i) = sum_j m_j / (beta + w_j)`. Only
+//! `positions_left` of the public parameters is used, which
+//! [`crate::public_parameters::PublicParametersBuilder::lookup_positions`] sets on its own.
+
+use crate::error::Error;
+use crate::kzg::{powers_of_scalars, Kzg};
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::sumcheck::{self, Half, SumcheckProof};
+use crate::transcript::{Label, Transcript};
+use crate::witness::{fold_columns, Witness};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::One;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::collections::BTreeMap;
+use ark_std::{vec, vec::Vec, Zero};
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LookupProof<P: Pairing> {
+    pub(crate) g1_affine_m: P::G1Affine,
+    pub(crate) sumcheck: SumcheckProof<P>,
+}
+
+pub fn prove<P: Pairing>(
+    pp: &PublicParameters<P>,
+    witness: &Witness<P>,
+    statement: &Statement<P>,
+) -> Result<LookupProof<P>, Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    // Count how often each right tuple is looked up, crediting the first position holding it.
+    let tuple_at = |columns: &[Vec<P::ScalarField>], i: usize| -> Vec<P::ScalarField> {
+        columns.iter().map(|column| column[i]).collect()
+    };
+    let mut right_positions = BTreeMap::new();
+    for j in (0..pp.size_right_values).rev() {
+        right_positions.insert(tuple_at(&witness.right_values, j), j);
+    }
+    let mut multiplicities = BTreeMap::<usize, u64>::new();
+    for &i in pp.positions_left.iter() {
+        let j = right_positions.get(&tuple_at(&witness.left_values, i)).ok_or(Error::ValueNotFound(i))?;
+        *multiplicities.entry(*j).or_insert(0) += 1;
+    }
+    let mut poly_eval_multiplicities = vec![P::ScalarField::zero(); pp.size_right_values];
+    multiplicities.iter().for_each(|(&j, &count)| poly_eval_multiplicities[j] = P::ScalarField::from(count));
+    let poly_multiplicities = DensePolynomial::from_coefficients_vec(pp.domain_r.ifft(&poly_eval_multiplicities));
+    let g1_affine_m = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_multiplicities).into_affine();
+
+    transcript.append_element(Label::G1M, &g1_affine_m)?;
+
+    // Sample random alpha, beta.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+
+    let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+    let (left_values, poly_left_values) =
+        fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+    let (right_values, poly_right_values) =
+        fold_columns(&witness.right_values, &witness.poly_right_values, &powers_of_alpha);
+
+    // The left half sums `1 / (beta + v_i)` over the selected positions, the right half
+    // `m_j / (beta + w_j)` over the looked up positions.
+    let fr_one = P::ScalarField::one();
+    let coset_eval_list_left_values = pp.domain_coset_l.fft(&poly_left_values);
+    let left = Half {
+        domain: &pp.domain_l,
+        domain_coset: &pp.domain_coset_l,
+        fraction_list: pp.positions_left.iter().map(|&i| (i, fr_one, beta + left_values[i])).collect(),
+        numerator: |k: usize| pp.coset_eval_list_positions_left[k],
+        denominator: |k: usize| beta + coset_eval_list_left_values[k],
+    };
+    let coset_eval_list_right_values = pp.domain_coset_r.fft(&poly_right_values);
+    let coset_eval_list_multiplicities = pp.domain_coset_r.fft(&poly_multiplicities);
+    let right = Half {
+        domain: &pp.domain_r,
+        domain_coset: &pp.domain_coset_r,
+        fraction_list: multiplicities.iter()
+            .map(|(&j, &count)| (j, P::ScalarField::from(count), beta + right_values[j]))
+            .collect(),
+        numerator: |k: usize| coset_eval_list_multiplicities[k],
+        denominator: |k: usize| beta + coset_eval_list_right_values[k],
+    };
+
+    let sumcheck = sumcheck::prove(
+        &mut transcript,
+        &pp.g1_affine_srs,
+        left,
+        right,
+        &[
+            (Label::FrLvAtDelta, &poly_left_values),
+            (Label::FrRvAtDelta, &poly_right_values),
+            (Label::FrPlAtDelta, &pp.poly_positions_left),
+            (Label::FrMAtDelta, &poly_multiplicities),
+        ],
+    )?;
+
+    Ok(LookupProof { g1_affine_m, sumcheck })
+}
+
+pub fn verify<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &Statement<P>,
+    proof: &LookupProof<P>,
+) -> Result<(), Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    if statement.g1_affine_left_values.is_empty()
+        || statement.g1_affine_left_values.len() != statement.g1_affine_right_values.len()
+    {
+        return Err(Error::WrongNumberOfColumns(statement.g1_affine_right_values.len()));
+    }
+    let &[lv_at_delta, rv_at_delta, pl_at_delta, m_at_delta] = &proof.sumcheck.opened_at_delta[..] else {
+        return Err(Error::MalformedProof("evaluations at delta"));
+    };
+
+    transcript.append_element(Label::G1M, &proof.g1_affine_m)?;
+
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+
+    // Fold the column commitments as the prover folded the columns.
+    let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+    let g1_affine_left_values = P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha)
+        .into_affine();
+    let g1_affine_right_values = P::G1::msm_unchecked(&statement.g1_affine_right_values, &powers_of_alpha)
+        .into_affine();
+
+    sumcheck::verify(
+        &mut transcript,
+        &pp.g1_affine_srs,
+        &pp.g2_affine_srs,
+        pp.size_left_values,
+        pp.size_right_values,
+        &proof.sumcheck,
+        &[
+            (Label::FrLvAtDelta, g1_affine_left_values),
+            (Label::FrRvAtDelta, g1_affine_right_values),
+            (Label::FrPlAtDelta, pp.g1_affine_positions_left),
+            (Label::FrMAtDelta, proof.g1_affine_m),
+        ],
+        (pl_at_delta, beta + lv_at_delta),
+        (m_at_delta, beta + rv_at_delta),
+    )
+}
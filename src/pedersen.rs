@@ -0,0 +1,233 @@
+//! Links left values of a statement to external Pedersen commitments `v * G + r * H`.
+//!
+//! For a batch of left positions `S`, the prover opens the column polynomial `f` at the points
+//! of `S` with the quotient `q = (f - I) / Z_S`, where `I` interpolates the values, and blinds
+//! it as `q' = q + s * [1]`. Then
+//! `e(C_f, [1]) - e(q', [Z_S(tau)]) = e([I(tau)], [1]) - s * e([1], [Z_S(tau)])`,
+//! which is linear in the values and `s`. A Σ-protocol, with its challenge from the same
+//! transcript, proves knowledge of values, Pedersen randomness and `s` satisfying this
+//! equation and opening every Pedersen commitment, without revealing the values.
+
+use crate::error::Error;
+use crate::kzg::{g2_commit, interpolate, Kzg};
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::transcript::{Label, Transcript};
+use crate::witness::Witness;
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_poly::EvaluationDomain;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::Rng;
+use ark_std::{vec::Vec, UniformRand};
+
+/// Generators of external Pedersen commitments.
+#[derive(Debug, Clone, Copy, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenGenerators<P: Pairing> {
+    pub g: P::G1Affine,
+    pub h: P::G1Affine,
+}
+
+impl<P: Pairing> PedersenGenerators<P> {
+    pub fn commit(&self, value: P::ScalarField, randomness: P::ScalarField) -> P::G1Affine {
+        (self.g * value + self.h * randomness).into_affine()
+    }
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenLinkProof<P: Pairing> {
+    pub(crate) g1_affine_q: P::G1Affine,
+    pub(crate) g1_affine_t: Vec<P::G1Affine>,
+    pub(crate) gt_t: PairingOutput<P>,
+    pub(crate) fr_z_values: Vec<P::ScalarField>,
+    pub(crate) fr_z_randomness: Vec<P::ScalarField>,
+    pub(crate) fr_z_blinder: P::ScalarField,
+}
+
+/// Proves that the value of left column `column` at each position of `openings` is the value
+/// of the Pedersen commitment made with the paired randomness.
+///
+/// `openings` pairs distinct left positions with their randomness, and the proof follows
+/// the positions in ascending order.
+pub fn prove_link<P: Pairing, R: Rng + ?Sized>(
+    pp: &PublicParameters<P>,
+    witness: &Witness<P>,
+    statement: &Statement<P>,
+    generators: &PedersenGenerators<P>,
+    column: usize,
+    openings: &[(usize, P::ScalarField)],
+    rng: &mut R,
+) -> Result<PedersenLinkProof<P>, Error> {
+    if column >= witness.num_columns() {
+        return Err(Error::InputIsTooLarge(column));
+    }
+    let mut openings = openings.to_vec();
+    openings.sort_unstable_by_key(|&(i, _)| i);
+    let indices: Vec<usize> = openings.iter().map(|&(i, _)| i).collect();
+    check_indices(pp, &indices)?;
+
+    let poly = &witness.poly_left_values[column];
+    let fr_points: Vec<P::ScalarField> = indices.iter().map(|&i| pp.domain_l.element(i)).collect();
+    let (values, g1_affine_q) = Kzg::<P::G1>::open_at_points(&pp.g1_affine_srs, poly, &fr_points);
+    let commitments: Vec<P::G1Affine> = values.iter().zip(openings.iter())
+        .map(|(&v, &(_, r))| generators.commit(v, r))
+        .collect();
+
+    let g1_generator = pp.g1_affine_srs[0];
+    let fr_blinder = P::ScalarField::rand(rng);
+    let g1_affine_q = (g1_affine_q + g1_generator * fr_blinder).into_affine();
+
+    // Commitments of the Σ-protocol.
+    let fr_mask_values: Vec<P::ScalarField> = (0..indices.len()).map(|_| P::ScalarField::rand(rng)).collect();
+    let fr_mask_randomness: Vec<P::ScalarField> = (0..indices.len()).map(|_| P::ScalarField::rand(rng)).collect();
+    let fr_mask_blinder = P::ScalarField::rand(rng);
+    let g1_affine_t: Vec<P::G1Affine> = fr_mask_values.iter().zip(fr_mask_randomness.iter())
+        .map(|(&a, &b)| generators.commit(a, b))
+        .collect();
+    let g2_vanishing = g2_commit::<P>(&pp.g2_affine_srs, &fr_points, None);
+    let g1_mask_interpolation = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &interpolate(&fr_points, &fr_mask_values));
+    let gt_t = P::multi_pairing(
+        P::G1::normalize_batch(&[g1_mask_interpolation, -(g1_generator * fr_mask_blinder)]),
+        P::G2::normalize_batch(&[pp.g2_affine_srs[0].into_group(), g2_vanishing]),
+    );
+
+    let challenge = link_challenge(
+        pp,
+        statement.hash_representation.clone(),
+        generators,
+        column,
+        &indices,
+        &commitments,
+        g1_affine_q,
+        &g1_affine_t,
+        &gt_t,
+    )?;
+
+    let fr_z_values = fr_mask_values.iter().zip(values.iter())
+        .map(|(&a, &v)| a + challenge * v)
+        .collect();
+    let fr_z_randomness = fr_mask_randomness.iter().zip(openings.iter())
+        .map(|(&b, &(_, r))| b + challenge * r)
+        .collect();
+
+    Ok(PedersenLinkProof {
+        g1_affine_q,
+        g1_affine_t,
+        gt_t,
+        fr_z_values,
+        fr_z_randomness,
+        fr_z_blinder: fr_mask_blinder + challenge * fr_blinder,
+    })
+}
+
+/// Checks a proof of [`prove_link`] against the left commitment of column `column` and the
+/// Pedersen commitments at their positions.
+pub fn verify_link<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &Statement<P>,
+    generators: &PedersenGenerators<P>,
+    column: usize,
+    commitments: &[(usize, P::G1Affine)],
+    proof: &PedersenLinkProof<P>,
+) -> Result<(), Error> {
+    if column >= statement.num_columns() {
+        return Err(Error::InputIsTooLarge(column));
+    }
+    let mut commitments = commitments.to_vec();
+    commitments.sort_unstable_by_key(|&(i, _)| i);
+    let indices: Vec<usize> = commitments.iter().map(|&(i, _)| i).collect();
+    check_indices(pp, &indices)?;
+    if [proof.g1_affine_t.len(), proof.fr_z_values.len(), proof.fr_z_randomness.len()]
+        .iter()
+        .any(|&len| len != indices.len())
+    {
+        return Err(Error::MalformedProof("responses"));
+    }
+    let commitments: Vec<P::G1Affine> = commitments.iter().map(|&(_, c)| c).collect();
+
+    let challenge = link_challenge(
+        pp,
+        statement.hash_representation.clone(),
+        generators,
+        column,
+        &indices,
+        &commitments,
+        proof.g1_affine_q,
+        &proof.g1_affine_t,
+        &proof.gt_t,
+    )?;
+
+    // Each Pedersen commitment is opened by the responses.
+    for ((&commitment, &g1_affine_t), (&z_v, &z_r)) in commitments.iter()
+        .zip(proof.g1_affine_t.iter())
+        .zip(proof.fr_z_values.iter().zip(proof.fr_z_randomness.iter()))
+    {
+        if generators.commit(z_v, z_r) != (g1_affine_t + commitment * challenge).into_affine() {
+            return Err(Error::EqualityCheckFailed("opening of a pedersen commitment"));
+        }
+    }
+
+    // The responses satisfy the opening equation of the left commitment at the positions.
+    let g1_generator = pp.g1_affine_srs[0];
+    let fr_points: Vec<P::ScalarField> = indices.iter().map(|&i| pp.domain_l.element(i)).collect();
+    let g2_vanishing = g2_commit::<P>(&pp.g2_affine_srs, &fr_points, None);
+    let g1_interpolation = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &interpolate(&fr_points, &proof.fr_z_values));
+    let g1_affine_left_values = statement.g1_affine_left_values[column];
+    let gt_lhs = P::multi_pairing(
+        P::G1::normalize_batch(&[
+            g1_interpolation - g1_affine_left_values * challenge,
+            proof.g1_affine_q * challenge - g1_generator * proof.fr_z_blinder,
+        ]),
+        P::G2::normalize_batch(&[pp.g2_affine_srs[0].into_group(), g2_vanishing]),
+    );
+    if gt_lhs != proof.gt_t {
+        return Err(Error::PairingCheckFailed("opening of the left commitment at the linked positions"));
+    }
+
+    Ok(())
+}
+
+fn check_indices<P: Pairing>(pp: &PublicParameters<P>, indices: &[usize]) -> Result<(), Error> {
+    if indices.is_empty() {
+        return Err(Error::LeftIndicesCannotBeNone);
+    }
+    if indices.windows(2).any(|w| w[0] == w[1]) {
+        return Err(Error::MalformedProof("indices"));
+    }
+    if let Some(&i) = indices.iter().find(|&&i| i >= pp.size_left_values) {
+        return Err(Error::InputIsTooLarge(i));
+    }
+    if indices.len() + 1 > pp.g2_affine_srs.len() {
+        return Err(Error::InputIsTooLarge(indices.len()));
+    }
+
+    Ok(())
+}
+
+/// Binds the parameters, the statement, the Pedersen commitments and the first message of the
+/// Σ-protocol before squeezing its challenge.
+#[allow(clippy::too_many_arguments)]
+fn link_challenge<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement_hash: Vec<u8>,
+    generators: &PedersenGenerators<P>,
+    column: usize,
+    indices: &[usize],
+    commitments: &[P::G1Affine],
+    g1_affine_q: P::G1Affine,
+    g1_affine_t: &[P::G1Affine],
+    gt_t: &PairingOutput<P>,
+) -> Result<P::ScalarField, Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement_hash),
+    ])?;
+    transcript.append_element(Label::PedersenGenerators, generators)?;
+    transcript.append_element(Label::DisclosedIndices, &(column, indices.to_vec()))?;
+    transcript.append_element(Label::PedersenCommitments, &commitments.to_vec())?;
+    transcript.append_element(Label::G1Q, &g1_affine_q)?;
+    transcript.append_element(Label::SigmaCommitments, &(g1_affine_t.to_vec(), *gt_t))?;
+
+    transcript.squeeze_challenge(Label::ChallengeSigma)
+}
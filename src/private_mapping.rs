@@ -0,0 +1,999 @@
+//! Proves the relation for a mapping that only the prover knows.
+//!
+//! The selector and mapping polynomials are committed by the prover instead of the setup. They
+//! are blinded with `B(X) * Z_H(X)`, where `Z_H` is the vanishing polynomial of their domain and
+//! `B` is random of degree equal to the number of times they are opened: once by
+//! [`PrivateMapping::prove_well_formed`] and once by each of at most
+//! [`PrivateParameters::max_proofs`] proofs. The commitment and all openings together then fix
+//! no more than the coefficients of `B` would, so the [`MappingCommitment`] and the proofs do
+//! not reveal which positions are linked.
+//!
+//! [`PrivateMapping::prove_well_formed`] shows once that the committed mapping is well formed:
+//! both selectors are boolean over their domains, and the mapping values at the selected left
+//! positions are exactly the selected elements of the right domain. After
+//! [`verify_mapping`] has accepted a commitment, [`verify`] checks proofs against it.
+
+use crate::error::Error;
+use crate::kzg::{multi_point_batch_verify, powers_of_scalars, unsafe_setup_from_tau, Kzg};
+use crate::prover::{fractional_poly, quotient_poly};
+use crate::statement::Statement;
+use crate::transcript::{Label, Transcript};
+use crate::witness::{fold_columns, Witness};
+use crate::COMPRESS_MOD;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{FftField, Field, One};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Polynomial, Radix2EvaluationDomain};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::cell::Cell;
+use ark_std::cmp::max;
+use ark_std::collections::BTreeMap;
+use ark_std::io::{Read, Write};
+use ark_std::rand::Rng;
+use ark_std::{cfg_iter, vec, vec::Vec, UniformRand, Zero};
+use blake2::{Blake2b512, Digest};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Public parameters without a mapping.
+#[derive(Debug)]
+pub struct PrivateParameters<P: Pairing> {
+    pub size_left_values: usize,
+    pub size_right_values: usize,
+    /// Number of proofs of [`prove`] that one [`PrivateMapping`] can produce before its
+    /// blinding is used up.
+    pub max_proofs: usize,
+
+    pub g1_affine_srs: Vec<P::G1Affine>,
+    pub g2_affine_srs: Vec<P::G2Affine>,
+
+    pub domain_l: Radix2EvaluationDomain<P::ScalarField>,
+    pub domain_r: Radix2EvaluationDomain<P::ScalarField>,
+    /// Cosets of at least `2n + max_openings() + 3` elements, as the blinded halves of degree
+    /// `n + 2` are multiplied by the blinded polynomials of degree `n + max_openings()`.
+    pub domain_coset_l: Radix2EvaluationDomain<P::ScalarField>,
+    pub domain_coset_r: Radix2EvaluationDomain<P::ScalarField>,
+    pub roots_of_unity_coset_r: Vec<P::ScalarField>,
+
+    pub(crate) hash_representation: Vec<u8>,
+}
+
+impl<P: Pairing> PrivateParameters<P> {
+    pub fn builder() -> PrivateParametersBuilder<P> {
+        PrivateParametersBuilder::<P>::default()
+    }
+
+    /// How often the selector and mapping polynomials of a mapping are opened at most: once for
+    /// the well-formedness proof and once per proof. It is also the degree of their blinders.
+    pub fn max_openings(&self) -> usize {
+        self.max_proofs + 1
+    }
+
+    fn compute_hash_representation(&self) -> Result<Vec<u8>, Error> {
+        let mut buf = Vec::new();
+        self.size_left_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("size_left_values", e))?;
+        self.size_right_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("size_right_values", e))?;
+        self.max_proofs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("max_proofs", e))?;
+        self.g1_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_srs", e))?;
+        self.g2_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g2_affine_srs", e))?;
+
+        Ok(Blake2b512::digest(&buf).to_vec())
+    }
+}
+
+// As for [`crate::public_parameters::PublicParameters`], the hash representation is recomputed
+// on deserialization instead of being read.
+impl<P: Pairing> CanonicalSerialize for PrivateParameters<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.size_left_values.serialize_with_mode(&mut writer, compress)?;
+        self.size_right_values.serialize_with_mode(&mut writer, compress)?;
+        self.max_proofs.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_srs.serialize_with_mode(&mut writer, compress)?;
+        self.g2_affine_srs.serialize_with_mode(&mut writer, compress)?;
+        self.domain_l.serialize_with_mode(&mut writer, compress)?;
+        self.domain_r.serialize_with_mode(&mut writer, compress)?;
+        self.domain_coset_l.serialize_with_mode(&mut writer, compress)?;
+        self.domain_coset_r.serialize_with_mode(&mut writer, compress)?;
+        self.roots_of_unity_coset_r.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.size_left_values.serialized_size(compress)
+            + self.size_right_values.serialized_size(compress)
+            + self.max_proofs.serialized_size(compress)
+            + self.g1_affine_srs.serialized_size(compress)
+            + self.g2_affine_srs.serialized_size(compress)
+            + self.domain_l.serialized_size(compress)
+            + self.domain_r.serialized_size(compress)
+            + self.domain_coset_l.serialized_size(compress)
+            + self.domain_coset_r.serialized_size(compress)
+            + self.roots_of_unity_coset_r.serialized_size(compress)
+    }
+}
+
+impl<P: Pairing> Valid for PrivateParameters<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.g1_affine_srs.check()?;
+        self.g2_affine_srs.check()?;
+        self.domain_l.check()?;
+        self.domain_r.check()?;
+        self.domain_coset_l.check()?;
+        self.domain_coset_r.check()?;
+        self.roots_of_unity_coset_r.check()
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for PrivateParameters<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut pp = PrivateParameters {
+            size_left_values: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            size_right_values: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            max_proofs: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            g1_affine_srs: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            g2_affine_srs: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_l: Radix2EvaluationDomain::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_r: Radix2EvaluationDomain::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_coset_l: Radix2EvaluationDomain::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_coset_r: Radix2EvaluationDomain::deserialize_with_mode(&mut reader, compress, validate)?,
+            roots_of_unity_coset_r: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            hash_representation: Vec::new(),
+        };
+        pp.hash_representation = pp.compute_hash_representation()
+            .map_err(|_| SerializationError::InvalidData)?;
+
+        Ok(pp)
+    }
+}
+
+pub struct PrivateParametersBuilder<P: Pairing> {
+    size_left_values: Option<usize>,
+    size_right_values: Option<usize>,
+    max_proofs: usize,
+    tau: Option<P::ScalarField>,
+}
+
+impl<P: Pairing> PrivateParametersBuilder<P> {
+    fn default() -> Self {
+        Self {
+            size_left_values: None,
+            size_right_values: None,
+            max_proofs: 1,
+            tau: None,
+        }
+    }
+
+    pub fn size_left_values(mut self, size: usize) -> Self {
+        self.size_left_values = Some(size);
+        self
+    }
+
+    pub fn size_right_values(mut self, size: usize) -> Self {
+        self.size_right_values = Some(size);
+        self
+    }
+
+    /// Sets how many proofs one mapping may produce, one by default. Each more proof raises
+    /// the degree of the blinders by one and the SRS by two powers.
+    pub fn max_proofs(mut self, max_proofs: usize) -> Self {
+        self.max_proofs = max_proofs;
+        self
+    }
+
+    pub fn tau(mut self, tau: P::ScalarField) -> Self {
+        self.tau = Some(tau);
+        self
+    }
+
+    pub fn build<R: Rng + ?Sized>(self, rng: &mut R) -> Result<PrivateParameters<P>, Error> {
+        let size_left_values = self.size_left_values.ok_or(Error::MissingParameter("Left \
+        Element Size"))?;
+        let size_right_values = self.size_right_values.ok_or(Error::MissingParameter("Right \
+        Element Size"))?;
+        if let Some(&size) = [size_left_values, size_right_values].iter().find(|size| !size.is_power_of_two()) {
+            return Err(Error::InputShouldBePowerOfTwo(size));
+        }
+
+        // With `t = max_openings`, the blinded selectors have degree `n + t`, so the booleanity
+        // quotients of the well-formedness proof have degree `n + 2t`. The blinded halves have
+        // degree `n + 2`, so their products with the mapping polynomial have degree `2n + t + 2`.
+        let max_openings = self.max_proofs + 1;
+        let tau = self.tau.unwrap_or(P::ScalarField::rand(rng));
+        let (g1_affine_srs, g2_affine_srs) = unsafe_setup_from_tau::<P, R>(
+            max(size_left_values, size_right_values) + 2 * max_openings + 2,
+            tau,
+        );
+
+        let domain_l = Radix2EvaluationDomain::<P::ScalarField>::new(size_left_values)
+            .ok_or(Error::FailedToCreateEvaluationDomain)?;
+        let domain_r = Radix2EvaluationDomain::<P::ScalarField>::new(size_right_values)
+            .ok_or(Error::FailedToCreateEvaluationDomain)?;
+        let domain_coset_l = coset_of_size::<P::ScalarField>(2 * size_left_values + max_openings + 3)?;
+        let domain_coset_r = coset_of_size::<P::ScalarField>(2 * size_right_values + max_openings + 3)?;
+        let roots_of_unity_coset_r = domain_coset_r.elements().collect();
+
+        let mut pp = PrivateParameters {
+            size_left_values,
+            size_right_values,
+            max_proofs: self.max_proofs,
+            g1_affine_srs,
+            g2_affine_srs,
+            domain_l,
+            domain_r,
+            domain_coset_l,
+            domain_coset_r,
+            roots_of_unity_coset_r,
+            hash_representation: Vec::new(),
+        };
+        pp.hash_representation = pp.compute_hash_representation()?;
+
+        Ok(pp)
+    }
+}
+
+/// A coset of the smallest power-of-two domain with at least `size` elements.
+fn coset_of_size<F: FftField>(size: usize) -> Result<Radix2EvaluationDomain<F>, Error> {
+    Radix2EvaluationDomain::<F>::new(size)
+        .ok_or(Error::FailedToCreateEvaluationDomain)?
+        .get_coset(F::GENERATOR)
+        .ok_or(Error::FailedToCreateCosetOfEvaluationDomain)
+}
+
+/// The commitments to the blinded selector and mapping polynomials of a [`PrivateMapping`].
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MappingCommitment<P: Pairing> {
+    pub g1_affine_positions_left: P::G1Affine,
+    pub g1_affine_positions_right: P::G1Affine,
+    pub g1_affine_position_mappings: P::G1Affine,
+}
+
+impl<P: Pairing> MappingCommitment<P> {
+    /// The hash that parties agree on in place of the mapping.
+    pub fn hash_representation(&self) -> Result<Vec<u8>, Error> {
+        let mut buf = Vec::new();
+        self.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("mapping_commitment", e))?;
+
+        Ok(Blake2b512::digest(&buf).to_vec())
+    }
+}
+
+/// A mapping known only to the prover.
+pub struct PrivateMapping<P: Pairing> {
+    position_mappings: BTreeMap<usize, usize>,
+    poly_positions_left: DensePolynomial<P::ScalarField>,
+    poly_positions_right: DensePolynomial<P::ScalarField>,
+    poly_position_mappings: DensePolynomial<P::ScalarField>,
+    coset_eval_list_positions_left: Vec<P::ScalarField>,
+    coset_eval_list_positions_right: Vec<P::ScalarField>,
+    coset_eval_list_position_mappings: Vec<P::ScalarField>,
+    commitment: MappingCommitment<P>,
+    /// Openings left before the blinders no longer hide the mapping.
+    remaining_openings: Cell<usize>,
+}
+
+impl<P: Pairing> PrivateMapping<P> {
+    /// Commits to an injective mapping from left to right positions, which can then be opened
+    /// by one well-formedness proof and [`PrivateParameters::max_proofs`] proofs.
+    pub fn new<R: Rng + ?Sized>(
+        pp: &PrivateParameters<P>,
+        mappings: &BTreeMap<usize, usize>,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        if mappings.is_empty() {
+            return Err(Error::IndexMappingCannotBeNone);
+        }
+        if let Some((&i, _)) = mappings.iter().find(|(&i, _)| i >= pp.size_left_values) {
+            return Err(Error::InputIsTooLarge(i));
+        }
+        let mut positions_right: Vec<usize> = mappings.values().copied().collect();
+        positions_right.sort_unstable();
+        if let Some(w) = positions_right.windows(2).find(|w| w[0] == w[1]) {
+            return Err(Error::MappingIsNotInjective(w[0]));
+        }
+        if let Some(&j) = positions_right.iter().find(|&&j| j >= pp.size_right_values) {
+            return Err(Error::InputIsTooLarge(j));
+        }
+
+        let fr_zero = P::ScalarField::zero();
+        let fr_one = P::ScalarField::one();
+        let mut poly_eval_positions_left = vec![fr_zero; pp.size_left_values];
+        let mut poly_eval_positions_right = vec![fr_zero; pp.size_right_values];
+        let mut poly_eval_position_mappings = vec![fr_zero; pp.size_left_values];
+        mappings.iter().for_each(|(&i, &j)| {
+            poly_eval_positions_left[i] = fr_one;
+            poly_eval_positions_right[j] = fr_one;
+            poly_eval_position_mappings[i] = pp.domain_r.element(j);
+        });
+        let max_openings = pp.max_openings();
+        let poly_positions_left = blind(
+            DensePolynomial::from_coefficients_vec(pp.domain_l.ifft(&poly_eval_positions_left)),
+            pp.size_left_values,
+            &random_blinder(max_openings, rng),
+        );
+        let poly_positions_right = blind(
+            DensePolynomial::from_coefficients_vec(pp.domain_r.ifft(&poly_eval_positions_right)),
+            pp.size_right_values,
+            &random_blinder(max_openings, rng),
+        );
+        let poly_position_mappings = blind(
+            DensePolynomial::from_coefficients_vec(pp.domain_l.ifft(&poly_eval_position_mappings)),
+            pp.size_left_values,
+            &random_blinder(max_openings, rng),
+        );
+
+        let commitment = MappingCommitment {
+            g1_affine_positions_left: Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_positions_left).into_affine(),
+            g1_affine_positions_right: Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_positions_right).into_affine(),
+            g1_affine_position_mappings: Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_position_mappings)
+                .into_affine(),
+        };
+
+        Ok(Self {
+            position_mappings: mappings.clone(),
+            coset_eval_list_positions_left: pp.domain_coset_l.fft(&poly_positions_left),
+            coset_eval_list_positions_right: pp.domain_coset_r.fft(&poly_positions_right),
+            coset_eval_list_position_mappings: pp.domain_coset_l.fft(&poly_position_mappings),
+            poly_positions_left,
+            poly_positions_right,
+            poly_position_mappings,
+            commitment,
+            remaining_openings: Cell::new(max_openings),
+        })
+    }
+
+    pub fn commitment(&self) -> &MappingCommitment<P> {
+        &self.commitment
+    }
+
+    /// Takes one opening from the budget of the blinders.
+    fn use_opening(&self) -> Result<(), Error> {
+        let remaining = self.remaining_openings.get();
+        if remaining == 0 {
+            return Err(Error::OpeningLimitReached);
+        }
+        self.remaining_openings.set(remaining - 1);
+
+        Ok(())
+    }
+
+    /// Proves that the committed mapping is well formed.
+    ///
+    /// With `S_L`, `S_R` the selectors and `M` the mapping polynomial, it shows
+    /// `S_L^2 = S_L` over the left domain, `S_R^2 = S_R` over the right domain, and
+    /// `sum_i S_L(w^i) / (beta + M(w^i)) = sum_j S_R(v^j) / (beta + v^j)`, so the mapping
+    /// values at the selected left positions are the selected right domain elements, each
+    /// once.
+    ///
+    /// The halves `A` and `B` of the sum only depend on the mapping and `beta`, so they are
+    /// blinded as well, and only `n * A - m * B` is opened at zero. This uses one opening of
+    /// the mapping.
+    pub fn prove_well_formed<R: Rng + ?Sized>(
+        &self,
+        pp: &PrivateParameters<P>,
+        rng: &mut R,
+    ) -> Result<MappingProof<P>, Error> {
+        self.use_opening()?;
+
+        let mut transcript = Transcript::<P::ScalarField>::new();
+        transcript.append_elements(&[
+            (Label::PublicParameters, pp.hash_representation.clone()),
+            (Label::MappingCommitment, self.commitment.hash_representation()?),
+        ])?;
+
+        let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+
+        // `A` and `B` are opened once at delta. Their blinders vanish at zero, which keeps
+        // `A(0)` and `B(0)` for the sumcheck.
+        let fr_zero = P::ScalarField::zero();
+        let fr_one = P::ScalarField::one();
+        let fraction_list_a = self.position_mappings.iter()
+            .map(|(&i, &j)| (i, fr_one, beta + pp.domain_r.element(j)))
+            .collect();
+        let poly_a = blind(
+            fractional_poly(&pp.domain_l, fraction_list_a)?,
+            pp.size_left_values,
+            &[fr_zero, P::ScalarField::rand(rng), P::ScalarField::rand(rng)],
+        );
+        let fraction_list_b = self.position_mappings.values()
+            .map(|&j| (j, fr_one, beta + pp.domain_r.element(j)))
+            .collect();
+        let poly_b = blind(
+            fractional_poly(&pp.domain_r, fraction_list_b)?,
+            pp.size_right_values,
+            &[fr_zero, P::ScalarField::rand(rng), P::ScalarField::rand(rng)],
+        );
+        let g1_affine_a = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_a).into_affine();
+        let g1_affine_b = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_b).into_affine();
+        transcript.append_elements(&[(Label::G1L, g1_affine_a), (Label::G1R, g1_affine_b)])?;
+
+        // Sample random eta to combine the booleanity and lookup identities of each domain.
+        let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+
+        let poly_beta = DensePolynomial::from_coefficients_vec(vec![beta]);
+        let poly_x = DensePolynomial::from_coefficients_vec(vec![fr_zero, fr_one]);
+        let s_l = &self.poly_positions_left;
+        let s_r = &self.poly_positions_right;
+        let numerator_a = &(&(s_l * s_l) - s_l)
+            + &(&(&(&poly_a * &(&poly_beta + &self.poly_position_mappings)) - s_l) * eta);
+        let numerator_b = &(&(s_r * s_r) - s_r) + &(&(&(&poly_b * &(&poly_beta + &poly_x)) - s_r) * eta);
+        let poly_qa = exact_quotient(&numerator_a, &pp.domain_l)?;
+        let poly_qb = exact_quotient(&numerator_b, &pp.domain_r)?;
+        let g1_affine_qa = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_qa).into_affine();
+        let g1_affine_qb = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_qb).into_affine();
+        transcript.append_elements(&[(Label::G1Ql, g1_affine_qa), (Label::G1Qr, g1_affine_qb)])?;
+
+        let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+        let proof = MappingProof {
+            g1_affine_a,
+            g1_affine_b,
+            g1_affine_qa,
+            g1_affine_qb,
+            // Filled in once the evaluations are bound into the transcript.
+            batch_proof: P::G1Affine::zero(),
+            a_at_delta: poly_a.evaluate(&delta),
+            b_at_delta: poly_b.evaluate(&delta),
+            pl_at_delta: s_l.evaluate(&delta),
+            pr_at_delta: s_r.evaluate(&delta),
+            pm_at_delta: self.poly_position_mappings.evaluate(&delta),
+        };
+        transcript.append_elements(&proof.evaluations())?;
+
+        let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+        let poly_sumcheck = sumcheck_poly(pp, &poly_a, &poly_b);
+        let batch_proof = Kzg::<P::G1>::multi_point_batch_open(
+            &pp.g1_affine_srs,
+            &[
+                &[&poly_a, &poly_b, &poly_qa, &poly_qb, s_l, s_r, &self.poly_position_mappings],
+                &[&poly_sumcheck],
+            ],
+            &[delta, fr_zero],
+            epsilon,
+        );
+
+        Ok(MappingProof { batch_proof, ..proof })
+    }
+}
+
+/// Adds `B(X) * (X^n - 1)` to `poly`, whose degree is below `n`, for the `blinder` coefficients
+/// of `B`. This leaves the evaluations over the domain of size `n` unchanged.
+fn blind<F: FftField>(poly: DensePolynomial<F>, n: usize, blinder: &[F]) -> DensePolynomial<F> {
+    let mut coeffs = poly.coeffs;
+    coeffs.resize(n + blinder.len(), F::zero());
+    blinder.iter().enumerate().for_each(|(k, &fr_blinder)| {
+        coeffs[k] -= fr_blinder;
+        coeffs[n + k] += fr_blinder;
+    });
+
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
+/// Random coefficients of a blinder that hides a polynomial over `num_openings` openings: the
+/// commitment and the openings give `num_openings + 1` equations in as many unknowns.
+fn random_blinder<F: FftField, R: Rng + ?Sized>(num_openings: usize, rng: &mut R) -> Vec<F> {
+    (0..=num_openings).map(|_| F::rand(rng)).collect()
+}
+
+/// `n * A(X) - m * B(X)`, which is zero at zero if the halves have equal sums over their
+/// domains.
+fn sumcheck_poly<P: Pairing>(
+    pp: &PrivateParameters<P>,
+    poly_a: &DensePolynomial<P::ScalarField>,
+    poly_b: &DensePolynomial<P::ScalarField>,
+) -> DensePolynomial<P::ScalarField> {
+    &(poly_a * P::ScalarField::from(pp.size_left_values as u64))
+        - &(poly_b * P::ScalarField::from(pp.size_right_values as u64))
+}
+
+fn exact_quotient<F: FftField>(
+    numerator: &DensePolynomial<F>,
+    domain: &Radix2EvaluationDomain<F>,
+) -> Result<DensePolynomial<F>, Error> {
+    let (quotient, remainder) = numerator.divide_by_vanishing_poly(*domain);
+    if !remainder.is_zero() {
+        return Err(Error::RemainderAfterDivisionIsNonZero);
+    }
+
+    Ok(quotient)
+}
+
+/// Proof that a [`MappingCommitment`] commits to a well-formed mapping.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MappingProof<P: Pairing> {
+    pub(crate) g1_affine_a: P::G1Affine,
+    pub(crate) g1_affine_b: P::G1Affine,
+    pub(crate) g1_affine_qa: P::G1Affine,
+    pub(crate) g1_affine_qb: P::G1Affine,
+    pub(crate) batch_proof: P::G1Affine,
+    pub(crate) a_at_delta: P::ScalarField,
+    pub(crate) b_at_delta: P::ScalarField,
+    pub(crate) pl_at_delta: P::ScalarField,
+    pub(crate) pr_at_delta: P::ScalarField,
+    pub(crate) pm_at_delta: P::ScalarField,
+}
+
+impl<P: Pairing> MappingProof<P> {
+    fn evaluations(&self) -> [(Label, P::ScalarField); 5] {
+        [
+            (Label::FrLAtDelta, self.a_at_delta),
+            (Label::FrRAtDelta, self.b_at_delta),
+            (Label::FrPlAtDelta, self.pl_at_delta),
+            (Label::FrPrAtDelta, self.pr_at_delta),
+            (Label::FrPmAtDelta, self.pm_at_delta),
+        ]
+    }
+}
+
+pub fn verify_mapping<P: Pairing>(
+    pp: &PrivateParameters<P>,
+    commitment: &MappingCommitment<P>,
+    proof: &MappingProof<P>,
+) -> Result<(), Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::MappingCommitment, commitment.hash_representation()?),
+    ])?;
+
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    transcript.append_elements(&[(Label::G1L, proof.g1_affine_a), (Label::G1R, proof.g1_affine_b)])?;
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+    transcript.append_elements(&[(Label::G1Ql, proof.g1_affine_qa), (Label::G1Qr, proof.g1_affine_qb)])?;
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+    transcript.append_elements(&proof.evaluations())?;
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let fr_one = P::ScalarField::one();
+    let fr_inv_zl_at_delta = (delta.pow([pp.size_left_values as u64]) - fr_one)
+        .inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let fr_inv_zr_at_delta = (delta.pow([pp.size_right_values as u64]) - fr_one)
+        .inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let s_l = proof.pl_at_delta;
+    let s_r = proof.pr_at_delta;
+    let fr_qa_at_delta = (s_l * s_l - s_l + eta * (proof.a_at_delta * (beta + proof.pm_at_delta) - s_l))
+        * fr_inv_zl_at_delta;
+    let fr_qb_at_delta = (s_r * s_r - s_r + eta * (proof.b_at_delta * (beta + delta) - s_r))
+        * fr_inv_zr_at_delta;
+
+    // The sumcheck `n * A(0) = m * B(0)` is the opening of `n * A - m * B` to zero.
+    let g1_affine_sumcheck = (proof.g1_affine_a * P::ScalarField::from(pp.size_left_values as u64)
+        - proof.g1_affine_b * P::ScalarField::from(pp.size_right_values as u64))
+        .into_affine();
+
+    let fr_zero = P::ScalarField::zero();
+    if !multi_point_batch_verify::<P>(
+        pp.g1_affine_srs[0],
+        &pp.g2_affine_srs,
+        &[
+            &[
+                proof.g1_affine_a,
+                proof.g1_affine_b,
+                proof.g1_affine_qa,
+                proof.g1_affine_qb,
+                commitment.g1_affine_positions_left,
+                commitment.g1_affine_positions_right,
+                commitment.g1_affine_position_mappings,
+            ],
+            &[g1_affine_sumcheck],
+        ],
+        &[
+            &[
+                proof.a_at_delta,
+                proof.b_at_delta,
+                fr_qa_at_delta,
+                fr_qb_at_delta,
+                proof.pl_at_delta,
+                proof.pr_at_delta,
+                proof.pm_at_delta,
+            ],
+            &[fr_zero],
+        ],
+        &[delta, fr_zero],
+        epsilon,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("batch opening of the mapping at delta and of the sumcheck at zero"));
+    }
+
+    Ok(())
+}
+
+impl<P: Pairing> Witness<P> {
+    pub fn new_private(
+        pp: &PrivateParameters<P>,
+        left_columns: &[&[P::ScalarField]],
+        right_columns: &[&[P::ScalarField]],
+    ) -> Result<Self, Error> {
+        Self::from_columns(&pp.domain_l, &pp.domain_r, left_columns, right_columns)
+    }
+
+    pub fn generate_private_statement(&self, pp: &PrivateParameters<P>) -> Result<Statement<P>, Error> {
+        self.commit_columns(&pp.g1_affine_srs)
+    }
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PrivateProof<P: Pairing> {
+    pub(crate) g1_affine_l: P::G1Affine,
+    pub(crate) g1_affine_r: P::G1Affine,
+    pub(crate) g1_affine_ql: P::G1Affine,
+    pub(crate) g1_affine_qr: P::G1Affine,
+    pub(crate) batch_proof: P::G1Affine,
+    pub(crate) l_at_delta: P::ScalarField,
+    pub(crate) r_at_delta: P::ScalarField,
+    pub(crate) lv_at_delta: P::ScalarField,
+    pub(crate) rv_at_delta: P::ScalarField,
+    pub(crate) pl_at_delta: P::ScalarField,
+    pub(crate) pr_at_delta: P::ScalarField,
+    pub(crate) pm_at_delta: P::ScalarField,
+    pub(crate) l_at_zero: P::ScalarField,
+    pub(crate) r_at_zero: P::ScalarField,
+}
+
+/// Proves the relation of [`crate::prover::prove`] for the committed private mapping.
+///
+/// The halves `L` and `R` only depend on the values and the mapping, so they are blinded like
+/// the halves of [`PrivateMapping::prove_well_formed`], which keeps `L(0)` and `R(0)`.
+pub fn prove<P: Pairing, R: Rng + ?Sized>(
+    pp: &PrivateParameters<P>,
+    mapping: &PrivateMapping<P>,
+    witness: &Witness<P>,
+    statement: &Statement<P>,
+    rng: &mut R,
+) -> Result<PrivateProof<P>, Error> {
+    mapping.use_opening()?;
+
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::MappingCommitment, mapping.commitment.hash_representation()?),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    // Sample random alpha, beta, gamma.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+
+    let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+    let (left_values, poly_left_values) =
+        fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+    let (right_values, poly_right_values) =
+        fold_columns(&witness.right_values, &witness.poly_right_values, &powers_of_alpha);
+
+    // `L` and `R` are opened once at delta. Their blinders vanish at zero, which keeps `L(0)`
+    // and `R(0)` for the sumcheck.
+    let fr_zero = P::ScalarField::zero();
+    let fr_one = P::ScalarField::one();
+    let fraction_list_l = mapping.position_mappings.iter()
+        .map(|(&i, &j)| (i, fr_one, beta + left_values[i] + gamma * pp.domain_r.element(j)))
+        .collect();
+    let poly_l = blind(
+        fractional_poly(&pp.domain_l, fraction_list_l)?,
+        pp.size_left_values,
+        &[fr_zero, P::ScalarField::rand(rng), P::ScalarField::rand(rng)],
+    );
+    let fraction_list_r = mapping.position_mappings.values()
+        .map(|&j| (j, fr_one, beta + right_values[j] + gamma * pp.domain_r.element(j)))
+        .collect();
+    let poly_r = blind(
+        fractional_poly(&pp.domain_r, fraction_list_r)?,
+        pp.size_right_values,
+        &[fr_zero, P::ScalarField::rand(rng), P::ScalarField::rand(rng)],
+    );
+
+    let coset_eval_list_l = pp.domain_coset_l.fft(&poly_l);
+    let coset_eval_list_left_values = pp.domain_coset_l.fft(&poly_left_values);
+    let coset_eval_list_ql: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_l)
+        .zip(cfg_iter!(coset_eval_list_left_values))
+        .zip(cfg_iter!(mapping.coset_eval_list_positions_left))
+        .zip(cfg_iter!(mapping.coset_eval_list_position_mappings))
+        .map(|(((&l, &v), &p), &m)| l * (beta + v + gamma * m) - p)
+        .collect();
+    let poly_ql = quotient_poly::<P>(&pp.domain_l, &pp.domain_coset_l, coset_eval_list_ql)?;
+
+    let coset_eval_list_r = pp.domain_coset_r.fft(&poly_r);
+    let coset_eval_list_right_values = pp.domain_coset_r.fft(&poly_right_values);
+    let coset_eval_list_qr: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_r)
+        .zip(cfg_iter!(coset_eval_list_right_values))
+        .zip(cfg_iter!(mapping.coset_eval_list_positions_right))
+        .zip(cfg_iter!(pp.roots_of_unity_coset_r))
+        .map(|(((&r, &e), &p), &c)| r * (beta + e + gamma * c) - p)
+        .collect();
+    let poly_qr = quotient_poly::<P>(&pp.domain_r, &pp.domain_coset_r, coset_eval_list_qr)?;
+
+    let g1_affine_l = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_l).into_affine();
+    let g1_affine_r = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_r).into_affine();
+    let g1_affine_ql = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_ql).into_affine();
+    let g1_affine_qr = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_qr).into_affine();
+    transcript.append_elements(
+        &[
+            (Label::G1L, g1_affine_l),
+            (Label::G1R, g1_affine_r),
+            (Label::G1Ql, g1_affine_ql),
+            (Label::G1Qr, g1_affine_qr),
+        ]
+    )?;
+
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    let proof = PrivateProof {
+        g1_affine_l,
+        g1_affine_r,
+        g1_affine_ql,
+        g1_affine_qr,
+        // Filled in once the evaluations are bound into the transcript.
+        batch_proof: P::G1Affine::zero(),
+        l_at_delta: poly_l.evaluate(&delta),
+        r_at_delta: poly_r.evaluate(&delta),
+        lv_at_delta: poly_left_values.evaluate(&delta),
+        rv_at_delta: poly_right_values.evaluate(&delta),
+        pl_at_delta: mapping.poly_positions_left.evaluate(&delta),
+        pr_at_delta: mapping.poly_positions_right.evaluate(&delta),
+        pm_at_delta: mapping.poly_position_mappings.evaluate(&delta),
+        l_at_zero: poly_l.evaluate(&fr_zero),
+        r_at_zero: poly_r.evaluate(&fr_zero),
+    };
+    transcript.append_elements(&proof.evaluations())?;
+
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let batch_proof = Kzg::<P::G1>::multi_point_batch_open(
+        &pp.g1_affine_srs,
+        &[
+            &[
+                &poly_l,
+                &poly_r,
+                &poly_ql,
+                &poly_qr,
+                &poly_left_values,
+                &poly_right_values,
+                &mapping.poly_positions_left,
+                &mapping.poly_positions_right,
+                &mapping.poly_position_mappings,
+            ],
+            &[&poly_l, &poly_r],
+        ],
+        &[delta, fr_zero],
+        epsilon,
+    );
+
+    Ok(PrivateProof { batch_proof, ..proof })
+}
+
+impl<P: Pairing> PrivateProof<P> {
+    fn evaluations(&self) -> [(Label, P::ScalarField); 9] {
+        [
+            (Label::FrLAtDelta, self.l_at_delta),
+            (Label::FrRAtDelta, self.r_at_delta),
+            (Label::FrLvAtDelta, self.lv_at_delta),
+            (Label::FrRvAtDelta, self.rv_at_delta),
+            (Label::FrPlAtDelta, self.pl_at_delta),
+            (Label::FrPrAtDelta, self.pr_at_delta),
+            (Label::FrPmAtDelta, self.pm_at_delta),
+            (Label::FrLAtZero, self.l_at_zero),
+            (Label::FrRAtZero, self.r_at_zero),
+        ]
+    }
+}
+
+/// Verifies a proof against a mapping commitment that [`verify_mapping`] has accepted.
+pub fn verify<P: Pairing>(
+    pp: &PrivateParameters<P>,
+    commitment: &MappingCommitment<P>,
+    statement: &Statement<P>,
+    proof: &PrivateProof<P>,
+) -> Result<(), Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::MappingCommitment, commitment.hash_representation()?),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    if statement.g1_affine_left_values.is_empty()
+        || statement.g1_affine_left_values.len() != statement.g1_affine_right_values.len()
+    {
+        return Err(Error::WrongNumberOfColumns(statement.g1_affine_right_values.len()));
+    }
+
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+    let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
+    transcript.append_elements(
+        &[
+            (Label::G1L, proof.g1_affine_l),
+            (Label::G1R, proof.g1_affine_r),
+            (Label::G1Ql, proof.g1_affine_ql),
+            (Label::G1Qr, proof.g1_affine_qr),
+        ]
+    )?;
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+    transcript.append_elements(&proof.evaluations())?;
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let fr_one = P::ScalarField::one();
+    let fr_inv_zl_at_delta = (delta.pow([pp.size_left_values as u64]) - fr_one)
+        .inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let fr_inv_zr_at_delta = (delta.pow([pp.size_right_values as u64]) - fr_one)
+        .inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let fr_ql_at_delta = (proof.l_at_delta * (beta + proof.lv_at_delta + gamma * proof.pm_at_delta)
+        - proof.pl_at_delta) * fr_inv_zl_at_delta;
+    let fr_qr_at_delta = (proof.r_at_delta * (beta + proof.rv_at_delta + gamma * delta)
+        - proof.pr_at_delta) * fr_inv_zr_at_delta;
+
+    let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+    let g1_affine_left_values = P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha)
+        .into_affine();
+    let g1_affine_right_values = P::G1::msm_unchecked(&statement.g1_affine_right_values, &powers_of_alpha)
+        .into_affine();
+
+    if !multi_point_batch_verify::<P>(
+        pp.g1_affine_srs[0],
+        &pp.g2_affine_srs,
+        &[
+            &[
+                proof.g1_affine_l,
+                proof.g1_affine_r,
+                proof.g1_affine_ql,
+                proof.g1_affine_qr,
+                g1_affine_left_values,
+                g1_affine_right_values,
+                commitment.g1_affine_positions_left,
+                commitment.g1_affine_positions_right,
+                commitment.g1_affine_position_mappings,
+            ],
+            &[proof.g1_affine_l, proof.g1_affine_r],
+        ],
+        &[
+            &[
+                proof.l_at_delta,
+                proof.r_at_delta,
+                fr_ql_at_delta,
+                fr_qr_at_delta,
+                proof.lv_at_delta,
+                proof.rv_at_delta,
+                proof.pl_at_delta,
+                proof.pr_at_delta,
+                proof.pm_at_delta,
+            ],
+            &[proof.l_at_zero, proof.r_at_zero],
+        ],
+        &[delta, P::ScalarField::zero()],
+        epsilon,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("batch opening at delta and zero"));
+    }
+
+    if proof.l_at_zero * P::ScalarField::from(pp.size_left_values as u64)
+        != proof.r_at_zero * P::ScalarField::from(pp.size_right_values as u64)
+    {
+        return Err(Error::EqualityCheckFailed("sumcheck of left and right halves"));
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "bn254"))]
+mod tests {
+    use super::{prove, PrivateMapping, PrivateParameters};
+    use crate::kzg::Kzg;
+    use crate::prover::fractional_poly;
+    use crate::transcript::{Label, Transcript};
+    use crate::witness::Witness;
+    use ark_bn254::{Bn254, Fr, G1Projective};
+    use ark_ec::CurveGroup;
+    use ark_ff::{Field, One};
+    use ark_poly::univariate::DensePolynomial;
+    use ark_poly::{DenseUVPolynomial, EvaluationDomain, Polynomial};
+    use ark_std::collections::BTreeMap;
+    use ark_std::{test_rng, vec, vec::Vec, UniformRand};
+
+    /// Two openings of the mapping polynomial, as a well-formedness proof and a proof give out,
+    /// do not let a verifier confirm a guessed mapping.
+    #[test]
+    fn two_openings_do_not_reveal_the_mapping() {
+        let rng = &mut test_rng();
+        let mappings: BTreeMap<usize, usize> = [(1, 5), (2, 0), (6, 9)].into_iter().collect();
+        let pp = PrivateParameters::<Bn254>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .max_proofs(1)
+            .build(rng).unwrap();
+        let mapping = PrivateMapping::new(&pp, &mappings, rng).unwrap();
+
+        // The correct guess, interpolated over the left domain.
+        let mut evals = vec![Fr::from(0u64); pp.size_left_values];
+        mappings.iter().for_each(|(&i, &j)| evals[i] = pp.domain_r.element(j));
+        let poly_guess = DensePolynomial::from_coefficients_vec(pp.domain_l.ifft(&evals));
+
+        let points = [Fr::rand(rng), Fr::rand(rng)];
+        let openings = points.map(|z| mapping.poly_position_mappings.evaluate(&z));
+        let zh = |z: Fr| z.pow([pp.size_left_values as u64]) - Fr::one();
+        let blinded = |z: Fr, y: Fr| (y - poly_guess.evaluate(&z)) / zh(z);
+
+        // A single `r * Z_H(X)` would be found from the first opening and confirmed by the second.
+        let r = blinded(points[0], openings[0]);
+        assert_ne!(poly_guess.evaluate(&points[1]) + r * zh(points[1]), openings[1]);
+
+        // A linear blinder through both openings does not give the commitment either.
+        let [b_0, b_1] = [blinded(points[0], openings[0]), blinded(points[1], openings[1])];
+        let slope = (b_1 - b_0) / (points[1] - points[0]);
+        let poly_blinder = DensePolynomial::from_coefficients_vec(vec![b_0 - slope * points[0], slope]);
+        let poly_zh = DensePolynomial::from(pp.domain_l.vanishing_polynomial());
+        let poly_candidate = &poly_guess + &(&poly_blinder * &poly_zh);
+        assert_eq!(poly_candidate.evaluate(&points[1]), openings[1]);
+        assert_ne!(
+            Kzg::<G1Projective>::commit(&pp.g1_affine_srs, &poly_candidate).into_affine(),
+            mapping.commitment().g1_affine_position_mappings
+        );
+    }
+
+    /// A verifier who knows the values cannot confirm a guessed mapping by recomputing the halves
+    /// from the challenges and committing to them.
+    #[test]
+    fn guessed_mapping_does_not_match_the_halves() {
+        let rng = &mut test_rng();
+        let mappings: BTreeMap<usize, usize> = [(1, 5), (2, 0), (6, 9)].into_iter().collect();
+        let pp = PrivateParameters::<Bn254>::builder()
+            .size_left_values(8)
+            .size_right_values(16)
+            .build(rng).unwrap();
+        let mapping = PrivateMapping::new(&pp, &mappings, rng).unwrap();
+        let right_values: Vec<Fr> = (0..pp.size_right_values).map(|_| Fr::rand(rng)).collect();
+        let mut left_values: Vec<Fr> = (0..pp.size_left_values).map(|_| Fr::rand(rng)).collect();
+        mappings.iter().for_each(|(&i, &j)| left_values[i] = right_values[j]);
+        let witness = Witness::new_private(&pp, &[&left_values], &[&right_values]).unwrap();
+        let statement = witness.generate_private_statement(&pp).unwrap();
+        let proof = prove(&pp, &mapping, &witness, &statement, rng).unwrap();
+
+        let mut transcript = Transcript::<Fr>::new();
+        transcript.append_elements(&[
+            (Label::PublicParameters, pp.hash_representation.clone()),
+            (Label::MappingCommitment, mapping.commitment().hash_representation().unwrap()),
+            (Label::Statement, statement.hash_representation.clone()),
+        ]).unwrap();
+        let _alpha = transcript.squeeze_challenge(Label::ChallengeAlpha).unwrap();
+        let beta = transcript.squeeze_challenge(Label::ChallengeBeta).unwrap();
+        let gamma = transcript.squeeze_challenge(Label::ChallengeGamma).unwrap();
+
+        // The correct guess gives the halves before blinding, with the same sums.
+        let fraction_list_l = mappings.iter()
+            .map(|(&i, &j)| (i, Fr::one(), beta + left_values[i] + gamma * pp.domain_r.element(j)))
+            .collect();
+        let poly_guess_l = fractional_poly(&pp.domain_l, fraction_list_l).unwrap();
+        let fraction_list_r = mappings.values()
+            .map(|&j| (j, Fr::one(), beta + right_values[j] + gamma * pp.domain_r.element(j)))
+            .collect();
+        let poly_guess_r = fractional_poly(&pp.domain_r, fraction_list_r).unwrap();
+        assert_eq!(poly_guess_l.evaluate(&Fr::from(0u64)), proof.l_at_zero);
+        assert_eq!(poly_guess_r.evaluate(&Fr::from(0u64)), proof.r_at_zero);
+
+        assert_ne!(
+            Kzg::<G1Projective>::commit(&pp.g1_affine_srs, &poly_guess_l).into_affine(),
+            proof.g1_affine_l
+        );
+        assert_ne!(
+            Kzg::<G1Projective>::commit(&pp.g1_affine_srs, &poly_guess_r).into_affine(),
+            proof.g1_affine_r
+        );
+    }
+}
@@ -1,25 +1,31 @@
 use crate::domain::{divide_by_vanishing_poly_on_coset_in_place, roots_of_unity};
 use crate::error::Error;
-use crate::kzg::Kzg;
+use crate::grand_product::{self, GrandProductProof};
+use crate::kzg::{linear_combination, powers_of_scalars, Kzg};
 use crate::public_parameters::PublicParameters;
 use crate::statement::Statement;
 use crate::transcript::{Label, Transcript};
-use crate::witness::Witness;
+use crate::witness::{fold_columns, Witness};
 use ark_ec::pairing::Pairing;
-use ark_ec::CurveGroup;
-use ark_ff::Field;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{batch_inversion, FftField, One};
 use ark_poly::univariate::DensePolynomial;
-use ark_poly::{DenseUVPolynomial, EvaluationDomain, Polynomial};
-use ark_std::Zero;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Polynomial, Radix2EvaluationDomain};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::io::{Read, Write};
+use ark_std::{cfg_iter, cfg_iter_mut, vec, vec::Vec, Zero};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Proof<P: Pairing> {
     pub(crate) g1_affine_l: P::G1Affine,
     pub(crate) g1_affine_r: P::G1Affine,
     pub(crate) g1_affine_ql: P::G1Affine,
     pub(crate) g1_affine_qr: P::G1Affine,
-    pub(crate) batch_proof_at_rand_point: P::G1Affine,
-    pub(crate) batch_proof_at_zero: P::G1Affine,
+    pub(crate) batch_proof: P::G1Affine,
     pub(crate) l_at_delta: P::ScalarField,
     pub(crate) r_at_delta: P::ScalarField,
     pub(crate) lv_at_delta: P::ScalarField,
@@ -27,179 +33,342 @@ pub struct Proof<P: Pairing> {
     pub(crate) pl_at_delta: P::ScalarField,
     pub(crate) pr_at_delta: P::ScalarField,
     pub(crate) pm_at_delta: P::ScalarField,
+    /// Only for parameters with [`PublicParameters::has_coefficients`].
+    pub(crate) pa_at_delta: Option<P::ScalarField>,
+    pub(crate) pb_at_delta: Option<P::ScalarField>,
+    /// Only for parameters with [`PublicParameters::has_constants`].
+    pub(crate) lv0_at_delta: Option<P::ScalarField>,
+    pub(crate) ps_at_delta: Option<P::ScalarField>,
+    pub(crate) pc_at_delta: Option<P::ScalarField>,
     pub(crate) l_at_zero: P::ScalarField,
     pub(crate) r_at_zero: P::ScalarField,
 }
 
+impl<P: Pairing> Proof<P> {
+    /// The evaluations as the transcript binds them, skipping those the proof does not carry.
+    pub(crate) fn evaluations(&self) -> Vec<(Label, P::ScalarField)> {
+        let mut evaluations = vec![
+            (Label::FrLAtDelta, self.l_at_delta),
+            (Label::FrRAtDelta, self.r_at_delta),
+            (Label::FrLvAtDelta, self.lv_at_delta),
+            (Label::FrRvAtDelta, self.rv_at_delta),
+            (Label::FrPlAtDelta, self.pl_at_delta),
+            (Label::FrPrAtDelta, self.pr_at_delta),
+            (Label::FrPmAtDelta, self.pm_at_delta),
+        ];
+        evaluations.extend(self.pa_at_delta.map(|pa_at_delta| (Label::FrPaAtDelta, pa_at_delta)));
+        evaluations.extend(self.pb_at_delta.map(|pb_at_delta| (Label::FrPbAtDelta, pb_at_delta)));
+        evaluations.extend(self.lv0_at_delta.map(|lv0_at_delta| (Label::FrLv0AtDelta, lv0_at_delta)));
+        evaluations.extend(self.ps_at_delta.map(|ps_at_delta| (Label::FrPsAtDelta, ps_at_delta)));
+        evaluations.extend(self.pc_at_delta.map(|pc_at_delta| (Label::FrPcAtDelta, pc_at_delta)));
+        evaluations.extend([
+            (Label::FrLAtZero, self.l_at_zero),
+            (Label::FrRAtZero, self.r_at_zero),
+        ]);
+
+        evaluations
+    }
+}
+
 pub fn prove<P: Pairing>(
     pp: &PublicParameters<P>,
     witness: &Witness<P>,
     statement: &Statement<P>,
 ) -> Result<Proof<P>, Error> {
+    prove_inner(pp, witness, &statement.hash_representation, None).map(|(proof, _)| proof)
+}
+
+/// The argument that proves the relation of [`prove`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Compares sums of fractions, as [`prove`] does.
+    #[default]
+    LogDerivative,
+    /// Compares products of the denominators, as [`crate::grand_product::prove`] does.
+    GrandProduct,
+}
+
+/// A proof made by one of the backends.
+#[derive(Debug)]
+pub enum BackendProof<P: Pairing> {
+    LogDerivative(Proof<P>),
+    GrandProduct(GrandProductProof<P>),
+}
+
+impl<P: Pairing> BackendProof<P> {
+    pub fn backend(&self) -> Backend {
+        match self {
+            BackendProof::LogDerivative(_) => Backend::LogDerivative,
+            BackendProof::GrandProduct(_) => Backend::GrandProduct,
+        }
+    }
+}
+
+// The backend is written as one byte ahead of its proof.
+impl<P: Pairing> CanonicalSerialize for BackendProof<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        match self {
+            BackendProof::LogDerivative(proof) => {
+                0u8.serialize_with_mode(&mut writer, compress)?;
+                proof.serialize_with_mode(&mut writer, compress)
+            }
+            BackendProof::GrandProduct(proof) => {
+                1u8.serialize_with_mode(&mut writer, compress)?;
+                proof.serialize_with_mode(&mut writer, compress)
+            }
+        }
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        1 + match self {
+            BackendProof::LogDerivative(proof) => proof.serialized_size(compress),
+            BackendProof::GrandProduct(proof) => proof.serialized_size(compress),
+        }
+    }
+}
+
+impl<P: Pairing> Valid for BackendProof<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        match self {
+            BackendProof::LogDerivative(proof) => proof.check(),
+            BackendProof::GrandProduct(proof) => proof.check(),
+        }
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for BackendProof<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        match u8::deserialize_with_mode(&mut reader, compress, validate)? {
+            0 => Proof::deserialize_with_mode(&mut reader, compress, validate).map(BackendProof::LogDerivative),
+            1 => GrandProductProof::deserialize_with_mode(&mut reader, compress, validate).map(BackendProof::GrandProduct),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+/// Proves the relation of [`prove`] with the chosen backend.
+pub fn prove_with_backend<P: Pairing>(
+    pp: &PublicParameters<P>,
+    witness: &Witness<P>,
+    statement: &Statement<P>,
+    backend: Backend,
+) -> Result<BackendProof<P>, Error> {
+    match backend {
+        Backend::LogDerivative => prove(pp, witness, statement).map(BackendProof::LogDerivative),
+        Backend::GrandProduct => grand_product::prove(pp, witness, statement).map(BackendProof::GrandProduct),
+    }
+}
+
+/// Proves the relation of [`prove`] for the statement with the given hash.
+///
+/// With the columns of an old right vector, it also proves that the right vector equals the
+/// old one at the positions outside `positions_right`, and returns the folded old right
+/// vector's evaluation at `delta`.
+pub(crate) fn prove_inner<P: Pairing>(
+    pp: &PublicParameters<P>,
+    witness: &Witness<P>,
+    statement_hash: &[u8],
+    poly_old_right_values: Option<&[DensePolynomial<P::ScalarField>]>,
+) -> Result<(Proof<P>, Option<P::ScalarField>), Error> {
+    pp.ensure_mapping()?;
+
     let mut transcript = Transcript::<P::ScalarField>::new();
     transcript.append_elements(&[
         (Label::PublicParameters, pp.hash_representation.clone()),
-        (Label::Statement, statement.hash_representation.clone()),
+        (Label::Statement, statement_hash.to_vec()),
     ])?;
 
-    // Sample random beta, gamma.
+    // Sample random alpha, beta, gamma.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
     let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
     let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
 
-    // Construct the polynomial representing the left half.
-    let mut poly_eval_l = vec![P::ScalarField::zero(); pp.size_left_values];
-    let non_zero_eval_list: Result<Vec<(usize, P::ScalarField)>, Error> = pp.positions_left
-        .par_iter() // Parallel iterator
+    // Fold the columns of each side into one, so that whole tuples are compared.
+    let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+    let (left_values, poly_left_values) =
+        fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+    let (right_values, poly_right_values) =
+        fold_columns(&witness.right_values, &witness.poly_right_values, &powers_of_alpha);
+    // The offset `b` applies to every column, so it is folded into `b` times the sum of powers.
+    let fr_sum_of_alpha: P::ScalarField = powers_of_alpha.iter().sum();
+    let poly_old_right_values = poly_old_right_values
+        .map(|polys| linear_combination(&polys.iter().collect::<Vec<_>>(), &powers_of_alpha));
+
+    // Construct the polynomials representing the left and right halves.
+    let fraction_list_l = pp.positions_left.iter()
         .map(|&i| {
-            let eval = beta + witness.left_values[i] + gamma * pp.position_mappings[&i];
-            let inv = eval.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+            let (a, b) = pp.coefficients(i);
+            let denominator = beta + a * left_values[i] + fr_sum_of_alpha * b + gamma * pp.position_mappings[&i];
 
-            Ok((i, inv))
+            (i, P::ScalarField::one(), denominator)
         })
         .collect();
-    let non_zero_eval_list = non_zero_eval_list?;
-    non_zero_eval_list.iter().for_each(|(i, eval)| {
-        poly_eval_l[*i] = *eval;
-    });
-    let poly_coeff_l = pp.domain_l.ifft(&poly_eval_l);
-    let poly_l = DensePolynomial::from_coefficients_vec(poly_coeff_l);
+    let poly_l = fractional_poly(&pp.domain_l, fraction_list_l)?;
     let g1_affine_l = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_l).into_affine();
 
+    let roots_of_unity_r = roots_of_unity::<P>(&pp.domain_r);
+    let fraction_list_r = pp.positions_right.iter()
+        .map(|&i| (i, P::ScalarField::one(), beta + right_values[i] + gamma * roots_of_unity_r[i]))
+        .collect();
+    let poly_r = fractional_poly(&pp.domain_r, fraction_list_r)?;
+    let g1_affine_r = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_r).into_affine();
+
+    transcript.append_elements(&[(Label::G1L, g1_affine_l), (Label::G1R, g1_affine_r)])?;
+
+    // Sample random eta to add the public constants of the first left column to the left
+    // quotient, and the unchanged old right values to the right quotient.
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+
     // Construct the quotient polynomial of the left half.
     let coset_eval_list_l = pp.domain_coset_l.fft(&poly_l);
-    let coset_eval_list_left_values = pp.domain_coset_l.fft(&witness.poly_left_values);
-    let mut coset_eval_list_ql: Vec<P::ScalarField> = coset_eval_list_l
-        .par_iter()
-        .zip(coset_eval_list_left_values.par_iter())
-        .zip(pp.coset_eval_list_positions_left.par_iter())
-        .zip(pp.coset_eval_list_position_mappings.par_iter())
-        .map(|(((&l, &v), &p), &m)| l * (beta + v + gamma * m) - p)
+    let coset_eval_list_left_values = pp.domain_coset_l.fft(&poly_left_values);
+    let coset_eval_list_first_column = pp.domain_coset_l.fft(&witness.poly_left_values[0]);
+    let coset_eval_list_ql: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_l)
+        .zip(cfg_iter!(coset_eval_list_left_values))
+        .zip(cfg_iter!(pp.coset_eval_list_positions_left))
+        .zip(cfg_iter!(pp.coset_eval_list_position_mappings))
+        .zip(cfg_iter!(pp.coset_eval_list_coefficients_a))
+        .zip(cfg_iter!(pp.coset_eval_list_coefficients_b))
+        .map(|(((((&l, &v), &p), &m), &a), &b)| l * (beta + a * v + fr_sum_of_alpha * b + gamma * m) - p)
+        .zip(cfg_iter!(coset_eval_list_first_column))
+        .zip(cfg_iter!(pp.coset_eval_list_constant_positions))
+        .zip(cfg_iter!(pp.coset_eval_list_constants))
+        .map(|(((n, &v0), &s), &c)| n + eta * (s * v0 - c))
         .collect();
-    pp.domain_coset_l.ifft_in_place(&mut coset_eval_list_ql);
-    let mut poly_coset_coeff_list_ql = coset_eval_list_ql;
-    divide_by_vanishing_poly_on_coset_in_place::<P::G1>(&pp.domain_l, &mut
-        poly_coset_coeff_list_ql)?;
-    let coeff_ql = poly_coset_coeff_list_ql;
-    let poly_ql = DensePolynomial::from_coefficients_vec(coeff_ql);
+    let poly_ql = quotient_poly::<P>(&pp.domain_l, &pp.domain_coset_l, coset_eval_list_ql)?;
     let g1_affine_ql = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_ql).into_affine();
 
-    // Construct the polynomial representing the right half.
-    let mut poly_eval_r = vec![P::ScalarField::zero(); pp.size_right_values];
-    let roots_of_unity_r = roots_of_unity::<P>(&pp.domain_r);
-    let non_zero_eval_list: Result<Vec<(usize, P::ScalarField)>, Error> = pp.positions_right.par_iter().map(|&i| {
-        let eval = beta + witness.right_values[i] + gamma * roots_of_unity_r[i];
-        let inv = eval.inverse().ok_or(Error::FailedToInverseFieldElement)?;
-
-        Ok((i, inv))
-    }).collect();
-    let non_zero_eval_list = non_zero_eval_list?;
-    non_zero_eval_list.iter().for_each(|(i, eval)| {
-        poly_eval_r[*i] = *eval;
-    });
-    let poly_coeff_r = pp.domain_r.ifft(&poly_eval_r);
-    let poly_r = DensePolynomial::from_coefficients_vec(poly_coeff_r);
-    let g1_affine_r = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_r).into_affine();
-
     // Construct the quotient polynomial of the right half.
     let coset_eval_list_r = pp.domain_coset_r.fft(&poly_r);
-    let coset_eval_list_right_values = pp.domain_coset_r.fft(&witness.poly_right_values);
-    let mut coset_eval_list_qr: Vec<P::ScalarField> = coset_eval_list_r
-        .par_iter()
-        .zip(coset_eval_list_right_values.par_iter())
-        .zip(pp.coset_eval_list_positions_right.par_iter())
-        .zip(pp.roots_of_unity_coset_r.par_iter())
+    let coset_eval_list_right_values = pp.domain_coset_r.fft(&poly_right_values);
+    let mut coset_eval_list_qr: Vec<P::ScalarField> = cfg_iter!(coset_eval_list_r)
+        .zip(cfg_iter!(coset_eval_list_right_values))
+        .zip(cfg_iter!(pp.coset_eval_list_positions_right))
+        .zip(cfg_iter!(pp.roots_of_unity_coset_r))
         .map(|(((&r, &e), &p), &c)| r * (beta + e + gamma * c) - p)
         .collect();
-    pp.domain_coset_r.ifft_in_place(&mut coset_eval_list_qr);
-    let mut poly_coset_coeff_list_qr = coset_eval_list_qr;
-    divide_by_vanishing_poly_on_coset_in_place::<P::G1>(&pp.domain_r, &mut poly_coset_coeff_list_qr)?;
-    let coeff_qr = poly_coset_coeff_list_qr;
-    let poly_qr = DensePolynomial::from_coefficients_vec(coeff_qr);
+    if let Some(poly_old_right_values) = poly_old_right_values.as_ref() {
+        let fr_one = P::ScalarField::one();
+        let coset_eval_list_old_right_values = pp.domain_coset_r.fft(poly_old_right_values);
+        cfg_iter_mut!(coset_eval_list_qr)
+            .zip(cfg_iter!(coset_eval_list_right_values))
+            .zip(cfg_iter!(coset_eval_list_old_right_values))
+            .zip(cfg_iter!(pp.coset_eval_list_positions_right))
+            .for_each(|(((q, &e), &o), &p)| *q += eta * (fr_one - p) * (e - o));
+    }
+    let poly_qr = quotient_poly::<P>(&pp.domain_r, &pp.domain_coset_r, coset_eval_list_qr)?;
     let g1_affine_qr = Kzg::<P::G1>::commit(&pp.g1_affine_srs, &poly_qr).into_affine();
 
-    transcript.append_elements(
-        &[
-            (Label::G1L, g1_affine_l),
-            (Label::G1R, g1_affine_r),
-            (Label::G1Ql, g1_affine_ql),
-            (Label::G1Qr, g1_affine_qr),
-        ]
-    )?;
+    transcript.append_elements(&[(Label::G1Ql, g1_affine_ql), (Label::G1Qr, g1_affine_qr)])?;
 
-    // Sample random delta, phi.
+    // Sample random delta.
     let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    let fr_zero = P::ScalarField::zero();
+    let has_coefficients = pp.has_coefficients();
+    let has_constants = pp.has_constants();
+    let proof = Proof {
+        g1_affine_l,
+        g1_affine_r,
+        g1_affine_ql,
+        g1_affine_qr,
+        // Filled in once the evaluations are bound into the transcript.
+        batch_proof: P::G1Affine::zero(),
+        l_at_delta: poly_l.evaluate(&delta),
+        r_at_delta: poly_r.evaluate(&delta),
+        lv_at_delta: poly_left_values.evaluate(&delta),
+        rv_at_delta: poly_right_values.evaluate(&delta),
+        pl_at_delta: pp.poly_positions_left.evaluate(&delta),
+        pr_at_delta: pp.poly_positions_right.evaluate(&delta),
+        pm_at_delta: pp.poly_position_mappings.evaluate(&delta),
+        pa_at_delta: has_coefficients.then(|| pp.poly_coefficients_a.evaluate(&delta)),
+        pb_at_delta: has_coefficients.then(|| pp.poly_coefficients_b.evaluate(&delta)),
+        lv0_at_delta: has_constants.then(|| witness.poly_left_values[0].evaluate(&delta)),
+        ps_at_delta: has_constants.then(|| pp.poly_constant_positions.evaluate(&delta)),
+        pc_at_delta: has_constants.then(|| pp.poly_constants.evaluate(&delta)),
+        l_at_zero: poly_l.evaluate(&fr_zero),
+        r_at_zero: poly_r.evaluate(&fr_zero),
+    };
+    transcript.append_elements(&proof.evaluations())?;
+
+    let rv_old_at_delta = poly_old_right_values.as_ref().map(|poly| poly.evaluate(&delta));
+    if let Some(rv_old_at_delta) = rv_old_at_delta {
+        transcript.append_element(Label::FrRvOldAtDelta, &rv_old_at_delta)?;
+    }
+
+    // Sample random epsilon to separate the opened polynomials.
     let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
 
-    let batch_proof_at_rand_point = Kzg::<P::G1>::batch_open(
+    // Open all polynomials at delta and the log-derivative polynomials at zero with one proof.
+    let mut poly_list_at_delta = vec![
+        &poly_l,
+        &poly_r,
+        &poly_ql,
+        &poly_qr,
+        &poly_left_values,
+        &poly_right_values,
+        &pp.poly_positions_left,
+        &pp.poly_positions_right,
+        &pp.poly_position_mappings,
+    ];
+    if has_coefficients {
+        poly_list_at_delta.extend([&pp.poly_coefficients_a, &pp.poly_coefficients_b]);
+    }
+    if has_constants {
+        poly_list_at_delta.extend([
+            &witness.poly_left_values[0],
+            &pp.poly_constant_positions,
+            &pp.poly_constants,
+        ]);
+    }
+    poly_list_at_delta.extend(poly_old_right_values.as_ref());
+    let batch_proof = Kzg::<P::G1>::multi_point_batch_open(
         &pp.g1_affine_srs,
-        &[
-            &poly_l,
-            &poly_r,
-            &poly_ql,
-            &poly_qr,
-            &witness.poly_left_values,
-            &witness.poly_right_values,
-            &pp.poly_positions_left,
-            &pp.poly_positions_right,
-            &pp.poly_position_mappings,
-        ],
-        delta,
+        &[&poly_list_at_delta, &[&poly_l, &poly_r]],
+        &[delta, fr_zero],
         epsilon,
     );
 
-    transcript.append_element(Label::G1BatchProofAtRandPoint, &batch_proof_at_rand_point)?;
+    Ok((Proof { batch_proof, ..proof }, rv_old_at_delta))
+}
+/// Interpolates over `domain` the polynomial that equals `numerator / denominator` at each
+/// listed `(index, numerator, denominator)` and zero elsewhere.
+pub(crate) fn fractional_poly<F: FftField>(
+    domain: &Radix2EvaluationDomain<F>,
+    fraction_list: Vec<(usize, F, F)>,
+) -> Result<DensePolynomial<F>, Error> {
+    let mut denominators: Vec<F> = fraction_list.iter().map(|&(_, _, d)| d).collect();
+    if denominators.iter().any(|d| d.is_zero()) {
+        return Err(Error::FailedToInverseFieldElement);
+    }
+    batch_inversion(&mut denominators);
 
-    let l_at_delta = poly_l.evaluate(&delta);
-    let r_at_delta = poly_r.evaluate(&delta);
-    let lv_at_delta = witness.poly_left_values.evaluate(&delta);
-    let rv_at_delta = witness.poly_right_values.evaluate(&delta);
-    let pl_at_delta = pp.poly_positions_left.evaluate(&delta);
-    let pr_at_delta = pp.poly_positions_right.evaluate(&delta);
-    let pm_at_delta = pp.poly_position_mappings.evaluate(&delta);
-    
-    let fr_zero = P::ScalarField::zero();
-    let l_at_zero = poly_l.evaluate(&fr_zero);
-    let r_at_zero = poly_r.evaluate(&fr_zero);
-
-    transcript.append_elements(
-        &[
-            (Label::FrLAtDelta, l_at_delta),
-            (Label::FrRAtDelta, r_at_delta),
-            (Label::FrLvAtDelta, lv_at_delta),
-            (Label::FrRvAtDelta, rv_at_delta),
-            (Label::FrPlAtDelta, pl_at_delta),
-            (Label::FrPrAtDelta, pr_at_delta),
-            (Label::FrPmAtDelta, pm_at_delta),
-            (Label::FrLAtZero, l_at_zero),
-            (Label::FrRAtZero, r_at_zero),
-        ]
-    )?;
-
-    let zeta = transcript.squeeze_challenge(Label::ChallengeZeta)?;
-
-    let batch_proof_at_zero = Kzg::<P::G1>::batch_open(
-        &pp.g1_affine_srs,
-        &[&poly_l, &poly_r],
-        fr_zero,
-        zeta,
-    );
+    let mut poly_eval_list = vec![F::zero(); domain.size()];
+    fraction_list.iter().zip(denominators.iter()).for_each(|(&(i, n, _), &inv)| {
+        poly_eval_list[i] += n * inv;
+    });
+    domain.ifft_in_place(&mut poly_eval_list);
 
+    Ok(DensePolynomial::from_coefficients_vec(poly_eval_list))
+}
 
-    Ok(Proof {
-        g1_affine_l,
-        g1_affine_r,
-        g1_affine_ql,
-        g1_affine_qr,
-        batch_proof_at_rand_point,
-        batch_proof_at_zero,
-        l_at_zero,
-        r_at_zero,
-        l_at_delta,
-        r_at_delta,
-        lv_at_delta,
-        rv_at_delta,
-        pl_at_delta,
-        pr_at_delta,
-        pm_at_delta,
-    })
-}
\ No newline at end of file
+/// Divides the numerator, given by its evaluations over the coset of `domain`, by the
+/// vanishing polynomial of `domain`.
+pub(crate) fn quotient_poly<P: Pairing>(
+    domain: &Radix2EvaluationDomain<P::ScalarField>,
+    domain_coset: &Radix2EvaluationDomain<P::ScalarField>,
+    mut coset_eval_list: Vec<P::ScalarField>,
+) -> Result<DensePolynomial<P::ScalarField>, Error> {
+    divide_by_vanishing_poly_on_coset_in_place(domain, domain_coset, &mut coset_eval_list)?;
+    domain_coset.ifft_in_place(&mut coset_eval_list);
+
+    Ok(DensePolynomial::from_coefficients_vec(coset_eval_list))
+}
@@ -7,12 +7,16 @@ use ark_ec::CurveGroup;
 use ark_ff::FftField;
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::{DenseUVPolynomial, EvaluationDomain, Radix2EvaluationDomain};
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::io::{Read, Write};
 use ark_std::rand::Rng;
 use ark_std::{One, UniformRand, Zero};
 use blake2::{Blake2b512, Digest};
-use std::cmp::max;
-use std::collections::BTreeMap;
+use ark_std::cmp::max;
+use ark_std::collections::BTreeMap;
+use ark_std::{vec, vec::Vec};
 
 #[derive(Debug)]
 pub struct PublicParameters<P: Pairing> {
@@ -36,12 +40,31 @@ pub struct PublicParameters<P: Pairing> {
     pub poly_position_mappings: DensePolynomial<P::ScalarField>,
     pub g1_affine_position_mappings: P::G1Affine,
 
+    /// Coefficients `(a, b)` of the mapped left positions whose right value is
+    /// `a * left + b` instead of the left value itself. Other positions use `(1, 0)`.
+    pub position_coefficients: BTreeMap<usize, (P::ScalarField, P::ScalarField)>,
+    pub poly_coefficients_a: DensePolynomial<P::ScalarField>,
+    pub poly_coefficients_b: DensePolynomial<P::ScalarField>,
+    pub g1_affine_coefficients_a: P::G1Affine,
+    pub g1_affine_coefficients_b: P::G1Affine,
+
+    /// Public values that the first left column must hold at the given positions.
+    pub left_constants: BTreeMap<usize, P::ScalarField>,
+    pub poly_constant_positions: DensePolynomial<P::ScalarField>,
+    pub poly_constants: DensePolynomial<P::ScalarField>,
+    pub g1_affine_constant_positions: P::G1Affine,
+    pub g1_affine_constants: P::G1Affine,
+
     pub domain_coset_l: Radix2EvaluationDomain<P::ScalarField>,
     pub domain_coset_r: Radix2EvaluationDomain<P::ScalarField>,
 
     pub coset_eval_list_positions_left: Vec<P::ScalarField>,
     pub coset_eval_list_positions_right: Vec<P::ScalarField>,
     pub coset_eval_list_position_mappings: Vec<P::ScalarField>,
+    pub coset_eval_list_coefficients_a: Vec<P::ScalarField>,
+    pub coset_eval_list_coefficients_b: Vec<P::ScalarField>,
+    pub coset_eval_list_constant_positions: Vec<P::ScalarField>,
+    pub coset_eval_list_constants: Vec<P::ScalarField>,
     pub roots_of_unity_coset_r: Vec<P::ScalarField>,
 
     pub(crate) hash_representation: Vec<u8>,
@@ -51,6 +74,356 @@ impl<P: Pairing> PublicParameters<P> {
     pub fn builder() -> PublicParametersBuilder<P> {
         PublicParametersBuilder::<P>::default()
     }
+
+    /// The coefficients `(a, b)` of a left position, `(1, 0)` unless set.
+    pub(crate) fn coefficients(&self, i: usize) -> (P::ScalarField, P::ScalarField) {
+        self.position_coefficients
+            .get(&i)
+            .copied()
+            .unwrap_or((P::ScalarField::one(), P::ScalarField::zero()))
+    }
+
+    /// Whether a mapped position has coefficients other than `(1, 0)`. Otherwise `A = 1` and
+    /// `B = 0`, and proofs neither carry nor open them. The hash binds this through
+    /// `position_coefficients`.
+    pub fn has_coefficients(&self) -> bool {
+        let fr_one = P::ScalarField::one();
+        self.position_coefficients.values().any(|&(a, b)| a != fr_one || !b.is_zero())
+    }
+
+    /// Whether some left position is pinned to a constant. Otherwise `S = C = 0`, and proofs
+    /// neither carry nor open them or the first left column. The hash binds this through
+    /// `left_constants`.
+    pub fn has_constants(&self) -> bool {
+        !self.left_constants.is_empty()
+    }
+
+    /// Fails for parameters built with `lookup_positions`, which select left positions without
+    /// mapping them.
+    pub(crate) fn ensure_mapping(&self) -> Result<(), Error> {
+        if self.position_mappings.len() != self.positions_left.len() {
+            return Err(Error::IndexMappingCannotBeNone);
+        }
+
+        Ok(())
+    }
+
+    /// Hashes the parameters that the transcript binds. Computed on build and again on
+    /// deserialization, so it always matches the parameters in use.
+    fn compute_hash_representation(&self) -> Result<Vec<u8>, Error> {
+        // The mapping as built, recovered from the positions it was unzipped into.
+        let position_mappings: BTreeMap<usize, usize> = self.positions_left.iter().copied()
+            .zip(self.positions_right.iter().copied())
+            .collect();
+
+        let mut blake2b_hasher = Blake2b512::new();
+        let mut buf = Vec::new();
+        serialize_usize(self.size_left_values, &mut buf);
+        serialize_usize(self.size_right_values, &mut buf);
+        self.g1_affine_positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_positions_left", e))?;
+        self.g1_affine_positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_positions_right", e))?;
+        self.g1_affine_position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_position_mappings", e))?;
+        self.g1_affine_coefficients_a.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_coefficients_a", e))?;
+        self.g1_affine_coefficients_b.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_coefficients_b", e))?;
+        self.position_coefficients.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("position_coefficients", e))?;
+        self.g1_affine_constant_positions.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_constant_positions", e))?;
+        self.g1_affine_constants.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_constants", e))?;
+        self.left_constants.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("left_constants", e))?;
+        self.domain_l.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("domain_l", e))?;
+        self.domain_r.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("domain_r", e))?;
+        self.domain_coset_l.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("domain_coset_l", e))?;
+        self.domain_coset_r.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("domain_coset_r", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.g1_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_srs", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.g2_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g2_affine_srs", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("positions_left", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("positions_right", e))?;
+        blake2b_hasher.update(&buf);
+
+        self.poly_positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("poly_positions_left", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.poly_positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("poly_positions_right", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("position_mappings", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.poly_position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("poly_position_mappings", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.coset_eval_list_positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("coset_eval_list_positions_left", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.coset_eval_list_positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("coset_eval_list_positions_right", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.coset_eval_list_position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("coset_eval_list_position_mappings", e))?;
+        blake2b_hasher.update(&buf);
+        buf.clear();
+
+        self.roots_of_unity_coset_r.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("roots_of_unity_coset_r", e))?;
+        blake2b_hasher.update(&buf);
+
+        Ok(blake2b_hasher.finalize().to_vec())
+    }
+}
+
+// The hash representation is not serialized. It is recomputed on deserialization so that it
+// always binds the parameters the verifier uses, as for [`crate::statement::Statement`].
+impl<P: Pairing> CanonicalSerialize for PublicParameters<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.size_left_values.serialize_with_mode(&mut writer, compress)?;
+        self.size_right_values.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_srs.serialize_with_mode(&mut writer, compress)?;
+        self.g2_affine_srs.serialize_with_mode(&mut writer, compress)?;
+        self.domain_l.serialize_with_mode(&mut writer, compress)?;
+        self.domain_r.serialize_with_mode(&mut writer, compress)?;
+        self.positions_left.serialize_with_mode(&mut writer, compress)?;
+        self.positions_right.serialize_with_mode(&mut writer, compress)?;
+        self.poly_positions_left.serialize_with_mode(&mut writer, compress)?;
+        self.poly_positions_right.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_positions_left.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_positions_right.serialize_with_mode(&mut writer, compress)?;
+        self.position_mappings.serialize_with_mode(&mut writer, compress)?;
+        self.poly_position_mappings.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_position_mappings.serialize_with_mode(&mut writer, compress)?;
+        self.position_coefficients.serialize_with_mode(&mut writer, compress)?;
+        self.poly_coefficients_a.serialize_with_mode(&mut writer, compress)?;
+        self.poly_coefficients_b.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_coefficients_a.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_coefficients_b.serialize_with_mode(&mut writer, compress)?;
+        self.left_constants.serialize_with_mode(&mut writer, compress)?;
+        self.poly_constant_positions.serialize_with_mode(&mut writer, compress)?;
+        self.poly_constants.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_constant_positions.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_constants.serialize_with_mode(&mut writer, compress)?;
+        self.domain_coset_l.serialize_with_mode(&mut writer, compress)?;
+        self.domain_coset_r.serialize_with_mode(&mut writer, compress)?;
+        self.coset_eval_list_positions_left.serialize_with_mode(&mut writer, compress)?;
+        self.coset_eval_list_positions_right.serialize_with_mode(&mut writer, compress)?;
+        self.coset_eval_list_position_mappings.serialize_with_mode(&mut writer, compress)?;
+        self.coset_eval_list_coefficients_a.serialize_with_mode(&mut writer, compress)?;
+        self.coset_eval_list_coefficients_b.serialize_with_mode(&mut writer, compress)?;
+        self.coset_eval_list_constant_positions.serialize_with_mode(&mut writer, compress)?;
+        self.coset_eval_list_constants.serialize_with_mode(&mut writer, compress)?;
+        self.roots_of_unity_coset_r.serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.size_left_values.serialized_size(compress)
+            + self.size_right_values.serialized_size(compress)
+            + self.g1_affine_srs.serialized_size(compress)
+            + self.g2_affine_srs.serialized_size(compress)
+            + self.domain_l.serialized_size(compress)
+            + self.domain_r.serialized_size(compress)
+            + self.positions_left.serialized_size(compress)
+            + self.positions_right.serialized_size(compress)
+            + self.poly_positions_left.serialized_size(compress)
+            + self.poly_positions_right.serialized_size(compress)
+            + self.g1_affine_positions_left.serialized_size(compress)
+            + self.g1_affine_positions_right.serialized_size(compress)
+            + self.position_mappings.serialized_size(compress)
+            + self.poly_position_mappings.serialized_size(compress)
+            + self.g1_affine_position_mappings.serialized_size(compress)
+            + self.position_coefficients.serialized_size(compress)
+            + self.poly_coefficients_a.serialized_size(compress)
+            + self.poly_coefficients_b.serialized_size(compress)
+            + self.g1_affine_coefficients_a.serialized_size(compress)
+            + self.g1_affine_coefficients_b.serialized_size(compress)
+            + self.left_constants.serialized_size(compress)
+            + self.poly_constant_positions.serialized_size(compress)
+            + self.poly_constants.serialized_size(compress)
+            + self.g1_affine_constant_positions.serialized_size(compress)
+            + self.g1_affine_constants.serialized_size(compress)
+            + self.domain_coset_l.serialized_size(compress)
+            + self.domain_coset_r.serialized_size(compress)
+            + self.coset_eval_list_positions_left.serialized_size(compress)
+            + self.coset_eval_list_positions_right.serialized_size(compress)
+            + self.coset_eval_list_position_mappings.serialized_size(compress)
+            + self.coset_eval_list_coefficients_a.serialized_size(compress)
+            + self.coset_eval_list_coefficients_b.serialized_size(compress)
+            + self.coset_eval_list_constant_positions.serialized_size(compress)
+            + self.coset_eval_list_constants.serialized_size(compress)
+            + self.roots_of_unity_coset_r.serialized_size(compress)
+    }
+}
+
+impl<P: Pairing> Valid for PublicParameters<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.size_left_values.check()?;
+        self.size_right_values.check()?;
+        self.g1_affine_srs.check()?;
+        self.g2_affine_srs.check()?;
+        self.domain_l.check()?;
+        self.domain_r.check()?;
+        self.positions_left.check()?;
+        self.positions_right.check()?;
+        self.poly_positions_left.check()?;
+        self.poly_positions_right.check()?;
+        self.g1_affine_positions_left.check()?;
+        self.g1_affine_positions_right.check()?;
+        self.position_mappings.check()?;
+        self.poly_position_mappings.check()?;
+        self.g1_affine_position_mappings.check()?;
+        self.position_coefficients.check()?;
+        self.poly_coefficients_a.check()?;
+        self.poly_coefficients_b.check()?;
+        self.g1_affine_coefficients_a.check()?;
+        self.g1_affine_coefficients_b.check()?;
+        self.left_constants.check()?;
+        self.poly_constant_positions.check()?;
+        self.poly_constants.check()?;
+        self.g1_affine_constant_positions.check()?;
+        self.g1_affine_constants.check()?;
+        self.domain_coset_l.check()?;
+        self.domain_coset_r.check()?;
+        self.coset_eval_list_positions_left.check()?;
+        self.coset_eval_list_positions_right.check()?;
+        self.coset_eval_list_position_mappings.check()?;
+        self.coset_eval_list_coefficients_a.check()?;
+        self.coset_eval_list_coefficients_b.check()?;
+        self.coset_eval_list_constant_positions.check()?;
+        self.coset_eval_list_constants.check()?;
+        self.roots_of_unity_coset_r.check()?;
+
+        Ok(())
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for PublicParameters<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let size_left_values = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let size_right_values = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_srs = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g2_affine_srs = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let domain_l = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let domain_r = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let positions_left = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let positions_right = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let poly_positions_left = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let poly_positions_right = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_positions_left = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_positions_right = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let position_mappings = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let poly_position_mappings = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_position_mappings = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let position_coefficients = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let poly_coefficients_a = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let poly_coefficients_b = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_coefficients_a = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_coefficients_b = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let left_constants = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let poly_constant_positions = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let poly_constants = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_constant_positions = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_constants = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let domain_coset_l = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let domain_coset_r = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let coset_eval_list_positions_left = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let coset_eval_list_positions_right = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let coset_eval_list_position_mappings = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let coset_eval_list_coefficients_a = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let coset_eval_list_coefficients_b = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let coset_eval_list_constant_positions = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let coset_eval_list_constants = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let roots_of_unity_coset_r = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        let mut pp = PublicParameters {
+            size_left_values,
+            size_right_values,
+            g1_affine_srs,
+            g2_affine_srs,
+            domain_l,
+            domain_r,
+            positions_left,
+            positions_right,
+            poly_positions_left,
+            poly_positions_right,
+            g1_affine_positions_left,
+            g1_affine_positions_right,
+            position_mappings,
+            poly_position_mappings,
+            g1_affine_position_mappings,
+            position_coefficients,
+            poly_coefficients_a,
+            poly_coefficients_b,
+            g1_affine_coefficients_a,
+            g1_affine_coefficients_b,
+            left_constants,
+            poly_constant_positions,
+            poly_constants,
+            g1_affine_constant_positions,
+            g1_affine_constants,
+            domain_coset_l,
+            domain_coset_r,
+            coset_eval_list_positions_left,
+            coset_eval_list_positions_right,
+            coset_eval_list_position_mappings,
+            coset_eval_list_coefficients_a,
+            coset_eval_list_coefficients_b,
+            coset_eval_list_constant_positions,
+            coset_eval_list_constants,
+            roots_of_unity_coset_r,
+            hash_representation: Vec::new(),
+        };
+        pp.hash_representation = pp.compute_hash_representation()
+            .map_err(|_| SerializationError::InvalidData)?;
+
+        Ok(pp)
+    }
 }
 
 pub struct PublicParametersBuilder<P: Pairing> {
@@ -60,6 +433,10 @@ pub struct PublicParametersBuilder<P: Pairing> {
     domain_generator_l: Option<P::ScalarField>,
     domain_generator_r: Option<P::ScalarField>,
     position_mappings: Option<BTreeMap<usize, usize>>,
+    unpaired_positions: Option<(Vec<usize>, Vec<usize>)>,
+    lookup_positions: Option<Vec<usize>>,
+    position_coefficients: BTreeMap<usize, (P::ScalarField, P::ScalarField)>,
+    left_constants: BTreeMap<usize, P::ScalarField>,
 }
 
 impl<P: Pairing> PublicParametersBuilder<P> {
@@ -71,6 +448,10 @@ impl<P: Pairing> PublicParametersBuilder<P> {
             domain_generator_l: None,
             domain_generator_r: None,
             position_mappings: None,
+            unpaired_positions: None,
+            lookup_positions: None,
+            position_coefficients: BTreeMap::new(),
+            left_constants: BTreeMap::new(),
         }
     }
 
@@ -104,6 +485,39 @@ impl<P: Pairing> PublicParametersBuilder<P> {
         self
     }
 
+    /// Selects left and right positions without pairing them, for [`crate::shuffle`]. The
+    /// positions are paired in ascending order, which the shuffle argument ignores. Unused if
+    /// `position_mappings` is set.
+    pub fn unpaired_positions(mut self, positions_left: &[usize], positions_right: &[usize]) -> Self {
+        self.unpaired_positions = Some((positions_left.to_vec(), positions_right.to_vec()));
+        self
+    }
+
+    /// Selects only left positions, for [`crate::lookup`], which needs no right positions. The
+    /// parameters then hold no mapping and serve no other argument. Unused if
+    /// `position_mappings` or `unpaired_positions` is set.
+    pub fn lookup_positions(mut self, positions_left: &[usize]) -> Self {
+        self.lookup_positions = Some(positions_left.to_vec());
+        self
+    }
+
+    /// Sets coefficients `(a, b)` for mapped left positions, so that the proof shows
+    /// `right[j] = a * left[i] + b` for them instead of equality.
+    pub fn position_coefficients(
+        mut self,
+        coefficients: &BTreeMap<usize, (P::ScalarField, P::ScalarField)>,
+    ) -> Self {
+        self.position_coefficients = coefficients.clone();
+        self
+    }
+
+    /// Pins positions of the first left column to public values, e.g. a protocol version or
+    /// a zero padding slot. The positions need not be mapped.
+    pub fn left_constants(mut self, constants: &BTreeMap<usize, P::ScalarField>) -> Self {
+        self.left_constants = constants.clone();
+        self
+    }
+
     pub fn build<R: Rng + ?Sized>(self, rng: &mut R) -> Result<PublicParameters<P>, Error> {
         let size_left_values = self.size_left_values.ok_or(Error::MissingParameter("Left \
         Element Size"))?;
@@ -111,7 +525,36 @@ impl<P: Pairing> PublicParametersBuilder<P> {
         let size_right_values = self.size_right_values.ok_or(Error::MissingParameter("Right \
         Element Size"))?;
         validate_input(size_right_values, None)?;
-        let pow_of_tau_g1 = max(size_left_values, size_right_values);
+        let (position_mappings, lookup_positions) =
+            match (self.position_mappings, self.unpaired_positions, self.lookup_positions) {
+                (Some(mappings), _, _) => (mappings, None),
+                (None, Some((positions_left, positions_right)), _) => {
+                    (pair_in_order(positions_left, positions_right)?, None)
+                }
+                (None, None, Some(positions_left)) => {
+                    (BTreeMap::new(), Some(lookup_only(positions_left, size_left_values)?))
+                }
+                (None, None, None) => return Err(Error::IndexMappingCannotBeNone),
+            };
+        let position_coefficients = self.position_coefficients;
+        if let Some(&i) = position_coefficients.keys().find(|i| !position_mappings.contains_key(i)) {
+            return Err(Error::CoefficientsForUnmappedPosition(i));
+        }
+        let left_constants = self.left_constants;
+        if let Some(&i) = left_constants.keys().find(|&&i| i >= size_left_values) {
+            return Err(Error::ConstantPositionOutOfRange(i));
+        }
+
+        // A non-trivial `a` multiplies the left values inside the left identity, which doubles
+        // the degree of its quotient. The quotient is then computed over a coset twice the size
+        // of the left domain, and the SRS grows accordingly.
+        let fr_one = P::ScalarField::one();
+        let size_coset_l = if position_coefficients.values().all(|&(a, _)| a == fr_one) {
+            size_left_values
+        } else {
+            2 * size_left_values
+        };
+        let pow_of_tau_g1 = max(size_coset_l, size_right_values);
 
         let tau = self.tau.unwrap_or(P::ScalarField::rand(rng));
         let (g1_affine_srs, g2_affine_srs) = unsafe_setup_from_tau::<P, R>(pow_of_tau_g1, tau);
@@ -119,28 +562,19 @@ impl<P: Pairing> PublicParametersBuilder<P> {
         let domain_l = create_domain::<P>(self.domain_generator_l, size_left_values)?;
         let domain_r = create_domain::<P>(self.domain_generator_r, size_right_values)?;
 
-        let position_mappings = self.position_mappings.ok_or(Error::IndexMappingCannotBeNone)?;
-        let (positions_left, positions_right): (Vec<_>, Vec<_>) = position_mappings.iter()
+        let (mut positions_left, positions_right): (Vec<_>, Vec<_>) = position_mappings.iter()
             .map(|(&key, &value)| (key, value))
             .unzip();
+        if let Some(lookup_positions) = lookup_positions {
+            positions_left = lookup_positions;
+        }
 
         let fr_zero = P::ScalarField::zero();
-        let fr_one = P::ScalarField::one();
-        let mut poly_eval_positions_left = vec![fr_zero; size_left_values];
-        positions_left.iter().for_each(|&i| {
-            poly_eval_positions_left[i] = fr_one;
-        });
-        let coeff_positions_left = domain_l.ifft(&poly_eval_positions_left);
-        let poly_positions_left = DensePolynomial::from_coefficients_vec(coeff_positions_left);
+        let poly_positions_left = indicator_poly(&domain_l, &positions_left);
         let g2_affine_positions_left = Kzg::<P::G1>::commit(&g1_affine_srs, &poly_positions_left)
             .into_affine();
 
-        let mut poly_eval_positions_right = vec![fr_zero; size_right_values];
-        positions_right.iter().for_each(|&i| {
-            poly_eval_positions_right[i] = fr_one;
-        });
-        let coeff_positions_right = domain_r.ifft(&poly_eval_positions_right);
-        let poly_positions_right = DensePolynomial::from_coefficients_vec(coeff_positions_right);
+        let poly_positions_right = indicator_poly(&domain_r, &positions_right);
         let g2_affine_positions_right = Kzg::<P::G1>::commit(&g1_affine_srs,
                                                              &poly_positions_right).into_affine();
 
@@ -159,92 +593,47 @@ impl<P: Pairing> PublicParametersBuilder<P> {
         let g1_affine_position_mappings = Kzg::<P::G1>::commit(&g1_affine_srs,
                                                                &poly_position_mappings).into_affine();
 
-        let domain_coset_l = domain_l.get_coset(P::ScalarField::GENERATOR)
+        let mut poly_eval_coefficients_a = vec![fr_one; size_left_values];
+        let mut poly_eval_coefficients_b = vec![fr_zero; size_left_values];
+        position_coefficients.iter().for_each(|(&i, &(a, b))| {
+            poly_eval_coefficients_a[i] = a;
+            poly_eval_coefficients_b[i] = b;
+        });
+        let poly_coefficients_a = DensePolynomial::from_coefficients_vec(domain_l.ifft(&poly_eval_coefficients_a));
+        let poly_coefficients_b = DensePolynomial::from_coefficients_vec(domain_l.ifft(&poly_eval_coefficients_b));
+        let g1_affine_coefficients_a = Kzg::<P::G1>::commit(&g1_affine_srs, &poly_coefficients_a).into_affine();
+        let g1_affine_coefficients_b = Kzg::<P::G1>::commit(&g1_affine_srs, &poly_coefficients_b).into_affine();
+
+        // The constants polynomial is zero outside the pinned positions, so the identity is
+        // `S(X) * V(X) - C(X) = 0` over the left domain.
+        let mut poly_eval_constant_positions = vec![fr_zero; size_left_values];
+        let mut poly_eval_constants = vec![fr_zero; size_left_values];
+        left_constants.iter().for_each(|(&i, &c)| {
+            poly_eval_constant_positions[i] = fr_one;
+            poly_eval_constants[i] = c;
+        });
+        let poly_constant_positions = DensePolynomial::from_coefficients_vec(domain_l.ifft(&poly_eval_constant_positions));
+        let poly_constants = DensePolynomial::from_coefficients_vec(domain_l.ifft(&poly_eval_constants));
+        let g1_affine_constant_positions = Kzg::<P::G1>::commit(&g1_affine_srs, &poly_constant_positions).into_affine();
+        let g1_affine_constants = Kzg::<P::G1>::commit(&g1_affine_srs, &poly_constants).into_affine();
+
+        let domain_coset_l = Radix2EvaluationDomain::<P::ScalarField>::new(size_coset_l)
+            .ok_or(Error::FailedToCreateEvaluationDomain)?
+            .get_coset(P::ScalarField::GENERATOR)
             .ok_or(Error::FailedToCreateCosetOfEvaluationDomain)?;
         let domain_coset_r = domain_r.get_coset(P::ScalarField::GENERATOR)
             .ok_or(Error::FailedToCreateCosetOfEvaluationDomain)?;
         let coset_eval_list_positions_left = domain_coset_l.fft(&poly_positions_left);
         let coset_eval_list_positions_right = domain_coset_r.fft(&poly_positions_right);
         let coset_eval_list_position_mappings = domain_coset_l.fft(&poly_position_mappings);
+        let coset_eval_list_coefficients_a = domain_coset_l.fft(&poly_coefficients_a);
+        let coset_eval_list_coefficients_b = domain_coset_l.fft(&poly_coefficients_b);
+        let coset_eval_list_constant_positions = domain_coset_l.fft(&poly_constant_positions);
+        let coset_eval_list_constants = domain_coset_l.fft(&poly_constants);
         let roots_of_unity_coset_r = roots_of_unity::<P>(&domain_coset_r);
 
-        // Construct Hash Representation.
-        let mut blake2b_hasher = Blake2b512::new();
-        let mut buf = Vec::new();
-        serialize_usize(size_left_values, &mut buf);
-        serialize_usize(size_right_values, &mut buf);
-        g2_affine_positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        g2_affine_positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        g1_affine_position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        domain_l.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_| Error::FailedToSerializeElement)?;
-        domain_r.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_| Error::FailedToSerializeElement)?;
-        domain_coset_l.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_| Error::FailedToSerializeElement)?;
-        domain_coset_r.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_| Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        g1_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_| Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        g2_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_| Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
 
-        positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-
-        poly_positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        poly_positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        poly_position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        coset_eval_list_positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        coset_eval_list_positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        coset_eval_list_position_mappings.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-        buf.clear();
-
-        roots_of_unity_coset_r.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_|
-            Error::FailedToSerializeElement)?;
-        blake2b_hasher.update(&buf);
-
-        let hash_representation = blake2b_hasher.finalize().to_vec();
-
-        Ok(PublicParameters {
+        let mut pp = PublicParameters {
             size_left_values,
             size_right_values,
             g1_affine_srs,
@@ -260,14 +649,31 @@ impl<P: Pairing> PublicParametersBuilder<P> {
             position_mappings: fr_position_mappings,
             poly_position_mappings,
             g1_affine_position_mappings,
-            hash_representation,
+            position_coefficients,
+            poly_coefficients_a,
+            poly_coefficients_b,
+            g1_affine_coefficients_a,
+            g1_affine_coefficients_b,
+            left_constants,
+            poly_constant_positions,
+            poly_constants,
+            g1_affine_constant_positions,
+            g1_affine_constants,
+            hash_representation: Vec::new(),
             domain_coset_l,
             domain_coset_r,
             coset_eval_list_positions_left,
             coset_eval_list_positions_right,
             coset_eval_list_position_mappings,
+            coset_eval_list_coefficients_a,
+            coset_eval_list_coefficients_b,
+            coset_eval_list_constant_positions,
+            coset_eval_list_constants,
             roots_of_unity_coset_r,
-        })
+        };
+        pp.hash_representation = pp.compute_hash_representation()?;
+
+        Ok(pp)
     }
 }
 
@@ -276,14 +682,54 @@ fn validate_input(input: usize, max_limit: Option<usize>) -> Result<(), Error> {
         return Err(Error::InputShouldBePowerOfTwo(input));
     }
 
-    if max_limit.map_or(false, |max| input > max) {
+    if max_limit.is_some_and(|max| input > max) {
         return Err(Error::InputIsTooLarge(input));
     }
 
     Ok(())
 }
 
+/// Interpolates over `domain` the polynomial that is one at `positions` and zero elsewhere.
+pub(crate) fn indicator_poly<F: FftField>(
+    domain: &Radix2EvaluationDomain<F>,
+    positions: &[usize],
+) -> DensePolynomial<F> {
+    let mut poly_eval_list = vec![F::zero(); domain.size()];
+    positions.iter().for_each(|&i| poly_eval_list[i] = F::one());
+
+    DensePolynomial::from_coefficients_vec(domain.ifft(&poly_eval_list))
+}
+
+fn lookup_only(mut positions_left: Vec<usize>, size_left_values: usize) -> Result<Vec<usize>, Error> {
+    positions_left.sort_unstable();
+    positions_left.dedup();
+    if positions_left.is_empty() {
+        return Err(Error::LeftIndicesCannotBeNone);
+    }
+    if let Some(&i) = positions_left.iter().find(|&&i| i >= size_left_values) {
+        return Err(Error::InputIsTooLarge(i));
+    }
+
+    Ok(positions_left)
+}
+
+fn pair_in_order(
+    mut positions_left: Vec<usize>,
+    mut positions_right: Vec<usize>,
+) -> Result<BTreeMap<usize, usize>, Error> {
+    positions_left.sort_unstable();
+    positions_left.dedup();
+    positions_right.sort_unstable();
+    positions_right.dedup();
+    if positions_left.len() != positions_right.len() {
+        return Err(Error::UnequalNumberOfPositions(positions_left.len(), positions_right.len()));
+    }
+
+    Ok(positions_left.into_iter().zip(positions_right).collect())
+}
+
 
+/// Writes `input` as eight bytes, so the hash is the same on 32-bit targets such as wasm32.
 fn serialize_usize(input: usize, buf: &mut Vec<u8>) {
-    buf.extend_from_slice(&input.to_le_bytes());
+    buf.extend_from_slice(&(input as u64).to_le_bytes());
 }
\ No newline at end of file
@@ -0,0 +1,129 @@
+//! Proves that the multiset of left values at `positions_left` equals the multiset of right
+//! values at `positions_right`, without fixing which left position goes where.
+//!
+//! This is the argument of [`crate::prover::prove`] without `gamma`:
+//! `sum_{i in positions_left} 1 / (beta + v_i) = sum_{j in positions_right} 1 / (beta + w_j)`.
+//! Only the indicator polynomials of the public parameters are used.
+
+use crate::error::Error;
+use crate::kzg::powers_of_scalars;
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::sumcheck::{self, Half, SumcheckProof};
+use crate::transcript::{Label, Transcript};
+use crate::witness::{fold_columns, Witness};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::One;
+use ark_poly::EvaluationDomain;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ShuffleProof<P: Pairing> {
+    pub(crate) sumcheck: SumcheckProof<P>,
+}
+
+pub fn prove<P: Pairing>(
+    pp: &PublicParameters<P>,
+    witness: &Witness<P>,
+    statement: &Statement<P>,
+) -> Result<ShuffleProof<P>, Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    // Sample random alpha, beta.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+
+    let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+    let (left_values, poly_left_values) =
+        fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+    let (right_values, poly_right_values) =
+        fold_columns(&witness.right_values, &witness.poly_right_values, &powers_of_alpha);
+
+    // The halves sum `1 / (beta + v_i)` over the selected positions of each side.
+    let fr_one = P::ScalarField::one();
+    let coset_eval_list_left_values = pp.domain_coset_l.fft(&poly_left_values);
+    let left = Half {
+        domain: &pp.domain_l,
+        domain_coset: &pp.domain_coset_l,
+        fraction_list: pp.positions_left.iter().map(|&i| (i, fr_one, beta + left_values[i])).collect(),
+        numerator: |k: usize| pp.coset_eval_list_positions_left[k],
+        denominator: |k: usize| beta + coset_eval_list_left_values[k],
+    };
+    let coset_eval_list_right_values = pp.domain_coset_r.fft(&poly_right_values);
+    let right = Half {
+        domain: &pp.domain_r,
+        domain_coset: &pp.domain_coset_r,
+        fraction_list: pp.positions_right.iter().map(|&j| (j, fr_one, beta + right_values[j])).collect(),
+        numerator: |k: usize| pp.coset_eval_list_positions_right[k],
+        denominator: |k: usize| beta + coset_eval_list_right_values[k],
+    };
+
+    let sumcheck = sumcheck::prove(
+        &mut transcript,
+        &pp.g1_affine_srs,
+        left,
+        right,
+        &[
+            (Label::FrLvAtDelta, &poly_left_values),
+            (Label::FrRvAtDelta, &poly_right_values),
+            (Label::FrPlAtDelta, &pp.poly_positions_left),
+            (Label::FrPrAtDelta, &pp.poly_positions_right),
+        ],
+    )?;
+
+    Ok(ShuffleProof { sumcheck })
+}
+
+pub fn verify<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &Statement<P>,
+    proof: &ShuffleProof<P>,
+) -> Result<(), Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    if statement.g1_affine_left_values.is_empty()
+        || statement.g1_affine_left_values.len() != statement.g1_affine_right_values.len()
+    {
+        return Err(Error::WrongNumberOfColumns(statement.g1_affine_right_values.len()));
+    }
+    let &[lv_at_delta, rv_at_delta, pl_at_delta, pr_at_delta] = &proof.sumcheck.opened_at_delta[..] else {
+        return Err(Error::MalformedProof("evaluations at delta"));
+    };
+
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+    let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
+
+    // Fold the column commitments as the prover folded the columns.
+    let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+    let g1_affine_left_values = P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha)
+        .into_affine();
+    let g1_affine_right_values = P::G1::msm_unchecked(&statement.g1_affine_right_values, &powers_of_alpha)
+        .into_affine();
+
+    sumcheck::verify(
+        &mut transcript,
+        &pp.g1_affine_srs,
+        &pp.g2_affine_srs,
+        pp.size_left_values,
+        pp.size_right_values,
+        &proof.sumcheck,
+        &[
+            (Label::FrLvAtDelta, g1_affine_left_values),
+            (Label::FrRvAtDelta, g1_affine_right_values),
+            (Label::FrPlAtDelta, pp.g1_affine_positions_left),
+            (Label::FrPrAtDelta, pp.g1_affine_positions_right),
+        ],
+        (pl_at_delta, beta + lv_at_delta),
+        (pr_at_delta, beta + rv_at_delta),
+    )
+}
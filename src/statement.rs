@@ -4,25 +4,29 @@ use crate::public_parameters::PublicParameters;
 use crate::witness::Witness;
 use crate::COMPRESS_MOD;
 use ark_ec::pairing::Pairing;
+use ark_std::io::{Read, Write};
+use ark_std::vec::Vec;
 use ark_ec::CurveGroup;
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
 use blake2::{Blake2b512, Digest};
 
+#[derive(Debug)]
 pub struct Statement<P: Pairing> {
-    pub(crate) g1_affine_left_values: P::G1Affine,
-    pub(crate) g1_affine_right_values: P::G1Affine,
+    pub(crate) g1_affine_left_values: Vec<P::G1Affine>,
+    pub(crate) g1_affine_right_values: Vec<P::G1Affine>,
     pub(crate) hash_representation: Vec<u8>,
 }
 
-impl<P: Pairing> Witness<P> {
-    pub fn generate_statement(&self, pp: &PublicParameters<P>) -> Result<Statement<P>, Error> {
-        let g1_affine_srs = &pp.g1_affine_srs;
-        let g1_affine_left_values = Kzg::<P::G1>::commit(g1_affine_srs, &self.poly_left_values).into_affine();
-        let g1_affine_right_values = Kzg::<P::G1>::commit(g1_affine_srs, &self.poly_right_values).into_affine();
-
+impl<P: Pairing> Statement<P> {
+    pub(crate) fn from_commitments(
+        g1_affine_left_values: Vec<P::G1Affine>,
+        g1_affine_right_values: Vec<P::G1Affine>,
+    ) -> Result<Self, Error> {
         let mut buf = Vec::new();
-        g1_affine_left_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_| Error::FailedToSerializeElement)?;
-        g1_affine_right_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|_| Error::FailedToSerializeElement)?;
+        g1_affine_left_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e| Error::FailedToSerializeElement("g1_affine_left_values", e))?;
+        g1_affine_right_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e| Error::FailedToSerializeElement("g1_affine_right_values", e))?;
         let mut hasher = Blake2b512::new();
         hasher.update(&buf);
         let hash_representation = hasher.finalize().to_vec();
@@ -33,4 +37,66 @@ impl<P: Pairing> Witness<P> {
             hash_representation,
         })
     }
-}
\ No newline at end of file
+}
+
+impl<P: Pairing> Statement<P> {
+    pub fn num_columns(&self) -> usize {
+        self.g1_affine_left_values.len()
+    }
+}
+
+impl<P: Pairing> Witness<P> {
+    pub fn generate_statement(&self, pp: &PublicParameters<P>) -> Result<Statement<P>, Error> {
+        self.commit_columns(&pp.g1_affine_srs)
+    }
+
+    pub(crate) fn commit_columns(&self, g1_affine_srs: &[P::G1Affine]) -> Result<Statement<P>, Error> {
+        let g1_affine_left_values = self.poly_left_values.iter()
+            .map(|poly| Kzg::<P::G1>::commit(g1_affine_srs, poly).into_affine())
+            .collect();
+        let g1_affine_right_values = self.poly_right_values.iter()
+            .map(|poly| Kzg::<P::G1>::commit(g1_affine_srs, poly).into_affine())
+            .collect();
+
+        Statement::from_commitments(g1_affine_left_values, g1_affine_right_values)
+    }
+}
+
+// Only the commitments are serialized. The hash representation is recomputed on
+// deserialization so that it always binds the commitments the verifier uses.
+impl<P: Pairing> CanonicalSerialize for Statement<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.g1_affine_left_values.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_right_values.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.g1_affine_left_values.serialized_size(compress)
+            + self.g1_affine_right_values.serialized_size(compress)
+    }
+}
+
+impl<P: Pairing> Valid for Statement<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.g1_affine_left_values.check()?;
+        self.g1_affine_right_values.check()
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for Statement<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let g1_affine_left_values = Vec::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_right_values = Vec::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        Statement::from_commitments(g1_affine_left_values, g1_affine_right_values)
+            .map_err(|_| SerializationError::InvalidData)
+    }
+}
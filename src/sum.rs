@@ -0,0 +1,419 @@
+//! Proves that the left values at `positions_left` sum to the same as the right values at
+//! `positions_right`.
+//!
+//! `L` equals `P_L * V` over the left domain, so `n_l * L(0)` is the sum of the selected left
+//! values, and likewise for `R`. The identities `L - P_L * V = 0` and `R - P_R * W = 0` are
+//! proven with one quotient each and the sums compared as in [`crate::verifier::verify`]. The
+//! proof reveals `L(0)` and thus the common sum.
+
+use crate::error::Error;
+use crate::kzg::{powers_of_scalars, unsafe_setup_from_tau, Kzg};
+use crate::public_parameters::indicator_poly;
+use crate::statement::Statement;
+use crate::sumcheck::{self, Half, SumcheckProof};
+use crate::transcript::{Label, Transcript};
+use crate::witness::{fold_columns, Witness};
+use crate::COMPRESS_MOD;
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{FftField, One};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::cmp::max;
+use ark_std::io::{Read, Write};
+use ark_std::rand::Rng;
+use ark_std::{vec::Vec, UniformRand};
+use blake2::{Blake2b512, Digest};
+
+/// Public parameters selecting the positions whose sums are compared.
+#[derive(Debug)]
+pub struct SumParameters<P: Pairing> {
+    pub size_left_values: usize,
+    pub size_right_values: usize,
+
+    pub g1_affine_srs: Vec<P::G1Affine>,
+    pub g2_affine_srs: Vec<P::G2Affine>,
+
+    pub domain_l: Radix2EvaluationDomain<P::ScalarField>,
+    pub domain_r: Radix2EvaluationDomain<P::ScalarField>,
+
+    pub positions_left: Vec<usize>,
+    pub positions_right: Vec<usize>,
+    pub poly_positions_left: DensePolynomial<P::ScalarField>,
+    pub poly_positions_right: DensePolynomial<P::ScalarField>,
+    pub g1_affine_positions_left: P::G1Affine,
+    pub g1_affine_positions_right: P::G1Affine,
+
+    pub domain_coset_l: Radix2EvaluationDomain<P::ScalarField>,
+    pub domain_coset_r: Radix2EvaluationDomain<P::ScalarField>,
+    pub coset_eval_list_positions_left: Vec<P::ScalarField>,
+    pub coset_eval_list_positions_right: Vec<P::ScalarField>,
+
+    pub(crate) hash_representation: Vec<u8>,
+}
+
+impl<P: Pairing> SumParameters<P> {
+    pub fn builder() -> SumParametersBuilder<P> {
+        SumParametersBuilder::<P>::default()
+    }
+
+    fn compute_hash_representation(&self) -> Result<Vec<u8>, Error> {
+        let mut buf = Vec::new();
+        self.size_left_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("size_left_values", e))?;
+        self.size_right_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("size_right_values", e))?;
+        self.positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("positions_left", e))?;
+        self.positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("positions_right", e))?;
+        self.g1_affine_positions_left.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_positions_left", e))?;
+        self.g1_affine_positions_right.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_positions_right", e))?;
+        self.g1_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_srs", e))?;
+        self.g2_affine_srs.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g2_affine_srs", e))?;
+
+        Ok(Blake2b512::digest(&buf).to_vec())
+    }
+}
+
+// As for [`crate::public_parameters::PublicParameters`], the hash representation is recomputed
+// on deserialization instead of being read.
+impl<P: Pairing> CanonicalSerialize for SumParameters<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.size_left_values.serialize_with_mode(&mut writer, compress)?;
+        self.size_right_values.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_srs.serialize_with_mode(&mut writer, compress)?;
+        self.g2_affine_srs.serialize_with_mode(&mut writer, compress)?;
+        self.domain_l.serialize_with_mode(&mut writer, compress)?;
+        self.domain_r.serialize_with_mode(&mut writer, compress)?;
+        self.positions_left.serialize_with_mode(&mut writer, compress)?;
+        self.positions_right.serialize_with_mode(&mut writer, compress)?;
+        self.poly_positions_left.serialize_with_mode(&mut writer, compress)?;
+        self.poly_positions_right.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_positions_left.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_positions_right.serialize_with_mode(&mut writer, compress)?;
+        self.domain_coset_l.serialize_with_mode(&mut writer, compress)?;
+        self.domain_coset_r.serialize_with_mode(&mut writer, compress)?;
+        self.coset_eval_list_positions_left.serialize_with_mode(&mut writer, compress)?;
+        self.coset_eval_list_positions_right.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.size_left_values.serialized_size(compress)
+            + self.size_right_values.serialized_size(compress)
+            + self.g1_affine_srs.serialized_size(compress)
+            + self.g2_affine_srs.serialized_size(compress)
+            + self.domain_l.serialized_size(compress)
+            + self.domain_r.serialized_size(compress)
+            + self.positions_left.serialized_size(compress)
+            + self.positions_right.serialized_size(compress)
+            + self.poly_positions_left.serialized_size(compress)
+            + self.poly_positions_right.serialized_size(compress)
+            + self.g1_affine_positions_left.serialized_size(compress)
+            + self.g1_affine_positions_right.serialized_size(compress)
+            + self.domain_coset_l.serialized_size(compress)
+            + self.domain_coset_r.serialized_size(compress)
+            + self.coset_eval_list_positions_left.serialized_size(compress)
+            + self.coset_eval_list_positions_right.serialized_size(compress)
+    }
+}
+
+impl<P: Pairing> Valid for SumParameters<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.g1_affine_srs.check()?;
+        self.g2_affine_srs.check()?;
+        self.domain_l.check()?;
+        self.domain_r.check()?;
+        self.poly_positions_left.check()?;
+        self.poly_positions_right.check()?;
+        self.g1_affine_positions_left.check()?;
+        self.g1_affine_positions_right.check()?;
+        self.domain_coset_l.check()?;
+        self.domain_coset_r.check()?;
+        self.coset_eval_list_positions_left.check()?;
+        self.coset_eval_list_positions_right.check()
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for SumParameters<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut pp = SumParameters {
+            size_left_values: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            size_right_values: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            g1_affine_srs: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            g2_affine_srs: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_l: Radix2EvaluationDomain::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_r: Radix2EvaluationDomain::deserialize_with_mode(&mut reader, compress, validate)?,
+            positions_left: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            positions_right: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            poly_positions_left: DensePolynomial::deserialize_with_mode(&mut reader, compress, validate)?,
+            poly_positions_right: DensePolynomial::deserialize_with_mode(&mut reader, compress, validate)?,
+            g1_affine_positions_left: P::G1Affine::deserialize_with_mode(&mut reader, compress, validate)?,
+            g1_affine_positions_right: P::G1Affine::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_coset_l: Radix2EvaluationDomain::deserialize_with_mode(&mut reader, compress, validate)?,
+            domain_coset_r: Radix2EvaluationDomain::deserialize_with_mode(&mut reader, compress, validate)?,
+            coset_eval_list_positions_left: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            coset_eval_list_positions_right: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            hash_representation: Vec::new(),
+        };
+        pp.hash_representation = pp.compute_hash_representation()
+            .map_err(|_| SerializationError::InvalidData)?;
+
+        Ok(pp)
+    }
+}
+
+pub struct SumParametersBuilder<P: Pairing> {
+    size_left_values: Option<usize>,
+    size_right_values: Option<usize>,
+    positions_left: Vec<usize>,
+    positions_right: Vec<usize>,
+    tau: Option<P::ScalarField>,
+}
+
+impl<P: Pairing> SumParametersBuilder<P> {
+    fn default() -> Self {
+        Self {
+            size_left_values: None,
+            size_right_values: None,
+            positions_left: Vec::new(),
+            positions_right: Vec::new(),
+            tau: None,
+        }
+    }
+
+    pub fn size_left_values(mut self, size: usize) -> Self {
+        self.size_left_values = Some(size);
+        self
+    }
+
+    pub fn size_right_values(mut self, size: usize) -> Self {
+        self.size_right_values = Some(size);
+        self
+    }
+
+    pub fn positions_left(mut self, positions: &[usize]) -> Self {
+        self.positions_left = positions.to_vec();
+        self
+    }
+
+    pub fn positions_right(mut self, positions: &[usize]) -> Self {
+        self.positions_right = positions.to_vec();
+        self
+    }
+
+    pub fn tau(mut self, tau: P::ScalarField) -> Self {
+        self.tau = Some(tau);
+        self
+    }
+
+    pub fn build<R: Rng + ?Sized>(self, rng: &mut R) -> Result<SumParameters<P>, Error> {
+        let size_left_values = self.size_left_values.ok_or(Error::MissingParameter("Left \
+        Element Size"))?;
+        let size_right_values = self.size_right_values.ok_or(Error::MissingParameter("Right \
+        Element Size"))?;
+        if let Some(&size) = [size_left_values, size_right_values].iter().find(|size| !size.is_power_of_two()) {
+            return Err(Error::InputShouldBePowerOfTwo(size));
+        }
+
+        let mut positions_left = self.positions_left;
+        positions_left.sort_unstable();
+        positions_left.dedup();
+        let mut positions_right = self.positions_right;
+        positions_right.sort_unstable();
+        positions_right.dedup();
+        if positions_left.is_empty() {
+            return Err(Error::LeftIndicesCannotBeNone);
+        }
+        if positions_right.is_empty() {
+            return Err(Error::RightIndicesCannotBeNone);
+        }
+        if let Some(&i) = positions_left.iter().find(|&&i| i >= size_left_values) {
+            return Err(Error::InputIsTooLarge(i));
+        }
+        if let Some(&j) = positions_right.iter().find(|&&j| j >= size_right_values) {
+            return Err(Error::InputIsTooLarge(j));
+        }
+
+        let tau = self.tau.unwrap_or(P::ScalarField::rand(rng));
+        let (g1_affine_srs, g2_affine_srs) =
+            unsafe_setup_from_tau::<P, R>(max(size_left_values, size_right_values), tau);
+
+        let domain_l = Radix2EvaluationDomain::<P::ScalarField>::new(size_left_values)
+            .ok_or(Error::FailedToCreateEvaluationDomain)?;
+        let domain_r = Radix2EvaluationDomain::<P::ScalarField>::new(size_right_values)
+            .ok_or(Error::FailedToCreateEvaluationDomain)?;
+        let domain_coset_l = domain_l.get_coset(P::ScalarField::GENERATOR)
+            .ok_or(Error::FailedToCreateCosetOfEvaluationDomain)?;
+        let domain_coset_r = domain_r.get_coset(P::ScalarField::GENERATOR)
+            .ok_or(Error::FailedToCreateCosetOfEvaluationDomain)?;
+
+        let poly_positions_left = indicator_poly(&domain_l, &positions_left);
+        let poly_positions_right = indicator_poly(&domain_r, &positions_right);
+        let g1_affine_positions_left = Kzg::<P::G1>::commit(&g1_affine_srs, &poly_positions_left).into_affine();
+        let g1_affine_positions_right = Kzg::<P::G1>::commit(&g1_affine_srs, &poly_positions_right).into_affine();
+        let coset_eval_list_positions_left = domain_coset_l.fft(&poly_positions_left);
+        let coset_eval_list_positions_right = domain_coset_r.fft(&poly_positions_right);
+
+        let mut pp = SumParameters {
+            size_left_values,
+            size_right_values,
+            g1_affine_srs,
+            g2_affine_srs,
+            domain_l,
+            domain_r,
+            positions_left,
+            positions_right,
+            poly_positions_left,
+            poly_positions_right,
+            g1_affine_positions_left,
+            g1_affine_positions_right,
+            domain_coset_l,
+            domain_coset_r,
+            coset_eval_list_positions_left,
+            coset_eval_list_positions_right,
+            hash_representation: Vec::new(),
+        };
+        pp.hash_representation = pp.compute_hash_representation()?;
+
+        Ok(pp)
+    }
+}
+
+impl<P: Pairing> Witness<P> {
+    pub fn new_sum(
+        pp: &SumParameters<P>,
+        left_columns: &[&[P::ScalarField]],
+        right_columns: &[&[P::ScalarField]],
+    ) -> Result<Self, Error> {
+        Self::from_columns(&pp.domain_l, &pp.domain_r, left_columns, right_columns)
+    }
+
+    pub fn generate_sum_statement(&self, pp: &SumParameters<P>) -> Result<Statement<P>, Error> {
+        self.commit_columns(&pp.g1_affine_srs)
+    }
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SumProof<P: Pairing> {
+    pub(crate) sumcheck: SumcheckProof<P>,
+}
+
+/// Proves equal sums for each column, folded with a transcript challenge.
+pub fn prove<P: Pairing>(
+    pp: &SumParameters<P>,
+    witness: &Witness<P>,
+    statement: &Statement<P>,
+) -> Result<SumProof<P>, Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    // Sample random alpha.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+
+    let powers_of_alpha = powers_of_scalars(alpha, witness.num_columns());
+    let (left_values, poly_left_values) =
+        fold_columns(&witness.left_values, &witness.poly_left_values, &powers_of_alpha);
+    let (right_values, poly_right_values) =
+        fold_columns(&witness.right_values, &witness.poly_right_values, &powers_of_alpha);
+
+    // The halves are the selected values themselves, fractions with denominator one.
+    let fr_one = P::ScalarField::one();
+    let coset_eval_list_left_values = pp.domain_coset_l.fft(&poly_left_values);
+    let left = Half {
+        domain: &pp.domain_l,
+        domain_coset: &pp.domain_coset_l,
+        fraction_list: pp.positions_left.iter().map(|&i| (i, left_values[i], fr_one)).collect(),
+        numerator: |k: usize| pp.coset_eval_list_positions_left[k] * coset_eval_list_left_values[k],
+        denominator: |_: usize| fr_one,
+    };
+    let coset_eval_list_right_values = pp.domain_coset_r.fft(&poly_right_values);
+    let right = Half {
+        domain: &pp.domain_r,
+        domain_coset: &pp.domain_coset_r,
+        fraction_list: pp.positions_right.iter().map(|&j| (j, right_values[j], fr_one)).collect(),
+        numerator: |k: usize| pp.coset_eval_list_positions_right[k] * coset_eval_list_right_values[k],
+        denominator: |_: usize| fr_one,
+    };
+
+    let sumcheck = sumcheck::prove(
+        &mut transcript,
+        &pp.g1_affine_srs,
+        left,
+        right,
+        &[
+            (Label::FrLvAtDelta, &poly_left_values),
+            (Label::FrRvAtDelta, &poly_right_values),
+            (Label::FrPlAtDelta, &pp.poly_positions_left),
+            (Label::FrPrAtDelta, &pp.poly_positions_right),
+        ],
+    )?;
+
+    Ok(SumProof { sumcheck })
+}
+
+pub fn verify<P: Pairing>(
+    pp: &SumParameters<P>,
+    statement: &Statement<P>,
+    proof: &SumProof<P>,
+) -> Result<(), Error> {
+    let mut transcript = Transcript::<P::ScalarField>::new();
+    transcript.append_elements(&[
+        (Label::PublicParameters, pp.hash_representation.clone()),
+        (Label::Statement, statement.hash_representation.clone()),
+    ])?;
+
+    if statement.g1_affine_left_values.is_empty()
+        || statement.g1_affine_left_values.len() != statement.g1_affine_right_values.len()
+    {
+        return Err(Error::WrongNumberOfColumns(statement.g1_affine_right_values.len()));
+    }
+    let &[lv_at_delta, rv_at_delta, pl_at_delta, pr_at_delta] = &proof.sumcheck.opened_at_delta[..] else {
+        return Err(Error::MalformedProof("evaluations at delta"));
+    };
+
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
+
+    // Fold the column commitments as the prover folded the columns.
+    let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+    let g1_affine_left_values = P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha)
+        .into_affine();
+    let g1_affine_right_values = P::G1::msm_unchecked(&statement.g1_affine_right_values, &powers_of_alpha)
+        .into_affine();
+
+    let fr_one = P::ScalarField::one();
+    sumcheck::verify(
+        &mut transcript,
+        &pp.g1_affine_srs,
+        &pp.g2_affine_srs,
+        pp.size_left_values,
+        pp.size_right_values,
+        &proof.sumcheck,
+        &[
+            (Label::FrLvAtDelta, g1_affine_left_values),
+            (Label::FrRvAtDelta, g1_affine_right_values),
+            (Label::FrPlAtDelta, pp.g1_affine_positions_left),
+            (Label::FrPrAtDelta, pp.g1_affine_positions_right),
+        ],
+        (pl_at_delta * lv_at_delta, fr_one),
+        (pr_at_delta * rv_at_delta, fr_one),
+    )
+}
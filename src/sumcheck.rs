@@ -0,0 +1,218 @@
+//! The sumcheck pipeline shared by [`crate::shuffle`], [`crate::lookup`] and [`crate::sum`].
+//!
+//! Each side commits to a half `H` that equals `n_i / d_i` at the listed positions of its domain
+//! and zero elsewhere, and to the quotient of `H * D - N` by the vanishing polynomial of the
+//! domain, where the caller gives `N` and `D` by their evaluations over the coset. The halves,
+//! the quotients and the caller's polynomials are opened at `delta` and the halves at zero with
+//! one batch proof, and the verifier checks `n_l * L(0) = n_r * R(0)`.
+
+use crate::error::Error;
+use crate::kzg::{multi_point_batch_verify, Kzg};
+use crate::prover::{fractional_poly, quotient_poly};
+use crate::transcript::{Label, Transcript};
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_ff::{FftField, Field, One, PrimeField};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{EvaluationDomain, Polynomial, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{cfg_into_iter, cfg_iter, vec, vec::Vec, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// One side of the sumcheck, with the numerator and denominator of its identity as functions of
+/// the index into the coset.
+pub(crate) struct Half<'a, F: FftField, N, D> {
+    pub(crate) domain: &'a Radix2EvaluationDomain<F>,
+    pub(crate) domain_coset: &'a Radix2EvaluationDomain<F>,
+    /// `(position, numerator, denominator)` at each position where the half is non-zero.
+    pub(crate) fraction_list: Vec<(usize, F, F)>,
+    pub(crate) numerator: N,
+    pub(crate) denominator: D,
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub(crate) struct SumcheckProof<P: Pairing> {
+    pub(crate) g1_affine_l: P::G1Affine,
+    pub(crate) g1_affine_r: P::G1Affine,
+    pub(crate) g1_affine_ql: P::G1Affine,
+    pub(crate) g1_affine_qr: P::G1Affine,
+    pub(crate) batch_proof: P::G1Affine,
+    pub(crate) l_at_delta: P::ScalarField,
+    pub(crate) r_at_delta: P::ScalarField,
+    /// Evaluations at `delta` of the caller's polynomials, in the order they were given.
+    pub(crate) opened_at_delta: Vec<P::ScalarField>,
+    pub(crate) l_at_zero: P::ScalarField,
+    pub(crate) r_at_zero: P::ScalarField,
+}
+
+/// Interpolates the half and divides its identity by the vanishing polynomial.
+fn half_and_quotient<P, N, D>(
+    half: Half<'_, P::ScalarField, N, D>,
+) -> Result<[DensePolynomial<P::ScalarField>; 2], Error>
+where
+    P: Pairing,
+    N: Fn(usize) -> P::ScalarField + Sync,
+    D: Fn(usize) -> P::ScalarField + Sync,
+{
+    let poly_h = fractional_poly(half.domain, half.fraction_list)?;
+    let coset_eval_list_h = half.domain_coset.fft(&poly_h);
+    let coset_eval_list_q: Vec<P::ScalarField> = cfg_into_iter!(0..coset_eval_list_h.len())
+        .map(|k| coset_eval_list_h[k] * (half.denominator)(k) - (half.numerator)(k))
+        .collect();
+    let poly_q = quotient_poly::<P>(half.domain, half.domain_coset, coset_eval_list_q)?;
+
+    Ok([poly_h, poly_q])
+}
+
+/// Runs the prover from the commitments to the halves on, after the caller has bound its
+/// statement and sampled its challenges.
+pub(crate) fn prove<P, NL, DL, NR, DR>(
+    transcript: &mut Transcript<P::ScalarField>,
+    g1_affine_srs: &[P::G1Affine],
+    left: Half<'_, P::ScalarField, NL, DL>,
+    right: Half<'_, P::ScalarField, NR, DR>,
+    opened: &[(Label, &DensePolynomial<P::ScalarField>)],
+) -> Result<SumcheckProof<P>, Error>
+where
+    P: Pairing,
+    NL: Fn(usize) -> P::ScalarField + Sync,
+    DL: Fn(usize) -> P::ScalarField + Sync,
+    NR: Fn(usize) -> P::ScalarField + Sync,
+    DR: Fn(usize) -> P::ScalarField + Sync,
+{
+    let [poly_l, poly_ql] = half_and_quotient::<P, _, _>(left)?;
+    let [poly_r, poly_qr] = half_and_quotient::<P, _, _>(right)?;
+
+    let g1_affine_l = Kzg::<P::G1>::commit(g1_affine_srs, &poly_l).into_affine();
+    let g1_affine_r = Kzg::<P::G1>::commit(g1_affine_srs, &poly_r).into_affine();
+    transcript.append_elements(&[(Label::G1L, g1_affine_l), (Label::G1R, g1_affine_r)])?;
+
+    let g1_affine_ql = Kzg::<P::G1>::commit(g1_affine_srs, &poly_ql).into_affine();
+    let g1_affine_qr = Kzg::<P::G1>::commit(g1_affine_srs, &poly_qr).into_affine();
+    transcript.append_elements(&[(Label::G1Ql, g1_affine_ql), (Label::G1Qr, g1_affine_qr)])?;
+
+    // Sample random delta.
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    let fr_zero = P::ScalarField::zero();
+    let l_at_delta = poly_l.evaluate(&delta);
+    let r_at_delta = poly_r.evaluate(&delta);
+    let opened_at_delta: Vec<P::ScalarField> = cfg_iter!(opened)
+        .map(|(_, poly)| poly.evaluate(&delta))
+        .collect();
+    let l_at_zero = poly_l.evaluate(&fr_zero);
+    let r_at_zero = poly_r.evaluate(&fr_zero);
+    append_evaluations(transcript, l_at_delta, r_at_delta, opened, &opened_at_delta, l_at_zero, r_at_zero)?;
+
+    // Sample random epsilon to separate the opened polynomials.
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let mut polys_at_delta = vec![&poly_l, &poly_r, &poly_ql, &poly_qr];
+    polys_at_delta.extend(opened.iter().map(|&(_, poly)| poly));
+    let batch_proof = Kzg::<P::G1>::multi_point_batch_open(
+        g1_affine_srs,
+        &[&polys_at_delta, &[&poly_l, &poly_r]],
+        &[delta, fr_zero],
+        epsilon,
+    );
+
+    Ok(SumcheckProof {
+        g1_affine_l,
+        g1_affine_r,
+        g1_affine_ql,
+        g1_affine_qr,
+        batch_proof,
+        l_at_delta,
+        r_at_delta,
+        opened_at_delta,
+        l_at_zero,
+        r_at_zero,
+    })
+}
+
+fn append_evaluations<F: PrimeField>(
+    transcript: &mut Transcript<F>,
+    l_at_delta: F,
+    r_at_delta: F,
+    opened: &[(Label, impl Sized)],
+    opened_at_delta: &[F],
+    l_at_zero: F,
+    r_at_zero: F,
+) -> Result<(), Error> {
+    let mut evaluations = vec![(Label::FrLAtDelta, l_at_delta), (Label::FrRAtDelta, r_at_delta)];
+    evaluations.extend(opened.iter().zip(opened_at_delta).map(|((label, _), &eval)| (*label, eval)));
+    evaluations.extend([(Label::FrLAtZero, l_at_zero), (Label::FrRAtZero, r_at_zero)]);
+
+    transcript.append_elements(&evaluations)
+}
+
+/// Checks a [`SumcheckProof`] against the commitments to the caller's polynomials, whose
+/// evaluations the caller has matched with `proof.opened_at_delta`. `identity_l` and
+/// `identity_r` are the numerator and denominator of each side at `delta`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify<P: Pairing>(
+    transcript: &mut Transcript<P::ScalarField>,
+    g1_affine_srs: &[P::G1Affine],
+    g2_affine_srs: &[P::G2Affine],
+    size_left_values: usize,
+    size_right_values: usize,
+    proof: &SumcheckProof<P>,
+    opened: &[(Label, P::G1Affine)],
+    identity_l: (P::ScalarField, P::ScalarField),
+    identity_r: (P::ScalarField, P::ScalarField),
+) -> Result<(), Error> {
+    transcript.append_elements(&[(Label::G1L, proof.g1_affine_l), (Label::G1R, proof.g1_affine_r)])?;
+    transcript.append_elements(&[(Label::G1Ql, proof.g1_affine_ql), (Label::G1Qr, proof.g1_affine_qr)])?;
+
+    let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
+
+    append_evaluations(
+        transcript,
+        proof.l_at_delta,
+        proof.r_at_delta,
+        opened,
+        &proof.opened_at_delta,
+        proof.l_at_zero,
+        proof.r_at_zero,
+    )?;
+
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
+
+    let fr_one = P::ScalarField::one();
+    let (numerator_l, denominator_l) = identity_l;
+    let fr_zl_at_delta = delta.pow([size_left_values as u64]) - fr_one;
+    let fr_inv_zl_at_delta = fr_zl_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let fr_ql_at_delta = (proof.l_at_delta * denominator_l - numerator_l) * fr_inv_zl_at_delta;
+
+    let (numerator_r, denominator_r) = identity_r;
+    let fr_zr_at_delta = delta.pow([size_right_values as u64]) - fr_one;
+    let fr_inv_zr_at_delta = fr_zr_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
+    let fr_qr_at_delta = (proof.r_at_delta * denominator_r - numerator_r) * fr_inv_zr_at_delta;
+
+    let mut commitments_at_delta = vec![proof.g1_affine_l, proof.g1_affine_r, proof.g1_affine_ql, proof.g1_affine_qr];
+    commitments_at_delta.extend(opened.iter().map(|&(_, commitment)| commitment));
+    let mut evaluations_at_delta = vec![proof.l_at_delta, proof.r_at_delta, fr_ql_at_delta, fr_qr_at_delta];
+    evaluations_at_delta.extend_from_slice(&proof.opened_at_delta);
+
+    if !multi_point_batch_verify::<P>(
+        g1_affine_srs[0],
+        g2_affine_srs,
+        &[&commitments_at_delta, &[proof.g1_affine_l, proof.g1_affine_r]],
+        &[&evaluations_at_delta, &[proof.l_at_zero, proof.r_at_zero]],
+        &[delta, P::ScalarField::zero()],
+        epsilon,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("batch opening at delta and zero"));
+    }
+
+    // Sumcheck Lemma.
+    if proof.l_at_zero * P::ScalarField::from(size_left_values as u64)
+        != proof.r_at_zero * P::ScalarField::from(size_right_values as u64)
+    {
+        return Err(Error::EqualityCheckFailed("sumcheck of left and right halves"));
+    }
+
+    Ok(())
+}
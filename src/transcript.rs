@@ -2,26 +2,45 @@ use crate::error::Error;
 use ark_ff::PrimeField;
 use ark_serialize::CanonicalSerialize;
 use merlin::Transcript as MerlinTranscript;
-use std::marker::PhantomData;
+use ark_std::marker::PhantomData;
+use ark_std::vec;
 
 /// Modified from https://github.com/caulk-crypto/caulk/blob/main/src/transcript.rs
 
 #[derive(Copy, Clone)]
 pub(crate) enum Label {
+    ChallengeAlpha,
     ChallengeBeta,
     ChallengeGamma,
     ChallengeDelta,
     ChallengeEpsilon,
-    ChallengeZeta,
+    ChallengeEta,
+    ChallengeSigma,
+    ChallengeRho,
 
     PublicParameters,
     Statement,
+    MappingCommitment,
+    DisclosedIndices,
+    DisclosedValues,
+    PedersenGenerators,
+    PedersenCommitments,
+    SigmaCommitments,
+    InstanceEvaluations,
+    Accumulator,
+    CrossTerms,
 
     G1L,
     G1R,
     G1Ql,
     G1Qr,
-    G1BatchProofAtRandPoint,
+    G1Q,
+    G1M,
+    G1S,
+    G1U,
+    G1T,
+    G1F,
+    G1Z,
 
     FrLAtZero,
     FrRAtZero,
@@ -32,32 +51,98 @@ pub(crate) enum Label {
     FrPlAtDelta,
     FrPrAtDelta,
     FrPmAtDelta,
+    FrPaAtDelta,
+    FrPbAtDelta,
+    FrLv0AtDelta,
+    FrPsAtDelta,
+    FrPcAtDelta,
+    FrVAtDelta,
+    FrCAtDelta,
+    FrMAtDelta,
+    FrSAtDelta,
+    FrUlAtDelta,
+    FrUrAtDelta,
+    FrTAtDelta,
+    FrRvOldAtDelta,
+    FrElAtDelta,
+    FrErAtDelta,
+    FrFlAtDelta,
+    FrFrAtDelta,
+    FrZlAtDelta,
+    FrZrAtDelta,
+    FrZlAtShiftedDelta,
+    FrZrAtShiftedDelta,
+    FrProduct,
 }
 
 impl Label {
     pub fn as_bytes(&self) -> &'static [u8] {
+        self.as_str().as_bytes()
+    }
+
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Label::ChallengeBeta => b"beta",
-            Label::ChallengeGamma => b"gamma",
-            Label::ChallengeDelta => b"delta",
-            Label::ChallengeEpsilon => b"epsilon",
-            Label::ChallengeZeta => b"zeta",
-            Label::PublicParameters => b"common_inputs",
-            Label::Statement => b"statement",
-            Label::G1L => b"g1_l",
-            Label::G1R => b"g1_r",
-            Label::G1Ql => b"g1_ql",
-            Label::G1Qr => b"g1_qr",
-            Label::G1BatchProofAtRandPoint => b"g1_batch_proof_at_rand_point",
-            Label::FrLAtZero => b"fr_l_at_zero",
-            Label::FrRAtZero => b"fr_r_at_zero",
-            Label::FrLAtDelta => b"fr_l_at_delta",
-            Label::FrRAtDelta => b"fr_r_at_delta",
-            Label::FrLvAtDelta => b"fr_ql_at_delta",
-            Label::FrRvAtDelta => b"fr_qr_at_delta",
-            Label::FrPlAtDelta => b"fr_pl_at_delta",
-            Label::FrPrAtDelta => b"fr_pr_at_delta",
-            Label::FrPmAtDelta => b"fr_pm_at_delta",
+            Label::ChallengeAlpha => "alpha",
+            Label::ChallengeBeta => "beta",
+            Label::ChallengeGamma => "gamma",
+            Label::ChallengeDelta => "delta",
+            Label::ChallengeEpsilon => "epsilon",
+            Label::ChallengeEta => "eta",
+            Label::ChallengeSigma => "sigma",
+            Label::ChallengeRho => "rho",
+            Label::PublicParameters => "common_inputs",
+            Label::Statement => "statement",
+            Label::MappingCommitment => "mapping_commitment",
+            Label::DisclosedIndices => "disclosed_indices",
+            Label::DisclosedValues => "disclosed_values",
+            Label::PedersenGenerators => "pedersen_generators",
+            Label::PedersenCommitments => "pedersen_commitments",
+            Label::SigmaCommitments => "sigma_commitments",
+            Label::InstanceEvaluations => "instance_evaluations",
+            Label::Accumulator => "accumulator",
+            Label::CrossTerms => "cross_terms",
+            Label::G1L => "g1_l",
+            Label::G1R => "g1_r",
+            Label::G1Ql => "g1_ql",
+            Label::G1Qr => "g1_qr",
+            Label::G1Q => "g1_q",
+            Label::G1M => "g1_m",
+            Label::G1S => "g1_s",
+            Label::G1U => "g1_u",
+            Label::G1T => "g1_t",
+            Label::G1F => "g1_f",
+            Label::G1Z => "g1_z",
+            Label::FrLAtZero => "fr_l_at_zero",
+            Label::FrRAtZero => "fr_r_at_zero",
+            Label::FrLAtDelta => "fr_l_at_delta",
+            Label::FrRAtDelta => "fr_r_at_delta",
+            Label::FrLvAtDelta => "fr_ql_at_delta",
+            Label::FrRvAtDelta => "fr_qr_at_delta",
+            Label::FrPlAtDelta => "fr_pl_at_delta",
+            Label::FrPrAtDelta => "fr_pr_at_delta",
+            Label::FrPmAtDelta => "fr_pm_at_delta",
+            Label::FrPaAtDelta => "fr_pa_at_delta",
+            Label::FrPbAtDelta => "fr_pb_at_delta",
+            Label::FrLv0AtDelta => "fr_lv0_at_delta",
+            Label::FrPsAtDelta => "fr_ps_at_delta",
+            Label::FrPcAtDelta => "fr_pc_at_delta",
+            Label::FrVAtDelta => "fr_v_at_delta",
+            Label::FrCAtDelta => "fr_c_at_delta",
+            Label::FrMAtDelta => "fr_m_at_delta",
+            Label::FrSAtDelta => "fr_s_at_delta",
+            Label::FrUlAtDelta => "fr_ul_at_delta",
+            Label::FrUrAtDelta => "fr_ur_at_delta",
+            Label::FrTAtDelta => "fr_t_at_delta",
+            Label::FrRvOldAtDelta => "fr_rv_old_at_delta",
+            Label::FrElAtDelta => "fr_el_at_delta",
+            Label::FrErAtDelta => "fr_er_at_delta",
+            Label::FrFlAtDelta => "fr_fl_at_delta",
+            Label::FrFrAtDelta => "fr_fr_at_delta",
+            Label::FrZlAtDelta => "fr_zl_at_delta",
+            Label::FrZrAtDelta => "fr_zr_at_delta",
+            Label::FrZlAtShiftedDelta => "fr_zl_at_shifted_delta",
+            Label::FrZrAtShiftedDelta => "fr_zr_at_shifted_delta",
+            Label::FrProduct => "fr_product",
         }
     }
 }
@@ -77,13 +162,16 @@ impl<F: PrimeField> Transcript<F> {
     pub(crate) fn new() -> Self {
         Self {
             transcript: MerlinTranscript::new(b"Init SegLookup Transcript"),
-            _marker: PhantomData::default(),
+            _marker: PhantomData,
         }
     }
 
-    /// Get a uniform random field element for field size < 384
+    /// Get a uniform random field element.
+    ///
+    /// Squeezes 128 bits more than the modulus size before reducing, so the bias of the
+    /// reduction stays below 2^-128 for any field size.
     pub(crate) fn squeeze_challenge(&mut self, label: Label) -> Result<F, Error> {
-        let mut bytes = [0u8; 64];
+        let mut bytes = vec![0u8; (F::MODULUS_BIT_SIZE as usize + 128).div_ceil(8)];
         self.transcript
             .challenge_bytes(label.as_bytes(), &mut bytes);
         let challenge = F::from_le_bytes_mod_order(bytes.as_ref());
@@ -101,7 +189,7 @@ impl<F: PrimeField> Transcript<F> {
         let mut buf = vec![];
         element
             .serialize_uncompressed(&mut buf)
-            .map_err(|_| Error::FailedToSerializeElement)?;
+            .map_err(|e| Error::FailedToSerializeElement(label.as_str(), e))?;
         self.transcript
             .append_message(label.as_bytes(), buf.as_ref());
 
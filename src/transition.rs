@@ -0,0 +1,165 @@
+//! Proves a state update: the new right vector takes the left values at the mapped positions
+//! and keeps the old right values everywhere else.
+//!
+//! The relation of [`crate::prover::prove`] holds between the left and the new right vector,
+//! and the right quotient additionally proves `(1 - P_R) * (W_new - W_old) = 0` over the right
+//! domain, where `P_R` is the indicator of `positions_right`.
+
+use crate::error::Error;
+use crate::kzg::Kzg;
+use crate::prover::{prove_inner, Proof};
+use crate::public_parameters::PublicParameters;
+use crate::statement::Statement;
+use crate::verifier::verify_inner;
+use crate::witness::Witness;
+use crate::COMPRESS_MOD;
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::io::{Read, Write};
+use ark_std::vec::Vec;
+use blake2::{Blake2b512, Digest};
+
+pub struct TransitionWitness<P: Pairing> {
+    pub(crate) witness: Witness<P>,
+    pub(crate) poly_old_right_values: Vec<DensePolynomial<P::ScalarField>>,
+}
+
+impl<P: Pairing> TransitionWitness<P> {
+    /// Creates a witness from the columns of the left, old right and new right vectors, which
+    /// must all have the same number of columns.
+    pub fn new(
+        pp: &PublicParameters<P>,
+        left_columns: &[&[P::ScalarField]],
+        old_right_columns: &[&[P::ScalarField]],
+        new_right_columns: &[&[P::ScalarField]],
+    ) -> Result<Self, Error> {
+        let witness = Witness::new_multi_column(pp, left_columns, new_right_columns)?;
+        if old_right_columns.len() != new_right_columns.len() {
+            return Err(Error::WrongNumberOfColumns(old_right_columns.len()));
+        }
+        if let Some(column) = old_right_columns.iter().find(|c| c.len() != pp.size_right_values) {
+            return Err(Error::WrongNumberOfRightValues(column.len()));
+        }
+        let poly_old_right_values = old_right_columns
+            .iter()
+            .map(|column| DensePolynomial::from_coefficients_vec(pp.domain_r.ifft(column)))
+            .collect();
+
+        Ok(Self {
+            witness,
+            poly_old_right_values,
+        })
+    }
+
+    pub fn generate_statement(&self, pp: &PublicParameters<P>) -> Result<TransitionStatement<P>, Error> {
+        let statement = self.witness.generate_statement(pp)?;
+        let g1_affine_old_right_values = self.poly_old_right_values.iter()
+            .map(|poly| Kzg::<P::G1>::commit(&pp.g1_affine_srs, poly).into_affine())
+            .collect();
+
+        TransitionStatement::from_statement(statement, g1_affine_old_right_values)
+    }
+}
+
+/// Commitments to the left, old right and new right columns.
+#[derive(Debug)]
+pub struct TransitionStatement<P: Pairing> {
+    pub(crate) statement: Statement<P>,
+    pub(crate) g1_affine_old_right_values: Vec<P::G1Affine>,
+    pub(crate) hash_representation: Vec<u8>,
+}
+
+impl<P: Pairing> TransitionStatement<P> {
+    fn from_statement(statement: Statement<P>, g1_affine_old_right_values: Vec<P::G1Affine>) -> Result<Self, Error> {
+        let mut buf = statement.hash_representation.clone();
+        g1_affine_old_right_values.serialize_with_mode(&mut buf, COMPRESS_MOD).map_err(|e|
+            Error::FailedToSerializeElement("g1_affine_old_right_values", e))?;
+        let hash_representation = Blake2b512::digest(&buf).to_vec();
+
+        Ok(Self {
+            statement,
+            g1_affine_old_right_values,
+            hash_representation,
+        })
+    }
+}
+
+// Only the commitments are serialized, as for `Statement`.
+impl<P: Pairing> CanonicalSerialize for TransitionStatement<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.statement.serialize_with_mode(&mut writer, compress)?;
+        self.g1_affine_old_right_values.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.statement.serialized_size(compress) + self.g1_affine_old_right_values.serialized_size(compress)
+    }
+}
+
+impl<P: Pairing> Valid for TransitionStatement<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.statement.check()?;
+        self.g1_affine_old_right_values.check()
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for TransitionStatement<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let statement = Statement::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g1_affine_old_right_values = Vec::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        TransitionStatement::from_statement(statement, g1_affine_old_right_values)
+            .map_err(|_| SerializationError::InvalidData)
+    }
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct TransitionProof<P: Pairing> {
+    pub(crate) proof: Proof<P>,
+    pub(crate) rv_old_at_delta: P::ScalarField,
+}
+
+pub fn prove<P: Pairing>(
+    pp: &PublicParameters<P>,
+    witness: &TransitionWitness<P>,
+    statement: &TransitionStatement<P>,
+) -> Result<TransitionProof<P>, Error> {
+    let (proof, rv_old_at_delta) = prove_inner(
+        pp,
+        &witness.witness,
+        &statement.hash_representation,
+        Some(&witness.poly_old_right_values),
+    )?;
+
+    Ok(TransitionProof {
+        proof,
+        rv_old_at_delta: rv_old_at_delta.ok_or(Error::MalformedProof("rv_old_at_delta"))?,
+    })
+}
+
+pub fn verify<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &TransitionStatement<P>,
+    proof: &TransitionProof<P>,
+) -> Result<(), Error> {
+    verify_inner(
+        pp,
+        &statement.statement,
+        &statement.hash_representation,
+        &proof.proof,
+        Some((&statement.g1_affine_old_right_values, proof.rv_old_at_delta)),
+    )
+}
@@ -1,86 +1,156 @@
 use crate::error::Error;
-use crate::prover::Proof;
+use crate::kzg::{multi_point_batch_verify, powers_of_scalars};
+use crate::grand_product;
+use crate::prover::{BackendProof, Proof};
 use crate::public_parameters::PublicParameters;
 use crate::statement::Statement;
 use crate::transcript::{Label, Transcript};
 use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
 use ark_ff::Field;
-use ark_std::{One, Zero};
-use std::ops::Mul;
+use ark_std::{array, vec, One, Zero};
 
 pub fn verify<P: Pairing>(
     pp: &PublicParameters<P>,
     statement: &Statement<P>,
     proof: &Proof<P>,
+) -> Result<(), Error> {
+    verify_inner(pp, statement, &statement.hash_representation, proof, None)
+}
+
+/// Verifies a proof of [`crate::prover::prove_with_backend`] with the backend that made it.
+pub fn verify_with_backend<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &Statement<P>,
+    proof: &BackendProof<P>,
+) -> Result<(), Error> {
+    match proof {
+        BackendProof::LogDerivative(proof) => verify(pp, statement, proof),
+        BackendProof::GrandProduct(proof) => grand_product::verify(pp, statement, proof),
+    }
+}
+
+/// The evaluations of public polynomials that proofs open only if `opened`, or their values
+/// `trivial` if not, in which case the proof must not carry them.
+pub(crate) fn opened_or<F: Copy, const N: usize>(
+    opened: bool,
+    evaluations: [Option<F>; N],
+    trivial: [F; N],
+    name: &'static str,
+) -> Result<[F; N], Error> {
+    if !evaluations.iter().all(|evaluation| evaluation.is_some() == opened) {
+        return Err(Error::MalformedProof(name));
+    }
+
+    Ok(array::from_fn(|k| evaluations[k].unwrap_or(trivial[k])))
+}
+
+/// Verifies a proof of [`crate::prover::prove_inner`], given the commitments to the old right
+/// columns and the folded old right vector's evaluation at `delta` if it was used.
+pub(crate) fn verify_inner<P: Pairing>(
+    pp: &PublicParameters<P>,
+    statement: &Statement<P>,
+    statement_hash: &[u8],
+    proof: &Proof<P>,
+    old_right: Option<(&[P::G1Affine], P::ScalarField)>,
 ) -> Result<(), Error> {
     let mut transcript = Transcript::<P::ScalarField>::new();
     transcript.append_elements(&[
         (Label::PublicParameters, pp.hash_representation.clone()),
-        (Label::Statement, statement.hash_representation.clone()),
+        (Label::Statement, statement_hash.to_vec()),
     ])?;
 
-    // Sample random beta, gamma.
+    if statement.g1_affine_left_values.is_empty()
+        || statement.g1_affine_left_values.len() != statement.g1_affine_right_values.len()
+    {
+        return Err(Error::WrongNumberOfColumns(statement.g1_affine_right_values.len()));
+    }
+    if let Some((g1_affine_old_right_values, _)) = old_right {
+        if g1_affine_old_right_values.len() != statement.num_columns() {
+            return Err(Error::WrongNumberOfColumns(g1_affine_old_right_values.len()));
+        }
+    }
+
+    // Sample random alpha, beta, gamma.
+    let alpha = transcript.squeeze_challenge(Label::ChallengeAlpha)?;
     let beta = transcript.squeeze_challenge(Label::ChallengeBeta)?;
     let gamma = transcript.squeeze_challenge(Label::ChallengeGamma)?;
 
-    transcript.append_elements(
-        &[
-            (Label::G1L, proof.g1_affine_l),
-            (Label::G1R, proof.g1_affine_r),
-            (Label::G1Ql, proof.g1_affine_ql),
-            (Label::G1Qr, proof.g1_affine_qr),
-        ]
-    )?;
+    transcript.append_elements(&[(Label::G1L, proof.g1_affine_l), (Label::G1R, proof.g1_affine_r)])?;
+
+    let eta = transcript.squeeze_challenge(Label::ChallengeEta)?;
+
+    transcript.append_elements(&[(Label::G1Ql, proof.g1_affine_ql), (Label::G1Qr, proof.g1_affine_qr)])?;
 
     let delta = transcript.squeeze_challenge(Label::ChallengeDelta)?;
-    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
 
-    transcript.append_element(Label::G1BatchProofAtRandPoint, &proof.batch_proof_at_rand_point)?;
-    transcript.append_elements(
-        &[
-            (Label::FrLAtDelta, proof.l_at_delta),
-            (Label::FrRAtDelta, proof.r_at_delta),
-            (Label::FrLvAtDelta, proof.lv_at_delta),
-            (Label::FrRvAtDelta, proof.rv_at_delta),
-            (Label::FrPlAtDelta, proof.pl_at_delta),
-            (Label::FrPrAtDelta, proof.pr_at_delta),
-            (Label::FrPmAtDelta, proof.pm_at_delta),
-            (Label::FrLAtZero, proof.l_at_zero),
-            (Label::FrRAtZero, proof.r_at_zero),
-        ]
-    )?;
+    transcript.append_elements(&proof.evaluations())?;
+
+    if let Some((_, rv_old_at_delta)) = old_right {
+        transcript.append_element(Label::FrRvOldAtDelta, &rv_old_at_delta)?;
+    }
 
-    let zeta = transcript.squeeze_challenge(Label::ChallengeZeta)?;
+    let epsilon = transcript.squeeze_challenge(Label::ChallengeEpsilon)?;
 
-    // Pairing check of batch proof at random point.
     let fr_one = P::ScalarField::one();
-    let fr_zl_at_delta = delta.pow(&[pp.size_left_values as u64]) - fr_one;
+    let has_coefficients = pp.has_coefficients();
+    let [pa_at_delta, pb_at_delta] = opened_or(
+        has_coefficients,
+        [proof.pa_at_delta, proof.pb_at_delta],
+        [fr_one, P::ScalarField::zero()],
+        "coefficient evaluations",
+    )?;
+    let has_constants = pp.has_constants();
+    let [lv0_at_delta, ps_at_delta, pc_at_delta] = opened_or(
+        has_constants,
+        [proof.lv0_at_delta, proof.ps_at_delta, proof.pc_at_delta],
+        [P::ScalarField::zero(); 3],
+        "constant evaluations",
+    )?;
+
+    let fr_zl_at_delta = delta.pow([pp.size_left_values as u64]) - fr_one;
     let fr_inv_zl_at_delta = fr_zl_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
-    let fr_ql_at_delta = beta + proof.lv_at_delta + gamma * proof.pm_at_delta;
+    let powers_of_alpha = powers_of_scalars(alpha, statement.num_columns());
+    let fr_sum_of_alpha: P::ScalarField = powers_of_alpha.iter().sum();
+    let fr_ql_at_delta = beta + pa_at_delta * proof.lv_at_delta + fr_sum_of_alpha * pb_at_delta
+        + gamma * proof.pm_at_delta;
     let fr_ql_at_delta = fr_ql_at_delta * proof.l_at_delta;
     let fr_ql_at_delta = fr_ql_at_delta - proof.pl_at_delta;
+    let fr_ql_at_delta = fr_ql_at_delta + eta * (ps_at_delta * lv0_at_delta - pc_at_delta);
     let fr_ql_at_delta = fr_ql_at_delta * fr_inv_zl_at_delta;
 
-    let fr_zr_at_delta = delta.pow(&[pp.size_right_values as u64]) - fr_one;
+    let fr_zr_at_delta = delta.pow([pp.size_right_values as u64]) - fr_one;
     let fr_inv_zr_at_delta = fr_zr_at_delta.inverse().ok_or(Error::FailedToInverseFieldElement)?;
     let fr_qr_at_delta = beta + proof.rv_at_delta + gamma * delta;
     let fr_qr_at_delta = fr_qr_at_delta * proof.r_at_delta;
     let fr_qr_at_delta = fr_qr_at_delta - proof.pr_at_delta;
+    let fr_qr_at_delta = match old_right {
+        Some((_, rv_old_at_delta)) => {
+            fr_qr_at_delta + eta * (fr_one - proof.pr_at_delta) * (proof.rv_at_delta - rv_old_at_delta)
+        }
+        None => fr_qr_at_delta,
+    };
     let fr_qr_at_delta = fr_qr_at_delta * fr_inv_zr_at_delta;
 
-    let g1_list = vec![
+    // Fold the column commitments as the prover folded the columns.
+    let g1_affine_left_values = P::G1::msm_unchecked(&statement.g1_affine_left_values, &powers_of_alpha)
+        .into_affine();
+    let g1_affine_right_values = P::G1::msm_unchecked(&statement.g1_affine_right_values, &powers_of_alpha)
+        .into_affine();
+
+    // Pairing check of the batch proof at delta and zero.
+    let mut g1_list_at_delta = vec![
         proof.g1_affine_l,
         proof.g1_affine_r,
         proof.g1_affine_ql,
         proof.g1_affine_qr,
-        statement.g1_affine_left_values,
-        statement.g1_affine_right_values,
+        g1_affine_left_values,
+        g1_affine_right_values,
         pp.g1_affine_positions_left,
         pp.g1_affine_positions_right,
         pp.g1_affine_position_mappings,
     ];
-
-    let fr_list = vec![
+    let mut fr_list_at_delta = vec![
         proof.l_at_delta,
         proof.r_at_delta,
         fr_ql_at_delta,
@@ -91,44 +161,40 @@ pub fn verify<P: Pairing>(
         proof.pr_at_delta,
         proof.pm_at_delta,
     ];
-
-    let mut g1_batched = P::G1::zero();
-    let mut fr_batched = P::ScalarField::zero();
-    let mut fr_pow_epsilon = fr_one;
-    g1_list
-        .iter()
-        .zip(fr_list.iter())
-        .for_each(|(g1, &fr)| {
-            g1_batched += g1.mul(fr_pow_epsilon);
-            fr_batched += fr * fr_pow_epsilon;
-            fr_pow_epsilon = fr_pow_epsilon * epsilon;
-        });
-
-    let pairing_left = P::pairing(
-        g1_batched - pp.g1_affine_srs[0].mul(fr_batched) + proof.batch_proof_at_rand_point.mul(delta),
-        pp.g2_affine_srs[0],
-    );
-    let pairing_right = P::pairing(
-        proof.batch_proof_at_rand_point,
-        pp.g2_affine_srs[1],
-    );
-    if pairing_left != pairing_right {
-        return Err(Error::Pairing1Failed);
+    if has_coefficients {
+        g1_list_at_delta.extend([pp.g1_affine_coefficients_a, pp.g1_affine_coefficients_b]);
+        fr_list_at_delta.extend([pa_at_delta, pb_at_delta]);
+    }
+    if has_constants {
+        g1_list_at_delta.extend([
+            statement.g1_affine_left_values[0],
+            pp.g1_affine_constant_positions,
+            pp.g1_affine_constants,
+        ]);
+        fr_list_at_delta.extend([lv0_at_delta, ps_at_delta, pc_at_delta]);
+    }
+    if let Some((g1_affine_old_right_values, rv_old_at_delta)) = old_right {
+        g1_list_at_delta.push(P::G1::msm_unchecked(g1_affine_old_right_values, &powers_of_alpha).into_affine());
+        fr_list_at_delta.push(rv_old_at_delta);
     }
+    let g1_list_at_zero = [proof.g1_affine_l, proof.g1_affine_r];
+    let fr_list_at_zero = [proof.l_at_zero, proof.r_at_zero];
 
-    // Pairing check of batch proof at zero.
-    let tmp = proof.g1_affine_r.mul(zeta);
-    let tmp = tmp + proof.g1_affine_l;
-    let tmp = tmp - pp.g1_affine_srs[0].mul(proof.l_at_zero + proof.r_at_zero * zeta);
-    let pairing_left = P::pairing(tmp, pp.g2_affine_srs[0]);
-    let pairing_right = P::pairing(proof.batch_proof_at_zero, pp.g2_affine_srs[1]);
-    if pairing_left != pairing_right {
-        return Err(Error::Pairing2Failed);
+    if !multi_point_batch_verify::<P>(
+        pp.g1_affine_srs[0],
+        &pp.g2_affine_srs,
+        &[&g1_list_at_delta, &g1_list_at_zero],
+        &[&fr_list_at_delta, &fr_list_at_zero],
+        &[delta, P::ScalarField::zero()],
+        epsilon,
+        proof.batch_proof,
+    ) {
+        return Err(Error::PairingCheckFailed("batch opening at delta and zero"));
     }
 
     // Sumcheck Lemma.
     if proof.l_at_zero * P::ScalarField::from(pp.size_left_values as u64) != proof.r_at_zero * P::ScalarField::from(pp.size_right_values as u64) {
-        return Err(Error::EqualityCheckFailed);
+        return Err(Error::EqualityCheckFailed("sumcheck of left and right halves"));
     }
 
     Ok(())
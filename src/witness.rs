@@ -1,14 +1,19 @@
 use crate::error::Error;
+use crate::kzg::linear_combination;
 use crate::public_parameters::PublicParameters;
 use ark_ec::pairing::Pairing;
+use ark_ff::FftField;
+use ark_std::{cfg_iter, cfg_iter_mut, vec, vec::Vec};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use ark_poly::univariate::DensePolynomial;
-use ark_poly::{DenseUVPolynomial, EvaluationDomain};
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Radix2EvaluationDomain};
 
 pub struct Witness<P: Pairing> {
-    pub(crate) left_values: Vec<P::ScalarField>,
-    pub(crate) right_values: Vec<P::ScalarField>,
-    pub(crate) poly_left_values: DensePolynomial<P::ScalarField>,
-    pub(crate) poly_right_values: DensePolynomial<P::ScalarField>,
+    pub(crate) left_values: Vec<Vec<P::ScalarField>>,
+    pub(crate) right_values: Vec<Vec<P::ScalarField>>,
+    pub(crate) poly_left_values: Vec<DensePolynomial<P::ScalarField>>,
+    pub(crate) poly_right_values: Vec<DensePolynomial<P::ScalarField>>,
 }
 
 impl<P: Pairing> Witness<P> {
@@ -17,27 +22,73 @@ impl<P: Pairing> Witness<P> {
         left_values: &[P::ScalarField],
         right_values: &[P::ScalarField],
     ) -> Result<Self, Error> {
-        if left_values.len() != pp.size_left_values {
-            return Err(Error::WrongNumberOfLeftValues(left_values.len()));
+        Self::new_multi_column(pp, &[left_values], &[right_values])
+    }
+
+    /// Creates a witness whose left and right positions hold tuples of field elements, given
+    /// as one column per tuple component. Left and right must have the same number of columns.
+    pub fn new_multi_column(
+        pp: &PublicParameters<P>,
+        left_columns: &[&[P::ScalarField]],
+        right_columns: &[&[P::ScalarField]],
+    ) -> Result<Self, Error> {
+        Self::from_columns(&pp.domain_l, &pp.domain_r, left_columns, right_columns)
+    }
+
+    /// Interpolates the columns over the left and right domains, whose sizes they must match.
+    pub(crate) fn from_columns(
+        domain_l: &Radix2EvaluationDomain<P::ScalarField>,
+        domain_r: &Radix2EvaluationDomain<P::ScalarField>,
+        left_columns: &[&[P::ScalarField]],
+        right_columns: &[&[P::ScalarField]],
+    ) -> Result<Self, Error> {
+        if left_columns.is_empty() || left_columns.len() != right_columns.len() {
+            return Err(Error::WrongNumberOfColumns(right_columns.len()));
         }
 
-        if right_values.len() != pp.size_right_values {
-            return Err(Error::WrongNumberOfRightValues(right_values.len()));
+        if let Some(column) = left_columns.iter().find(|c| c.len() != domain_l.size()) {
+            return Err(Error::WrongNumberOfLeftValues(column.len()));
         }
 
-        let coeff_left_values = pp.domain_l.ifft(left_values);
-        let poly_left_values = DensePolynomial::from_coefficients_vec(coeff_left_values);
-        let coeff_right_values = pp.domain_r.ifft(right_values);
-        let poly_right_values = DensePolynomial::from_coefficients_vec(coeff_right_values);
+        if let Some(column) = right_columns.iter().find(|c| c.len() != domain_r.size()) {
+            return Err(Error::WrongNumberOfRightValues(column.len()));
+        }
+
+        let poly_left_values = left_columns
+            .iter()
+            .map(|column| DensePolynomial::from_coefficients_vec(domain_l.ifft(column)))
+            .collect();
+        let poly_right_values = right_columns
+            .iter()
+            .map(|column| DensePolynomial::from_coefficients_vec(domain_r.ifft(column)))
+            .collect();
 
         Ok(Self {
-            left_values: left_values.to_vec(),
-            right_values: right_values.to_vec(),
+            left_values: left_columns.iter().map(|column| column.to_vec()).collect(),
+            right_values: right_columns.iter().map(|column| column.to_vec()).collect(),
             poly_left_values,
             poly_right_values,
         })
     }
-}
 
+    pub fn num_columns(&self) -> usize {
+        self.left_values.len()
+    }
+}
 
+/// Folds the columns of a vector and of its polynomial with the given powers of a challenge.
+pub(crate) fn fold_columns<F: FftField>(
+    columns: &[Vec<F>],
+    poly_columns: &[DensePolynomial<F>],
+    powers: &[F],
+) -> (Vec<F>, DensePolynomial<F>) {
+    let mut folded = vec![F::zero(); columns[0].len()];
+    columns.iter().zip(powers.iter()).for_each(|(column, &fr_pow)| {
+        cfg_iter_mut!(folded)
+            .zip(cfg_iter!(column))
+            .for_each(|(f, &v)| *f += v * fr_pow);
+    });
+    let poly_list = poly_columns.iter().collect::<Vec<_>>();
 
+    (folded, linear_combination(&poly_list, powers))
+}
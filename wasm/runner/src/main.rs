@@ -0,0 +1,106 @@
+//! Runs the `no_std` verifier compiled to `wasm32-unknown-unknown` inside the `wasmi`
+//! interpreter, against a proof produced natively.
+//!
+//! Usage: `isep_wasm_runner <path to isep_wasm_verifier.wasm>`
+use ark_bn254::{Bn254, Fr};
+use ark_isep::prover::prove;
+use ark_isep::public_parameters::PublicParameters;
+use ark_isep::witness::Witness;
+use ark_serialize::CanonicalSerialize;
+use ark_std::{test_rng, UniformRand};
+use std::collections::BTreeMap;
+use wasmi::{Engine, Instance, Linker, Module, Store};
+
+const VERIFY_OK: i32 = 0;
+const VERIFY_REJECTED: i32 = 2;
+
+fn main() {
+    let wasm_path = std::env::args()
+        .nth(1)
+        .expect("usage: isep_wasm_runner <path to isep_wasm_verifier.wasm>");
+    let wasm = std::fs::read(&wasm_path).expect("failed to read the wasm module");
+
+    let rng = &mut test_rng();
+    let mut mappings = BTreeMap::new();
+    mappings.insert(1, 3);
+    mappings.insert(5, 10);
+    let pp = PublicParameters::<Bn254>::builder()
+        .size_left_values(8)
+        .size_right_values(16)
+        .position_mappings(&mappings)
+        .build(rng)
+        .unwrap();
+
+    let left_values = (0..8).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let mut right_values = (0..16).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    right_values[3] = left_values[1];
+    right_values[10] = left_values[5];
+    let witness = Witness::new(&pp, &left_values, &right_values).unwrap();
+    let statement = witness.generate_statement(&pp).unwrap();
+    let proof = prove(&pp, &witness, &statement).unwrap();
+
+    // A statement the proof was not produced for.
+    right_values[0] += Fr::from(1u64);
+    let other_statement = Witness::new(&pp, &left_values, &right_values)
+        .unwrap()
+        .generate_statement(&pp)
+        .unwrap();
+
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).expect("failed to compile the wasm module");
+
+    let code = run(&engine, &module, &pp, &statement, &proof);
+    assert_eq!(code, VERIFY_OK, "valid proof rejected in wasm");
+    println!("valid proof accepted");
+
+    let code = run(&engine, &module, &pp, &other_statement, &proof);
+    assert_eq!(code, VERIFY_REJECTED, "invalid proof accepted in wasm");
+    println!("invalid proof rejected");
+}
+
+fn run(
+    engine: &Engine,
+    module: &Module,
+    pp: &impl CanonicalSerialize,
+    statement: &impl CanonicalSerialize,
+    proof: &impl CanonicalSerialize,
+) -> i32 {
+    let mut store = Store::new(engine, ());
+    let instance = Linker::<()>::new(engine)
+        .instantiate(&mut store, module)
+        .and_then(|pre| pre.start(&mut store))
+        .expect("failed to instantiate the wasm module");
+
+    let pp = write_input(&mut store, &instance, pp);
+    let statement = write_input(&mut store, &instance, statement);
+    let proof = write_input(&mut store, &instance, proof);
+
+    instance
+        .get_typed_func::<(u32, u32, u32, u32, u32, u32), i32>(&store, "isep_verify")
+        .expect("missing export isep_verify")
+        .call(&mut store, (pp.0, pp.1, statement.0, statement.1, proof.0, proof.1))
+        .expect("wasm trapped during verification")
+}
+
+fn write_input(
+    store: &mut Store<()>,
+    instance: &Instance,
+    input: &impl CanonicalSerialize,
+) -> (u32, u32) {
+    let mut bytes = Vec::new();
+    input.serialize_compressed(&mut bytes).unwrap();
+    let len = bytes.len() as u32;
+
+    let ptr = instance
+        .get_typed_func::<u32, u32>(&*store, "isep_alloc")
+        .expect("missing export isep_alloc")
+        .call(&mut *store, len)
+        .expect("wasm trapped during allocation");
+    instance
+        .get_memory(&*store, "memory")
+        .expect("missing export memory")
+        .write(&mut *store, ptr as usize, &bytes)
+        .expect("failed to write into wasm memory");
+
+    (ptr, len)
+}
@@ -0,0 +1,54 @@
+//! Builds `wasm/verifier` for `wasm32-unknown-unknown` and runs it through the runner binary.
+//!
+//! Skips with a notice when the target is not installed, so `cargo test` stays usable on
+//! toolchains without it. CI installs the target and runs this test.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const TARGET: &str = "wasm32-unknown-unknown";
+
+fn target_installed() -> bool {
+    let output = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into()))
+        .args(["--print", "sysroot"])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            let sysroot = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+            Path::new(&sysroot).join("lib/rustlib").join(TARGET).exists()
+        }
+        _ => false,
+    }
+}
+
+#[test]
+fn verifier_runs_in_wasm() {
+    if !target_installed() {
+        eprintln!("skipping: the {TARGET} target is not installed (rustup target add {TARGET})");
+        return;
+    }
+
+    let verifier_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../verifier");
+    let target_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("verifier");
+    let status = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .args(["build", "--release", "--target", TARGET, "--manifest-path"])
+        .arg(verifier_dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "failed to build the wasm verifier");
+
+    let wasm = target_dir.join(TARGET).join("release/isep_wasm_verifier.wasm");
+    let output = Command::new(env!("CARGO_BIN_EXE_isep_wasm_runner"))
+        .arg(&wasm)
+        .output()
+        .expect("failed to run the wasm runner");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "wasm runner failed:\n{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("valid proof accepted"));
+    assert!(stdout.contains("invalid proof rejected"));
+}
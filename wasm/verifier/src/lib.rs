@@ -0,0 +1,116 @@
+//! `no_std` ISEP verifier exported to WebAssembly hosts.
+//!
+//! The host copies the serialized public parameters, statement and proof into linear memory
+//! through `isep_alloc`, then calls `isep_verify`, which returns one of the `VERIFY_*` codes.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use ark_bn254::Bn254;
+use ark_isep::prover::Proof;
+use ark_isep::public_parameters::PublicParameters;
+use ark_isep::statement::Statement;
+use ark_isep::verifier::verify;
+use ark_serialize::CanonicalDeserialize;
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::UnsafeCell;
+
+pub const VERIFY_OK: i32 = 0;
+pub const VERIFY_MALFORMED_INPUT: i32 = 1;
+pub const VERIFY_REJECTED: i32 = 2;
+
+/// Allocates `len` bytes for the host to write an input into.
+#[no_mangle]
+pub extern "C" fn isep_alloc(len: usize) -> *mut u8 {
+    let mut buf = Vec::<u8>::with_capacity(len);
+    let ptr = buf.as_mut_ptr();
+    core::mem::forget(buf);
+
+    ptr
+}
+
+/// Verifies a proof given the compressed serializations of its inputs.
+///
+/// # Safety
+///
+/// Each `(ptr, len)` pair must describe an initialized region returned by `isep_alloc`.
+#[no_mangle]
+pub unsafe extern "C" fn isep_verify(
+    pp_ptr: *const u8,
+    pp_len: usize,
+    statement_ptr: *const u8,
+    statement_len: usize,
+    proof_ptr: *const u8,
+    proof_len: usize,
+) -> i32 {
+    let pp_bytes = core::slice::from_raw_parts(pp_ptr, pp_len);
+    let statement_bytes = core::slice::from_raw_parts(statement_ptr, statement_len);
+    let proof_bytes = core::slice::from_raw_parts(proof_ptr, proof_len);
+
+    let (pp, statement, proof) = match (
+        PublicParameters::<Bn254>::deserialize_compressed(pp_bytes),
+        Statement::<Bn254>::deserialize_compressed(statement_bytes),
+        Proof::<Bn254>::deserialize_compressed(proof_bytes),
+    ) {
+        (Ok(pp), Ok(statement), Ok(proof)) => (pp, statement, proof),
+        _ => return VERIFY_MALFORMED_INPUT,
+    };
+
+    match verify::<Bn254>(&pp, &statement, &proof) {
+        Ok(()) => VERIFY_OK,
+        Err(_) => VERIFY_REJECTED,
+    }
+}
+
+/// Bump allocator over the wasm linear memory. Verification is a one-shot computation, so
+/// memory is never reclaimed.
+struct BumpAllocator {
+    next: UnsafeCell<usize>,
+    end: UnsafeCell<usize>,
+}
+
+// The wasm32 target is single-threaded.
+unsafe impl Sync for BumpAllocator {}
+
+const PAGE_SIZE: usize = 64 * 1024;
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let next = &mut *self.next.get();
+        let end = &mut *self.end.get();
+
+        let start = (*next + layout.align() - 1) & !(layout.align() - 1);
+        let new_next = start + layout.size();
+        if new_next > *end {
+            let num_pages = (new_next - *end).div_ceil(PAGE_SIZE);
+            let prev_pages = core::arch::wasm32::memory_grow(0, num_pages);
+            if prev_pages == usize::MAX {
+                return core::ptr::null_mut();
+            }
+            if *end != prev_pages * PAGE_SIZE {
+                // Fresh region not contiguous with the previous one: start over from it.
+                *next = prev_pages * PAGE_SIZE;
+                *end = (prev_pages + num_pages) * PAGE_SIZE;
+                return self.alloc(layout);
+            }
+            *end = (prev_pages + num_pages) * PAGE_SIZE;
+        }
+        *next = new_next;
+
+        start as *mut u8
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = BumpAllocator {
+    next: UnsafeCell::new(0),
+    end: UnsafeCell::new(0),
+};
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    core::arch::wasm32::unreachable()
+}